@@ -6,10 +6,49 @@ use proc_macro_crate::{crate_name, FoundCrate};
 use proc_quote::quote;
 use syn::parse_macro_input;
 use syn::DeriveInput;
-use syn::{Data, DataStruct, Fields, Ident, Type};
+use syn::{
+    Attribute, Data, DataEnum, DataStruct, Expr, ExprLit, ExprUnary, Field, Fields, Ident, Lit,
+    Meta, NestedMeta, UnOp,
+};
 
 /// the macro derives `anim::Animatable` for you automatically.
-#[proc_macro_derive(Animatable, attributes(tag))]
+///
+/// fields are animated in place by `anim::Animatable::animate_in_place()`, so nested structs
+/// are cloned only once per frame.
+///
+/// a field can be interpolated by an `anim::Interpolator` instead of its own `Animatable`:
+/// ```rust,ignore
+/// #[derive(Clone, Animatable)]
+/// struct Needle {
+///     #[anim(interpolator = "anim::animatable::ShortestArc::degrees()")]
+///     angle: f32,
+/// }
+/// ```
+///
+/// fields of a struct can start one after another for a cascading effect, each later than the
+/// previous one by a fraction of the animation time, see `anim::animatable::stagger()`:
+/// ```rust,ignore
+/// #[derive(Clone, Animatable)]
+/// #[anim(stagger = "0.1")]
+/// struct Card {
+///     opacity: f32,
+///     offset: f32,
+///     scale: f32,
+/// }
+/// ```
+/// a literal stagger is checked at compile time, e.g. `"0.5"` of the three fields above fails
+/// to compile, as it leaves no time for them.
+///
+/// enums are animated field by field if both values are the same variant, otherwise they snap
+/// to the target value at the half of the animation time:
+/// ```rust,ignore
+/// #[derive(Clone, Animatable)]
+/// enum Shape {
+///     Circle { r: f32 },
+///     Rect { w: f32, h: f32 },
+/// }
+/// ```
+#[proc_macro_derive(Animatable, attributes(tag, anim))]
 pub fn animatable_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     expand_derive(input)
@@ -19,15 +58,34 @@ pub fn animatable_derive(input: TokenStream) -> TokenStream {
 
 fn expand_derive(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let anim = get_crate()?;
-    let fields = get_fields(input.data)
-        .unwrap()
-        .iter()
-        .map(|(field_name, _)| {
-            Ok(quote! {
-                res.#field_name = #anim::Animatable::animate(&self.#field_name,&to.#field_name, time);
-            })
-        })
-        .collect::<syn::Result<proc_macro2::TokenStream>>()?;
+    let stagger = get_anim_attr(&input.attrs, "stagger")?;
+    let fields = match input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => {
+            let fields = get_fields(fields.named)?;
+            check_stagger(stagger.as_ref(), fields.len())?;
+            let fields = fields
+                .into_iter()
+                .map(|(field_name, interpolator)| {
+                    (
+                        quote! { self.#field_name },
+                        quote! { to.#field_name },
+                        interpolator,
+                    )
+                })
+                .collect::<Vec<_>>();
+            animate_fields(&anim, stagger.as_ref(), &fields)
+        }
+        Data::Enum(data) => expand_enum(&anim, stagger.as_ref(), data)?,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "this derive macro only works on structs with named fields or enums",
+            ))
+        }
+    };
     let st_name = input.ident;
 
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
@@ -37,9 +95,14 @@ fn expand_derive(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
             #[inline]
             fn animate(&self, to: &Self, time: f64) -> Self{
                 let mut res = self.clone();
-                #fields
+                #anim::Animatable::animate_in_place(&mut res, to, time);
                 res
             }
+
+            #[inline]
+            fn animate_in_place(&mut self, to: &Self, time: f64) {
+                #fields
+            }
         }
     })
 }
@@ -55,22 +118,238 @@ fn get_crate() -> syn::Result<Ident> {
     Ok(anim)
 }
 
-fn get_fields(data: Data) -> syn::Result<Vec<(Ident, Type)>> {
-    let fields = match data {
-        Data::Struct(DataStruct {
-            fields: Fields::Named(fields),
-            ..
-        }) => fields.named,
-        _ => panic!("this derive macro only works on structs with named fields"),
+/// animates `(place, target, interpolator)`s in place, where `place` is the field of `self`
+fn animate_fields(
+    anim: &Ident,
+    stagger: Option<&Expr>,
+    fields: &[(
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+        Option<Expr>,
+    )],
+) -> proc_macro2::TokenStream {
+    let count = fields.len();
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, (place, target, interpolator))| {
+            let time = match stagger {
+                Some(stagger) => quote! {
+                    let time = #anim::animatable::stagger(time, #index, #count, #stagger);
+                },
+                None => quote! {},
+            };
+            match interpolator {
+                Some(interpolator) => quote! {{
+                    #time
+                    #place = #anim::Interpolator::interpolate(&(#interpolator), &#place, &#target, time);
+                }},
+                None => quote! {{
+                    #time
+                    #anim::Animatable::animate_in_place(&mut #place, &#target, time);
+                }},
+            }
+        })
+        .collect()
+}
+
+/// same variants are animated field by field, otherwise it snaps at the half
+fn expand_enum(
+    anim: &Ident,
+    stagger: Option<&Expr>,
+    data: DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    // a single variant always matches
+    let fallback = if data.variants.len() == 1 {
+        quote! {}
+    } else {
+        quote! { _ => {} }
     };
-    let items = fields
+    let arms = data
+        .variants
         .into_iter()
-        .map(|f| {
-            let field_name = f.ident.unwrap();
-            let ty = f.ty;
-            (field_name, ty)
+        .map(|variant| {
+            let name = variant.ident;
+            let (fields, named) = match variant.fields {
+                Fields::Named(fields) => (fields.named, true),
+                Fields::Unnamed(fields) => (fields.unnamed, false),
+                Fields::Unit => return Ok(quote! { (Self::#name, Self::#name) => {} }),
+            };
+            let fields = get_fields(fields)?;
+            check_stagger(stagger, fields.len())?;
+            let places: Vec<_> = (0..fields.len())
+                .map(|i| Ident::new(&format!("__self_{}", i), Span::call_site()))
+                .collect();
+            let targets: Vec<_> = (0..fields.len())
+                .map(|i| Ident::new(&format!("__to_{}", i), Span::call_site()))
+                .collect();
+            let (self_pattern, to_pattern) = if named {
+                let names = fields.iter().map(|(field_name, _)| field_name);
+                let names2 = names.clone();
+                (
+                    quote! { Self::#name { #(#names: #places),* } },
+                    quote! { Self::#name { #(#names2: #targets),* } },
+                )
+            } else {
+                (
+                    quote! { Self::#name(#(#places),*) },
+                    quote! { Self::#name(#(#targets),*) },
+                )
+            };
+            let fields = fields
+                .into_iter()
+                .zip(places.iter().zip(targets.iter()))
+                .map(|((_, interpolator), (place, target))| {
+                    (quote! { *#place }, quote! { *#target }, interpolator)
+                })
+                .collect::<Vec<_>>();
+            let body = animate_fields(anim, stagger, &fields);
+            Ok(quote! { (#self_pattern, #to_pattern) => { #body } })
         })
-        .collect();
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote! {
+        if ::core::mem::discriminant(self) != ::core::mem::discriminant(to) {
+            if time >= 0.5 {
+                *self = to.clone();
+            }
+            return;
+        }
+        match (self, to) {
+            #(#arms)*
+            #fallback
+        }
+    })
+}
+
+/// rejects a literal stagger which `anim::animatable::stagger()` would panic on at runtime
+fn check_stagger(stagger: Option<&Expr>, count: usize) -> syn::Result<()> {
+    let value = match stagger.and_then(literal_value) {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+    let span = 1.0 - value * count.saturating_sub(1) as f64;
+    if value >= 0.0 && span > 0.0 && span <= 1.0 {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            stagger,
+            format!("invalid stagger {} of {} fields", value, count),
+        ))
+    }
+}
+
+/// value of a number literal, e.g. `0.1` or `-1`
+fn literal_value(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => lit.base10_parse().ok(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_value(expr).map(|value| -value),
+        _ => None,
+    }
+}
+
+/// names of fields, unnamed fields are numbered, and their interpolators
+fn get_fields(
+    fields: impl IntoIterator<Item = Field>,
+) -> syn::Result<Vec<(proc_macro2::TokenStream, Option<Expr>)>> {
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(index, f)| {
+            let interpolator = get_anim_attr(&f.attrs, "interpolator")?;
+            let field_name = match f.ident {
+                Some(ident) => quote! { #ident },
+                None => {
+                    let index = syn::Index::from(index);
+                    quote! { #index }
+                }
+            };
+            Ok((field_name, interpolator))
+        })
+        .collect()
+}
+
+/// parse `#[anim(key = "expr")]`, e.g. `interpolator` of fields, `stagger` of structs
+fn get_anim_attr(attrs: &[Attribute], key: &str) -> syn::Result<Option<Expr>> {
+    let mut res = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("anim")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[anim(...)]")),
+        };
+        for item in list.nested {
+            match item {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident(key) => match &nv.lit {
+                    Lit::Str(s) => res = Some(s.parse()?),
+                    lit => return Err(syn::Error::new_spanned(lit, "expected a string literal")),
+                },
+                item => return Err(syn::Error::new_spanned(item, "unknown anim attribute")),
+            }
+        }
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::parse_quote;
+
+    fn error(input: DeriveInput) -> String {
+        expand_derive(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_stagger() {
+        let input: DeriveInput = parse_quote! {
+            #[anim(stagger = "0.25")]
+            struct Card { a: f32, b: f32, c: f32 }
+        };
+        assert!(expand_derive(input).is_ok());
+
+        // not known until runtime
+        let input: DeriveInput = parse_quote! {
+            #[anim(stagger = "STAGGER")]
+            struct Card { a: f32, b: f32, c: f32 }
+        };
+        assert!(expand_derive(input).is_ok());
+
+        let input: DeriveInput = parse_quote! {
+            #[anim(stagger = "0.5")]
+            struct Card { a: f32, b: f32, c: f32 }
+        };
+        assert_eq!(error(input), "invalid stagger 0.5 of 3 fields");
+
+        let input: DeriveInput = parse_quote! {
+            #[anim(stagger = "-0.1")]
+            struct Card { a: f32, b: f32 }
+        };
+        assert_eq!(error(input), "invalid stagger -0.1 of 2 fields");
+
+        let input: DeriveInput = parse_quote! {
+            #[anim(stagger = "1")]
+            enum Shape { Point, Circle(f32), Rect { w: f32, h: f32 } }
+        };
+        assert_eq!(error(input), "invalid stagger 1 of 2 fields");
+    }
 
-    Ok(items)
+    #[test]
+    fn test_unsupported() {
+        let input: DeriveInput = parse_quote! {
+            struct Point(f32, f32);
+        };
+        assert_eq!(
+            error(input),
+            "this derive macro only works on structs with named fields or enums"
+        );
+    }
 }
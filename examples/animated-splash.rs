@@ -277,18 +277,12 @@ fn raindrop_animation2() -> impl Animation<Item = Raindrop> {
         .build()
         .delay(duration.mul_f64(0.5));
 
-    drop_size
-        .zip(drop_pos)
-        .zip(drop_visible)
-        .zip(hole_size)
-        .map(
-            |(((drop_size, drop_pos), drop_visible), hole_size)| Raindrop {
-                drop_size,
-                drop_pos,
-                drop_visible,
-                hole_size,
-            },
-        )
+    anim::zip_struct!(Raindrop {
+        drop_size: drop_size,
+        drop_pos: drop_pos,
+        drop_visible: drop_visible,
+        hole_size: hole_size,
+    })
 }
 
 /// demo key-frames, requires Raindrop animatable
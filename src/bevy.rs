@@ -0,0 +1,267 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use crate::core::{math, timeline::Status, Animatable, Clock, DefaultClock, PathPoint, Timeline};
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        component::{Component, TableStorage},
+        system::Query,
+    },
+    math::{Quat, Vec2, Vec3},
+    transform::components::Transform,
+    utils::synccell::SyncCell,
+};
+use core::{fmt, marker::PhantomData};
+
+impl Animatable for Vec2 {
+    #[inline]
+    fn animate(&self, to: &Self, time: f64) -> Self {
+        let x = self.x.animate(&to.x, time);
+        let y = self.y.animate(&to.y, time);
+        Vec2::new(x, y)
+    }
+}
+
+impl PathPoint for Vec2 {
+    #[inline]
+    fn distance(&self, other: &Self) -> f64 {
+        let (dx, dy) = ((other.x - self.x) as f64, (other.y - self.y) as f64);
+        math::sqrt(dx * dx + dy * dy)
+    }
+}
+
+impl Animatable for Vec3 {
+    #[inline]
+    fn animate(&self, to: &Self, time: f64) -> Self {
+        let x = self.x.animate(&to.x, time);
+        let y = self.y.animate(&to.y, time);
+        let z = self.z.animate(&to.z, time);
+        Vec3::new(x, y, z)
+    }
+}
+
+impl PathPoint for Vec3 {
+    #[inline]
+    fn distance(&self, other: &Self) -> f64 {
+        let (dx, dy, dz) = (
+            (other.x - self.x) as f64,
+            (other.y - self.y) as f64,
+            (other.z - self.z) as f64,
+        );
+        math::sqrt(dx * dx + dy * dy + dz * dz)
+    }
+}
+
+/// spherical linear interpolation
+impl Animatable for Quat {
+    #[inline]
+    fn animate(&self, to: &Self, time: f64) -> Self {
+        self.slerp(*to, time as f32)
+    }
+}
+
+impl Animatable for Transform {
+    #[inline]
+    fn animate(&self, to: &Self, time: f64) -> Self {
+        Transform {
+            translation: self.translation.animate(&to.translation, time),
+            rotation: self.rotation.animate(&to.rotation, time),
+            scale: self.scale.animate(&to.scale, time),
+        }
+    }
+}
+
+/// a [`Timeline`] attached to an entity, ticked by [`TimelinePlugin`] every frame;
+/// read the latest value by [`TimelineComponent::value()`] in your own systems.
+///
+/// ## Example
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use anim::{tick_timelines, DefaultClock, Options, TimelineComponent, TimelinePlugin};
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let from = Transform::default();
+///     let to = Transform::from_xyz(100.0, 0.0, 0.0);
+///     let timeline = Options::new(from, to)
+///         .duration(Duration::from_millis(500))
+///         .begin_animation();
+///     commands.spawn((from, TimelineComponent::new(timeline)));
+/// }
+///
+/// fn apply(mut query: Query<(&mut Transform, &TimelineComponent<Transform>)>) {
+///     for (mut transform, timeline) in &mut query {
+///         if timeline.status().is_animating() {
+///             *transform = *timeline.value();
+///         }
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(TimelinePlugin::<Transform>::default())
+///     .add_systems(Startup, setup)
+///     .add_systems(Update, apply.after(tick_timelines::<Transform, DefaultClock>))
+///     .run();
+/// ```
+pub struct TimelineComponent<T, C: Clock = DefaultClock> {
+    timeline: SyncCell<Timeline<T, C>>,
+    value: T,
+    status: Status,
+}
+
+impl<T, C: Clock> TimelineComponent<T, C> {
+    /// attach `timeline`
+    #[inline]
+    pub fn new(timeline: Timeline<T, C>) -> Self {
+        let value = timeline.value();
+        let status = timeline.status();
+        Self {
+            timeline: SyncCell::new(timeline),
+            value,
+            status,
+        }
+    }
+
+    /// the timeline, e.g. to begin or stop it
+    #[inline]
+    pub fn timeline_mut(&mut self) -> &mut Timeline<T, C> {
+        self.timeline.get()
+    }
+
+    /// value observed by the last update
+    #[inline]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// status observed by the last update
+    #[inline]
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// update the timeline, see [`Timeline::update()`]
+    #[inline]
+    pub fn update(&mut self) -> Status {
+        let timeline = self.timeline.get();
+        self.status = timeline.update();
+        self.value = timeline.value();
+        self.status
+    }
+}
+
+impl<T, C: Clock> From<Timeline<T, C>> for TimelineComponent<T, C> {
+    #[inline]
+    fn from(timeline: Timeline<T, C>) -> Self {
+        Self::new(timeline)
+    }
+}
+
+impl<T, C> Component for TimelineComponent<T, C>
+where
+    T: Send + Sync + 'static,
+    C: Clock + 'static,
+    Timeline<T, C>: Send,
+{
+    type Storage = TableStorage;
+}
+
+impl<T: fmt::Debug, C: Clock> fmt::Debug for TimelineComponent<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimelineComponent")
+            .field("value", &self.value)
+            .field("status", &self.status)
+            .finish_non_exhaustive()
+    }
+}
+
+/// the system which ticks all the [`TimelineComponent`]s of `T`
+pub fn tick_timelines<T, C>(mut query: Query<&mut TimelineComponent<T, C>>)
+where
+    T: Send + Sync + 'static,
+    C: Clock + 'static,
+    Timeline<T, C>: Send,
+{
+    for mut timeline in &mut query {
+        timeline.update();
+    }
+}
+
+/// ticks [`TimelineComponent`]s of `T` in the [`Update`] schedule, see [`tick_timelines()`];
+/// add one for every animated type
+pub struct TimelinePlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for TimelinePlugin<T> {
+    #[inline]
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> fmt::Debug for TimelinePlugin<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimelinePlugin").finish()
+    }
+}
+
+impl<T: Send + Sync + 'static> Plugin for TimelinePlugin<T>
+where
+    Timeline<T>: Send,
+{
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, tick_timelines::<T, DefaultClock>);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::{easing, Options},
+        test::MockClock as TestClock,
+    };
+    use core::time::Duration;
+
+    #[test]
+    fn test_tick_timelines() {
+        let at = Duration::from_millis;
+        let mut timeline: Timeline<Vec2, TestClock> =
+            Options::new(Vec2::ZERO, Vec2::new(10.0, 20.0))
+                .easing(easing::linear())
+                .duration(at(100))
+                .into();
+        timeline.begin();
+
+        let mut app = App::new();
+        app.add_systems(Update, tick_timelines::<Vec2, TestClock>);
+        let entity = app.world.spawn(TimelineComponent::new(timeline)).id();
+        app.update();
+        let component = app
+            .world
+            .get::<TimelineComponent<Vec2, TestClock>>(entity)
+            .unwrap();
+        assert_eq!(component.status(), Status::Animating);
+        assert_eq!(component.value(), &Vec2::ZERO);
+
+        app.world
+            .get_mut::<TimelineComponent<Vec2, TestClock>>(entity)
+            .unwrap()
+            .timeline_mut()
+            .clock_mut()
+            .advance(at(50));
+        app.update();
+        let component = app
+            .world
+            .get::<TimelineComponent<Vec2, TestClock>>(entity)
+            .unwrap();
+        assert_eq!(component.value(), &Vec2::new(5.0, 10.0));
+
+        let transform = Transform::IDENTITY.animate(&Transform::from_xyz(2.0, 0.0, 0.0), 0.5);
+        assert_eq!(transform.translation, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(Quat::IDENTITY.animate(&Quat::IDENTITY, 0.5), Quat::IDENTITY);
+    }
+}
@@ -6,7 +6,9 @@
 
 #![allow(non_snake_case)]
 
-use std::marker::PhantomData;
+use crate::core::{easing::Function, math, MaybeSend, MaybeSync};
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+use core::{fmt, marker::PhantomData};
 
 ///  generates output values based on its timing progress
 ///
@@ -32,9 +34,233 @@ use std::marker::PhantomData;
 /// - `Option<T>` where `T:Animatable`
 /// - `PhantomData<T>`
 /// - `[T;N]` where `T:Animatable`
+/// - `Box<T>`, `Rc<T>`, `Arc<T>` where `T:Animatable`
 pub trait Animatable: Sized + Clone {
     /// generates output values based on its timing progress
     fn animate(&self, to: &Self, time: f64) -> Self;
+
+    /// same as [`Animatable::animate()`], but overwrites `self`, which is the start value,
+    /// with the output; large values, e.g. derived structs, avoid clones and allocations
+    #[inline]
+    fn animate_in_place(&mut self, to: &Self, time: f64) {
+        *self = self.animate(to, time);
+    }
+}
+
+/// interpolation strategy, which can be chosen per animation instead of the inherent
+/// [`Animatable::animate()`], e.g. [`ShortestArc`] for angles, without newtypes.
+///
+/// closures of `Fn(&T, &T, f64) -> T` are interpolators.
+///
+/// see [`crate::Options::interpolator()`], [`crate::builder::key_frames_with()`]
+pub trait Interpolator<T> {
+    /// generates output values between `from` and `to` based on the normalized `time`
+    fn interpolate(&self, from: &T, to: &T, time: f64) -> T;
+}
+
+impl<T, F: Fn(&T, &T, f64) -> T> Interpolator<T> for F {
+    #[inline]
+    fn interpolate(&self, from: &T, to: &T, time: f64) -> T {
+        self(from, to, time)
+    }
+}
+
+/// the inherent [`Animatable::animate()`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lerp;
+
+impl<T: Animatable> Interpolator<T> for Lerp {
+    #[inline]
+    fn interpolate(&self, from: &T, to: &T, time: f64) -> T {
+        from.animate(to, time)
+    }
+}
+
+/// interpolates angles along the shortest arc, e.g. from 350° to 10° passes 0° instead of 180°.
+///
+/// the outputs are not wrapped into the period, e.g. 350° to 10° ends at 370°
+#[derive(Debug, Clone, Copy)]
+pub struct ShortestArc {
+    period: f64,
+}
+
+impl ShortestArc {
+    /// angles in degrees
+    #[inline]
+    pub fn degrees() -> Self {
+        Self::period(360.0)
+    }
+
+    /// angles in radians
+    #[inline]
+    pub fn radians() -> Self {
+        Self::period(core::f64::consts::PI * 2.0)
+    }
+
+    /// angles of a full turn of `period`
+    ///
+    /// panics if period is not positive
+    #[inline]
+    pub fn period(period: f64) -> Self {
+        assert!(period > 0.0);
+        Self { period }
+    }
+}
+
+macro_rules! impl_shortest_arc {
+    ($ty:ident) => {
+        impl Interpolator<$ty> for ShortestArc {
+            #[inline]
+            fn interpolate(&self, from: &$ty, to: &$ty, time: f64) -> $ty {
+                let delta = (*to - *from) as f64;
+                let delta = delta - self.period * math::floor(delta / self.period + 0.5);
+                (*from as f64 + delta * time) as $ty
+            }
+        }
+    };
+}
+
+impl_shortest_arc!(f32);
+impl_shortest_arc!(f64);
+
+/// eases each axis of 2D values by its own function, e.g. x linear and y bouncing for arc-like
+/// motion.
+///
+/// implemented for `(A, B)`, `[T; 2]`, and `Point`, `Vector`, `Size` of iced;
+/// see [`crate::Options::axis_easing()`]
+#[derive(Clone)]
+pub struct AxisEasing<X, Y> {
+    x: X,
+    y: Y,
+}
+
+impl<X: Function, Y: Function> AxisEasing<X, Y> {
+    /// ease x by `x`, y by `y`
+    #[inline]
+    pub fn new(x: X, y: Y) -> Self {
+        Self { x, y }
+    }
+
+    /// interpolate each axis by its eased time
+    #[inline]
+    pub fn axes<A: Animatable, B: Animatable>(
+        &self,
+        from: (&A, &B),
+        to: (&A, &B),
+        time: f64,
+    ) -> (A, B) {
+        (
+            from.0.animate(to.0, self.x.ease(time)),
+            from.1.animate(to.1, self.y.ease(time)),
+        )
+    }
+}
+
+impl<A, B, X, Y> Interpolator<(A, B)> for AxisEasing<X, Y>
+where
+    A: Animatable,
+    B: Animatable,
+    X: Function,
+    Y: Function,
+{
+    #[inline]
+    fn interpolate(&self, from: &(A, B), to: &(A, B), time: f64) -> (A, B) {
+        self.axes((&from.0, &from.1), (&to.0, &to.1), time)
+    }
+}
+
+impl<T: Animatable, X: Function, Y: Function> Interpolator<[T; 2]> for AxisEasing<X, Y> {
+    #[inline]
+    fn interpolate(&self, from: &[T; 2], to: &[T; 2], time: f64) -> [T; 2] {
+        let (x, y) = self.axes((&from[0], &from[1]), (&to[0], &to[1]), time);
+        [x, y]
+    }
+}
+
+impl<X, Y> fmt::Debug for AxisEasing<X, Y> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AxisEasing")
+    }
+}
+
+/// normalized time of the `index`th of `count` parts, each starts `stagger` later than the
+/// previous one, e.g. fields of a struct deriving [`Animatable`] with `#[anim(stagger = "0.1")]`.
+///
+/// every part runs for the same span of `1 - stagger * (count - 1)`, so it ends `stagger` later
+/// than the previous one too; the last part ends at 1.
+///
+/// overshoots of `time` beyond 0..=1, e.g. by elastic easing, are kept;
+/// panics unless `0 <= stagger * (count - 1) < 1`
+///
+/// ## Example
+/// ```rust
+/// use anim::animatable::stagger;
+///
+/// assert_eq!(stagger(0.25, 0, 3, 0.25), 0.5);
+/// assert_eq!(stagger(0.25, 1, 3, 0.25), 0.0);
+/// assert_eq!(stagger(1.0, 2, 3, 0.25), 1.0);
+/// ```
+#[inline]
+pub fn stagger(time: f64, index: usize, count: usize, stagger: f64) -> f64 {
+    let span = 1.0 - stagger * count.saturating_sub(1) as f64;
+    assert!(
+        stagger >= 0.0 && span > 0.0 && span <= 1.0,
+        "invalid stagger {} of {} parts",
+        stagger,
+        count
+    );
+    let local = (time - stagger * index as f64) / span;
+    local.max(time.min(0.0)).min(time.max(1.0))
+}
+
+/// shared [`Interpolator`], see [`DynInterpolator`]
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+type ArcInterpolator<T> = Arc<dyn Interpolator<T>>;
+/// shared [`Interpolator`], which can be shared across threads
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+type ArcInterpolator<T> = Arc<dyn Interpolator<T> + Send + Sync>;
+
+/// an optional [`Interpolator`], which falls back to [`Animatable::animate()`]
+pub(crate) struct DynInterpolator<T>(Option<ArcInterpolator<T>>);
+
+impl<T: Animatable> DynInterpolator<T> {
+    #[inline]
+    pub(crate) fn new(
+        interpolator: impl Interpolator<T> + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        Self(Some(Arc::new(interpolator)))
+    }
+
+    #[inline]
+    pub(crate) fn interpolate(&self, from: &T, to: &T, time: f64) -> T {
+        match &self.0 {
+            Some(interpolator) => interpolator.interpolate(from, to, time),
+            None => from.animate(to, time),
+        }
+    }
+}
+
+impl<T> Default for DynInterpolator<T> {
+    #[inline]
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Clone for DynInterpolator<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for DynInterpolator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "Custom"),
+            None => write!(f, "Lerp"),
+        }
+    }
 }
 
 //-------- primitives -----------
@@ -46,7 +272,7 @@ macro_rules! impl_primitive {
                 if time == 0.0 {
                     return *self;
                 }
-                if (1.0 - time).abs() < f64::EPSILON {
+                if math::abs(1.0 - time) < f64::EPSILON {
                     return *to;
                 }
                 if self == to {
@@ -54,10 +280,14 @@ macro_rules! impl_primitive {
                 }
                 crate::utils::check_time(time);
                 let v = (*self as f64) * (1.0 - time) + (*to as f64) * time;
-                if *to >= *self {
-                    (v + 0.5) as Self
+                let v = if *to >= *self { v + 0.5 } else { v - 0.5 };
+                // easing might overshoot, saturate instead of wrapping
+                if v <= Self::MIN as f64 {
+                    Self::MIN
+                } else if v >= Self::MAX as f64 {
+                    Self::MAX
                 } else {
-                    (v - 0.5) as Self
+                    v as Self
                 }
             }
         }
@@ -69,10 +299,10 @@ macro_rules! impl_primitive {
                 if time == 0.0 {
                     return *self;
                 }
-                if (1.0 - time).abs() < f64::EPSILON {
+                if math::abs(1.0 - time) < f64::EPSILON {
                     return *to;
                 }
-                if (self - to).abs() < $ty::EPSILON {
+                if math::abs((self - to) as f64) < $ty::EPSILON as f64 {
                     return *self;
                 }
                 crate::utils::check_time(time);
@@ -110,26 +340,40 @@ impl Animatable for bool {
     }
 }
 
+/// surrogate code points, which are not valid chars
+const SURROGATE_START: u32 = 0xD800;
+const SURROGATE_SIZE: u32 = 0x800;
+
 impl Animatable for char {
+    /// interpolates in either direction, skipping surrogate code points
     #[inline]
     fn animate(&self, to: &Self, time: f64) -> Self {
-        if self == to {
+        if self == to || time == 0.0 {
             return *self;
         }
+        if math::abs(1.0 - time) < f64::EPSILON {
+            return *to;
+        }
+        crate::utils::check_time(time);
 
-        let from_idx = *self as u32;
-        let to_idx = *to as u32;
-        let idx = from_idx.animate(&to_idx, time);
-        let n = if from_idx > to_idx {
-            from_idx - idx
+        // position among valid chars
+        let pos = |c: char| {
+            let c = c as u32;
+            if c < SURROGATE_START {
+                c
+            } else {
+                c - SURROGATE_SIZE
+            }
+        };
+        let from = pos(*self) as f64;
+        let to = pos(*to) as f64;
+        let idx = math::floor(from + (to - from) * time + 0.5) as u32;
+        let idx = if idx < SURROGATE_START {
+            idx
         } else {
-            idx - from_idx
+            idx + SURROGATE_SIZE
         };
-        let mut rng = *self..=*to;
-        match rng.nth(n as usize) {
-            Some(c) => c,
-            None => *self,
-        }
+        core::char::from_u32(idx).unwrap_or(*self)
     }
 }
 
@@ -145,16 +389,61 @@ impl<T> Animatable for PhantomData<T> {
     }
 }
 
+/// interpolates if both are `Some`, otherwise it snaps to `to` at the half of the time
 impl<T: Animatable> Animatable for Option<T> {
     #[inline]
     fn animate(&self, to: &Self, time: f64) -> Self {
         match (self, to) {
             (Some(a), Some(b)) => Some(a.animate(b, time)),
-            _ => None,
+            _ if time < 0.5 => self.clone(),
+            _ => to.clone(),
+        }
+    }
+
+    #[inline]
+    fn animate_in_place(&mut self, to: &Self, time: f64) {
+        match (self.as_mut(), to) {
+            (Some(a), Some(b)) => a.animate_in_place(b, time),
+            _ if time < 0.5 => {}
+            _ => *self = to.clone(),
         }
     }
 }
 
+impl<T: Animatable> Animatable for Box<T> {
+    #[inline]
+    fn animate(&self, to: &Self, time: f64) -> Self {
+        Box::new((**self).animate(to, time))
+    }
+
+    #[inline]
+    fn animate_in_place(&mut self, to: &Self, time: f64) {
+        (**self).animate_in_place(to, time)
+    }
+}
+
+macro_rules! impl_shared_pointer {
+    ($ty:ident) => {
+        /// allocates only if the output differs from both ends, i.e. the pointer is reused
+        /// at the start or the end, or if both ends share the same pointer
+        impl<T: Animatable> Animatable for $ty<T> {
+            #[inline]
+            fn animate(&self, to: &Self, time: f64) -> Self {
+                if $ty::ptr_eq(self, to) || time == 0.0 {
+                    self.clone()
+                } else if time == 1.0 {
+                    to.clone()
+                } else {
+                    $ty::new((**self).animate(to, time))
+                }
+            }
+        }
+    };
+}
+
+impl_shared_pointer!(Rc);
+impl_shared_pointer!(Arc);
+
 impl<T: Animatable, const N: usize> Animatable for [T; N] {
     #[inline]
     fn animate(&self, to: &Self, time: f64) -> Self {
@@ -165,6 +454,13 @@ impl<T: Animatable, const N: usize> Animatable for [T; N] {
             .for_each(|((a, b), c)| *c = a.animate(b, time));
         res
     }
+
+    #[inline]
+    fn animate_in_place(&mut self, to: &Self, time: f64) {
+        self.iter_mut()
+            .zip(to.iter())
+            .for_each(|(a, b)| a.animate_in_place(b, time));
+    }
 }
 
 //-------- tuples -----------
@@ -206,7 +502,55 @@ impl_tuple!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T
 
 #[cfg(test)]
 mod test {
-    use crate::Animatable;
+    use super::{AxisEasing, Interpolator, Lerp, ShortestArc};
+    use crate::{easing, Animatable, Function};
+    use alloc::boxed::Box;
+
+    macro_rules! check_overshoot {
+        ($ty:ident) => {{
+            let funcs: [Box<dyn Function>; 4] = [
+                Box::new(easing::back_ease(1.70158)),
+                Box::new(easing::back_ease(1.70158).mode(easing::EasingMode::Out)),
+                Box::new(easing::back_ease(1.70158).mode(easing::EasingMode::InOut)),
+                Box::new(easing::elastic_ease().mode(easing::EasingMode::Out)),
+            ];
+            let ranges = [(0, 100), (100, 0), (0, $ty::MAX), ($ty::MAX, 0), (10, 20)];
+            for f in funcs.iter() {
+                for (from, to) in ranges.iter() {
+                    for i in 0..=100 {
+                        let time = f.ease(i as f64 / 100.0);
+                        let v = from.animate(to, time);
+                        let expected = (*from as f64) + (*to as f64 - *from as f64) * time;
+                        let expected = expected.max(0.0).min($ty::MAX as f64);
+                        // descending ranges round half down, then truncate
+                        assert!(
+                            (v as f64 - expected).abs() < 1.5,
+                            "{}: {} -> {} at {}, got {}",
+                            stringify!($ty),
+                            from,
+                            to,
+                            time,
+                            v
+                        );
+                    }
+                }
+            }
+        }};
+    }
+
+    #[test]
+    fn test_unsigned_overshoot() {
+        check_overshoot!(u8);
+        check_overshoot!(u16);
+        check_overshoot!(u32);
+    }
+
+    #[test]
+    fn test_descending_rounding() {
+        assert_eq!(10_u8.animate(&0, 0.25), 7);
+        assert_eq!(0_i32.animate(&-10, 0.25), -3);
+        assert_eq!(0_i32.animate(&10, 0.25), 3);
+    }
 
     #[test]
     fn test_bool() {
@@ -239,5 +583,218 @@ mod test {
 
         let v = 'a'.animate(&'e', 1.0);
         assert_eq!(v, 'e');
+
+        let v = 'e'.animate(&'a', 0.25);
+        assert_eq!(v, 'd');
+
+        let v = 'e'.animate(&'a', 0.5);
+        assert_eq!(v, 'c');
+
+        let v = 'e'.animate(&'a', 1.0);
+        assert_eq!(v, 'a');
+
+        // skips surrogate code points
+        let v = '\u{D7FF}'.animate(&'\u{E001}', 0.5);
+        assert_eq!(v, '\u{E000}');
+    }
+
+    #[test]
+    fn test_interpolator() {
+        assert_eq!(Lerp.interpolate(&0.0, &10.0, 0.5), 5.0);
+
+        let arc = ShortestArc::degrees();
+        assert_eq!(arc.interpolate(&350.0f32, &10.0, 0.5), 360.0);
+        assert_eq!(arc.interpolate(&10.0f64, &350.0, 0.5), 0.0);
+        assert_eq!(arc.interpolate(&0.0f64, &90.0, 0.5), 45.0);
+
+        let axes = AxisEasing::new(easing::linear(), easing::custom(|t| t * t));
+        assert_eq!(axes.interpolate(&(0.0, 0.0), &(1.0, 1.0), 0.5), (0.5, 0.25));
+        assert_eq!(axes.interpolate(&[0.0, 2.0], &[1.0, 4.0], 0.5), [0.5, 2.5]);
+
+        let snap = |from: &u8, to: &u8, time: f64| if time < 0.5 { *from } else { *to };
+        assert_eq!(snap.interpolate(&1, &2, 0.4), 1);
+        assert_eq!(snap.interpolate(&1, &2, 0.6), 2);
+    }
+
+    #[test]
+    fn test_option() {
+        assert_eq!(Some(0.0).animate(&Some(2.0), 0.25), Some(0.5));
+        assert_eq!(None.animate(&Some(2.0), 0.25), None);
+        assert_eq!(None.animate(&Some(2.0), 0.5), Some(2.0));
+        assert_eq!(Some(2.0).animate(&None, 0.25), Some(2.0));
+        assert_eq!(Some(2.0).animate(&None, 0.75), None);
+
+        let mut value = Some(0.0);
+        value.animate_in_place(&Some(2.0), 0.5);
+        assert_eq!(value, Some(1.0));
+        value.animate_in_place(&None, 0.5);
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_pointers() {
+        use alloc::{rc::Rc, sync::Arc};
+
+        let (from, to) = (Arc::new(0.0), Arc::new(1.0));
+        assert_eq!(*from.animate(&to, 0.5), 0.5);
+        assert!(Arc::ptr_eq(&from.animate(&to, 0.0), &from));
+        assert!(Arc::ptr_eq(&from.animate(&to, 1.0), &to));
+        assert!(Arc::ptr_eq(&from.animate(&from.clone(), 0.5), &from));
+
+        let (from, to) = (Rc::new([0.0, 2.0]), Rc::new([1.0, 4.0]));
+        assert_eq!(*from.animate(&to, 0.5), [0.5, 3.0]);
+        assert_eq!(*Box::new(0.0).animate(&Box::new(2.0), 0.5), 1.0);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_interpolator() {
+        #[derive(Debug, Clone, PartialEq, Animatable)]
+        struct Needle {
+            #[anim(interpolator = "ShortestArc::degrees()")]
+            angle: f32,
+            length: f32,
+        }
+
+        let from = Needle {
+            angle: 350.0,
+            length: 0.0,
+        };
+        let to = Needle {
+            angle: 10.0,
+            length: 10.0,
+        };
+        assert_eq!(
+            from.animate(&to, 0.5),
+            Needle {
+                angle: 360.0,
+                length: 5.0
+            }
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_in_place() {
+        #[derive(Debug, Clone, PartialEq, Animatable)]
+        struct Particle {
+            position: [f32; 2],
+            #[anim(interpolator = "ShortestArc::degrees()")]
+            angle: f32,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Animatable)]
+        struct Emitter {
+            particles: Box<[Particle; 2]>,
+            alpha: f64,
+        }
+
+        let particle = |x, angle| Particle {
+            position: [x, x * 2.0],
+            angle,
+        };
+        let from = Emitter {
+            particles: Box::new([particle(0.0, 350.0), particle(2.0, 0.0)]),
+            alpha: 0.0,
+        };
+        let to = Emitter {
+            particles: Box::new([particle(2.0, 10.0), particle(4.0, 90.0)]),
+            alpha: 1.0,
+        };
+        let expected = Emitter {
+            particles: Box::new([particle(1.0, 360.0), particle(3.0, 45.0)]),
+            alpha: 0.5,
+        };
+        assert_eq!(from.animate(&to, 0.5), expected);
+
+        let mut value = from;
+        value.animate_in_place(&to, 0.5);
+        assert_eq!(value, expected);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_enum() {
+        #[derive(Debug, Clone, PartialEq, Animatable)]
+        enum Shape {
+            Circle {
+                r: f32,
+            },
+            Rect {
+                w: f32,
+                #[anim(interpolator = "Lerp")]
+                h: f32,
+            },
+            Polygon(u8, f32),
+            Empty,
+        }
+
+        let (small, large) = (Shape::Circle { r: 1.0 }, Shape::Circle { r: 3.0 });
+        assert_eq!(small.animate(&large, 0.5), Shape::Circle { r: 2.0 });
+        let (from, to) = (
+            Shape::Rect { w: 0.0, h: 2.0 },
+            Shape::Rect { w: 2.0, h: 4.0 },
+        );
+        assert_eq!(from.animate(&to, 0.5), Shape::Rect { w: 1.0, h: 3.0 });
+        let (from, to) = (Shape::Polygon(3, 0.0), Shape::Polygon(5, 1.0));
+        assert_eq!(from.animate(&to, 0.5), Shape::Polygon(4, 0.5));
+        assert_eq!(Shape::Empty.animate(&Shape::Empty, 0.5), Shape::Empty);
+
+        // different variants snap
+        assert_eq!(small.animate(&to, 0.4), small);
+        assert_eq!(small.animate(&to, 0.5), to);
+        let mut value = small.clone();
+        value.animate_in_place(&Shape::Empty, 0.9);
+        assert_eq!(value, Shape::Empty);
+
+        #[derive(Debug, Clone, PartialEq, Animatable)]
+        enum Single {
+            Value(f64),
+        }
+        assert_eq!(
+            Single::Value(0.0).animate(&Single::Value(1.0), 0.25),
+            Single::Value(0.25)
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_stagger() {
+        #[derive(Debug, Clone, PartialEq, Animatable)]
+        #[anim(stagger = "0.25")]
+        struct Card {
+            opacity: f64,
+            #[anim(interpolator = "Lerp")]
+            offset: f64,
+            scale: f64,
+        }
+
+        let from = Card {
+            opacity: 0.0,
+            offset: 0.0,
+            scale: 0.0,
+        };
+        let to = Card {
+            opacity: 1.0,
+            offset: 1.0,
+            scale: 1.0,
+        };
+        assert_eq!(
+            from.animate(&to, 0.25),
+            Card {
+                opacity: 0.5,
+                offset: 0.0,
+                scale: 0.0,
+            }
+        );
+        assert_eq!(
+            from.animate(&to, 0.75),
+            Card {
+                opacity: 1.0,
+                offset: 1.0,
+                scale: 0.5,
+            }
+        );
+        assert_eq!(from.animate(&to, 1.0), to);
     }
 }
@@ -4,20 +4,42 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
-use std::{fmt, time::Duration};
+use super::{AnimNode, Animation, BaseAnimation};
+use crate::core::MaybeSend;
+use alloc::{boxed::Box, vec::Vec};
+use core::{any::TypeId, fmt, time::Duration};
+
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+type DynAnimation<T> = dyn Animation<Item = T> + Send;
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+type DynAnimation<T> = dyn Animation<Item = T>;
 
 /// wrapper for boxed [`Animation`]
-pub struct Boxed<T>(Box<dyn Animation<Item = T>>);
+pub struct Boxed<T>(Box<DynAnimation<T>>, TypeId);
 
 impl<T> Boxed<T> {
     /// construct [`Boxed`]
     #[inline]
     pub(crate) fn new<F>(src: F) -> Self
     where
-        F: Animation<Item = T> + 'static,
+        F: Animation<Item = T> + MaybeSend + 'static,
     {
-        Self(Box::new(src))
+        Self(Box::new(src), TypeId::of::<F>())
+    }
+
+    /// replace the animation; the allocation is reused if it's of the same type
+    #[inline]
+    pub(crate) fn replace<F>(&mut self, src: F)
+    where
+        F: Animation<Item = T> + MaybeSend + 'static,
+    {
+        if self.1 == TypeId::of::<F>() {
+            let ptr = &mut *self.0 as *mut DynAnimation<T> as *mut F;
+            // SAFETY: the boxed value is of type `F`, checked by its type id
+            unsafe { *ptr = src };
+        } else {
+            *self = Self::new(src);
+        }
     }
 }
 
@@ -32,10 +54,30 @@ impl<T> BaseAnimation for Boxed<T> {
     fn animate(&self, elapsed: Duration) -> Self::Item {
         self.0.animate(elapsed)
     }
+
+    #[inline]
+    fn animate_many(&self, times: &[Duration], output: &mut Vec<Self::Item>) {
+        self.0.animate_many(times, output)
+    }
+
+    #[inline]
+    fn normalized_time(&self, elapsed: Duration) -> Option<(f64, f64)> {
+        self.0.normalized_time(elapsed)
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.0.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        self.0.describe()
+    }
 }
 
 impl<T> fmt::Debug for Boxed<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "BoxedAnimation")
+        f.debug_tuple("Boxed").field(&self.0.describe()).finish()
     }
 }
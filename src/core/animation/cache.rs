@@ -4,11 +4,18 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
-use std::{cell::RefCell, time::Duration};
+use super::{AnimNode, Animation, BaseAnimation};
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
 /// caches animated value, reducing computing while not animating.
 /// you might want to use it at the end of the animation chains.
+///
+/// it's [`Sync`] if the source animation is, so it can be shared across threads.
 #[derive(Debug)]
 pub struct Cache<T>
 where
@@ -16,7 +23,7 @@ where
     T::Item: Clone,
 {
     src: T,
-    cell: RefCell<Option<(Duration, T::Item)>>,
+    cell: AtomicCell<(Duration, T::Item)>,
 }
 
 impl<T> Cache<T>
@@ -33,6 +40,17 @@ where
     }
 }
 
+impl<T> Clone for Cache<T>
+where
+    T: Animation + Clone,
+    T::Item: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.src.clone())
+    }
+}
+
 impl<T> BaseAnimation for Cache<T>
 where
     T: Animation,
@@ -54,16 +72,73 @@ where
             }
         }
 
-        if let Some((time, value)) = &*self.cell.borrow() {
-            if time == &elapsed {
-                return value.clone();
+        self.cell.with(|cell| match cell {
+            Some(cell) => {
+                if let Some((time, value)) = &*cell {
+                    if time == &elapsed {
+                        return value.clone();
+                    }
+                }
+                let value = self.src.animate(elapsed);
+                *cell = Some((elapsed, value.clone()));
+                value
             }
-        }
-        let value = self.src.animate(elapsed);
+            // someone else is holding the cache
+            None => self.src.animate(elapsed),
+        })
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.src.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("cache", self.duration()).child(self.src.describe())
+    }
+}
+
+/// a cell guarded by an atomic flag; it never blocks.
+struct AtomicCell<V> {
+    busy: AtomicBool,
+    value: UnsafeCell<Option<V>>,
+}
+
+impl<V> AtomicCell<V> {
+    /// provides the slot if no one else is using it, otherwise `None`
+    #[inline]
+    fn with<R>(&self, f: impl FnOnce(Option<&mut Option<V>>) -> R) -> R {
+        if self
+            .busy
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
         {
-            let cell = &mut *self.cell.borrow_mut();
-            *cell = Some((elapsed, value.clone()));
+            // SAFETY: exclusive access is guaranteed by the flag
+            let res = f(Some(unsafe { &mut *self.value.get() }));
+            self.busy.store(false, Ordering::Release);
+            res
+        } else {
+            f(None)
         }
-        value
+    }
+}
+
+impl<V> Default for AtomicCell<V> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            busy: AtomicBool::new(false),
+            value: UnsafeCell::new(None),
+        }
+    }
+}
+
+// SAFETY: the value is only accessed while holding the flag
+unsafe impl<V: Send> Sync for AtomicCell<V> {}
+
+impl<V> fmt::Debug for AtomicCell<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AtomicCell")
     }
 }
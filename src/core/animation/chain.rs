@@ -4,8 +4,9 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
-use std::time::Duration;
+use super::{AnimNode, Animation, BaseAnimation};
+use alloc::vec::Vec;
+use core::time::Duration;
 
 /// chained animations, runs in orders
 #[derive(Debug, Clone)]
@@ -47,4 +48,47 @@ where
         }
         self.first.animate(elapsed)
     }
+
+    /// each animation animates its own time points at once
+    #[inline]
+    fn animate_many(&self, times: &[Duration], output: &mut Vec<Self::Item>) {
+        let first = match self.first.duration() {
+            Some(duration) => duration,
+            None => return self.first.animate_many(times, output),
+        };
+        let (mut early, mut late) = (Vec::new(), Vec::new());
+        for elapsed in times.iter() {
+            if *elapsed >= first {
+                late.push(*elapsed - first);
+            } else {
+                early.push(*elapsed);
+            }
+        }
+        let mut firsts = Vec::with_capacity(early.len());
+        let mut seconds = Vec::with_capacity(late.len());
+        self.first.animate_many(&early, &mut firsts);
+        self.second.animate_many(&late, &mut seconds);
+        let (mut firsts, mut seconds) = (firsts.into_iter(), seconds.into_iter());
+        output.reserve(times.len());
+        output.extend(times.iter().filter_map(|elapsed| {
+            if *elapsed >= first {
+                seconds.next()
+            } else {
+                firsts.next()
+            }
+        }));
+    }
+
+    /// the period of the second one, once the first one is finished
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.first.duration().and_then(|_| self.second.period())
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("chain", self.duration())
+            .child(self.first.describe())
+            .child(self.second.describe())
+    }
 }
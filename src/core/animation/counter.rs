@@ -0,0 +1,112 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{key_frame::FrameEasing, AnimNode, Animation, BaseAnimation};
+use crate::core::{easing::Function, math, MaybeSend, MaybeSync, DURATION_ZERO};
+use alloc::string::String;
+use core::{fmt, time::Duration};
+
+/// counts integers from one to another, see [`crate::builder::count()`]
+#[derive(Clone)]
+pub struct Counter {
+    from: i64,
+    to: i64,
+    duration: Duration,
+    easing: FrameEasing,
+}
+
+impl Counter {
+    #[inline]
+    pub(super) fn new(from: i64, to: i64, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing: Default::default(),
+        }
+    }
+
+    /// ease function of the progress, default linear; overshoots are clamped, so the count
+    /// never goes beyond the range
+    #[inline]
+    pub fn easing(
+        mut self,
+        easing: impl Function + Clone + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        self.easing = FrameEasing::new(easing);
+        self
+    }
+
+    /// outputs texts formatted by `format`
+    #[inline]
+    pub fn format<F>(self, format: F) -> impl Animation<Item = String> + Clone
+    where
+        F: Fn(i64) -> String + Clone,
+    {
+        self.map(format)
+    }
+
+    /// outputs texts with thousands separated by `separator`, e.g. `1,234,567`
+    #[inline]
+    pub fn grouped(self, separator: char) -> impl Animation<Item = String> + Clone {
+        self.map(move |value| group_thousands(value, separator))
+    }
+}
+
+impl BaseAnimation for Counter {
+    type Item = i64;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+
+    /// every integer in the range is shown for the same amount of (eased) time
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        let progress = if self.duration == DURATION_ZERO || elapsed >= self.duration {
+            1.0
+        } else {
+            elapsed.as_secs_f64() / self.duration.as_secs_f64()
+        };
+        let progress = self.easing.ease(progress).clamp(0.0, 1.0);
+        let delta = self.to as i128 - self.from as i128;
+        let count = delta.abs();
+        let step = (math::floor(progress * (count as f64 + 1.0)) as i128).min(count);
+        (self.from as i128 + step * delta.signum()) as i64
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("count", self.duration())
+    }
+}
+
+impl fmt::Debug for Counter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Counter")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+/// e.g. `-1234567` => `-1,234,567`
+fn group_thousands(value: i64, separator: char) -> String {
+    let digits = format!("{}", value.unsigned_abs());
+    let mut text = String::with_capacity(digits.len() * 4 / 3 + 1);
+    if value < 0 {
+        text.push('-');
+    }
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            text.push(separator);
+        }
+        text.push(digit);
+    }
+    text
+}
@@ -4,9 +4,9 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
+use super::{AnimNode, Animation, BaseAnimation};
 use crate::core::DURATION_ZERO;
-use std::time::Duration;
+use core::time::Duration;
 
 /// delay your animation for a specified time; negative delay has no effect
 #[derive(Debug, Clone)]
@@ -48,4 +48,14 @@ impl<T: Animation> BaseAnimation for Delay<T> {
         };
         self.src.animate(elapsed)
     }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.src.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("delay", self.duration()).child(self.src.describe())
+    }
 }
@@ -0,0 +1,257 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, BaseAnimation, StepAnimation, StepEnd};
+use crate::core::{RepeatBehavior, DURATION_ZERO};
+use alloc::vec::Vec;
+use core::{ops::Range, time::Duration};
+
+/// plays frames of a sprite sheet, outputs frame indices.
+///
+/// built on top of [`StepAnimation`]; by default it plays the frames once at 24 fps.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Animation, Flipbook, SpriteGrid};
+///
+/// let walk = Flipbook::new(0..8).fps(12.0).forever();
+/// let timeline = walk.clone().begin_animation();
+/// assert_eq!(timeline.value(), 0);
+///
+/// // texture rects of 4 columns, 32x32 each
+/// let timeline = walk.grid(SpriteGrid::new(4, 32.0, 32.0)).begin_animation();
+/// assert_eq!(timeline.value().width, 32.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Flipbook {
+    frames: Range<usize>,
+    fps: f64,
+    repeat: RepeatBehavior,
+    ping_pong: bool,
+    steps: StepAnimation<Vec<usize>>,
+}
+
+impl Flipbook {
+    /// play `frames` of a sprite sheet; panics if it's empty
+    #[inline]
+    pub fn new(frames: Range<usize>) -> Self {
+        assert!(!frames.is_empty(), "flipbook requires at least one frame");
+        let mut flipbook = Self {
+            frames,
+            fps: 24.0,
+            repeat: RepeatBehavior::default(),
+            ping_pong: false,
+            steps: StepAnimation::new(Vec::new()),
+        };
+        flipbook.prepare();
+        flipbook
+    }
+
+    /// frames per second, default 24; panics if it's not positive
+    #[inline]
+    pub fn fps(mut self, fps: f64) -> Self {
+        assert!(fps > 0.0 && fps.is_finite(), "invalid fps: {}", fps);
+        self.fps = fps;
+        self.prepare();
+        self
+    }
+
+    /// how many times to play the frames, default once
+    ///
+    /// panics if the count is negative, NaN or infinite; use [`Flipbook::forever()`] for infinite
+    #[inline]
+    pub fn repeat(mut self, repeat: RepeatBehavior) -> Self {
+        if let RepeatBehavior::Count(count) = repeat {
+            assert!(
+                count >= 0.0 && count.is_finite(),
+                "invalid repeat count: {}",
+                count
+            );
+        }
+        self.repeat = repeat;
+        self
+    }
+
+    /// play the frames `count` times, see [`Flipbook::repeat()`]
+    #[inline]
+    pub fn times(self, count: f32) -> Self {
+        self.repeat(RepeatBehavior::Count(count))
+    }
+
+    /// play the frames exactly `count` times
+    #[inline]
+    pub fn times_exact(self, count: u32) -> Self {
+        self.repeat(RepeatBehavior::CountExact(count))
+    }
+
+    /// loop the frames forever
+    #[inline]
+    pub fn forever(self) -> Self {
+        self.repeat(RepeatBehavior::Forever)
+    }
+
+    /// play forward then backward, e.g. `0 1 2 1 0 1 2 ...`; the backward pass counts into the same
+    /// play
+    #[inline]
+    pub fn ping_pong(mut self, ping_pong: bool) -> Self {
+        self.ping_pong = ping_pong;
+        self.prepare();
+        self
+    }
+
+    /// output texture rects of `grid` instead of frame indices
+    #[inline]
+    pub fn grid(self, grid: SpriteGrid) -> SpriteSheet {
+        SpriteSheet {
+            flipbook: self,
+            grid,
+        }
+    }
+
+    fn prepare(&mut self) {
+        let mut frames: Vec<usize> = self.frames.clone().collect();
+        if self.ping_pong && frames.len() > 2 {
+            let backward = frames[1..frames.len() - 1].to_vec();
+            frames.extend(backward.into_iter().rev());
+        }
+        self.steps = StepAnimation::new(frames)
+            .interval(Duration::from_secs_f64(1.0 / self.fps))
+            .end(StepEnd::Wrap);
+    }
+}
+
+impl BaseAnimation for Flipbook {
+    type Item = usize;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        let period = self.steps.duration()?;
+        match self.repeat {
+            RepeatBehavior::Count(count) => Some(period.mul_f64(count as f64)),
+            repeat => repeat.total(period),
+        }
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        match self.duration() {
+            Some(duration) if duration == DURATION_ZERO => self.frames.start,
+            // a ping-pong play ends where it started
+            Some(duration) if elapsed >= duration && self.ping_pong => self.steps.animate(duration),
+            Some(duration) if elapsed >= duration => self
+                .steps
+                .animate(duration.checked_sub(Duration::from_nanos(1)).unwrap()),
+            _ => self.steps.animate(elapsed),
+        }
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        if !self.repeat.loops() {
+            return None;
+        }
+        self.steps
+            .duration()
+            .filter(|period| *period > DURATION_ZERO)
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("flipbook", self.duration()).child(self.steps.describe())
+    }
+}
+
+/// a rect in a texture, in pixels
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameRect {
+    /// left
+    pub x: f32,
+    /// top
+    pub y: f32,
+    /// width
+    pub width: f32,
+    /// height
+    pub height: f32,
+}
+
+/// layout of frames in a sprite sheet, row by row
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteGrid {
+    columns: usize,
+    width: f32,
+    height: f32,
+    offset: (f32, f32),
+    spacing: (f32, f32),
+}
+
+impl SpriteGrid {
+    /// `columns` frames per row, each frame is `width` x `height`; panics if `columns` is zero
+    #[inline]
+    pub fn new(columns: usize, width: f32, height: f32) -> Self {
+        assert!(columns > 0, "sprite grid requires at least one column");
+        Self {
+            columns,
+            width,
+            height,
+            offset: (0.0, 0.0),
+            spacing: (0.0, 0.0),
+        }
+    }
+
+    /// position of the first frame in the texture, default `(0, 0)`
+    #[inline]
+    pub fn offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// gaps between frames, default `(0, 0)`
+    #[inline]
+    pub fn spacing(mut self, x: f32, y: f32) -> Self {
+        self.spacing = (x, y);
+        self
+    }
+
+    /// texture rect of frame `index`
+    #[inline]
+    pub fn rect(&self, index: usize) -> FrameRect {
+        let column = (index % self.columns) as f32;
+        let row = (index / self.columns) as f32;
+        FrameRect {
+            x: self.offset.0 + column * (self.width + self.spacing.0),
+            y: self.offset.1 + row * (self.height + self.spacing.1),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// a [`Flipbook`] which outputs texture rects, see [`Flipbook::grid()`]
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    flipbook: Flipbook,
+    grid: SpriteGrid,
+}
+
+impl BaseAnimation for SpriteSheet {
+    type Item = FrameRect;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        self.flipbook.duration()
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        self.grid.rect(self.flipbook.animate(elapsed))
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        self.flipbook.describe()
+    }
+}
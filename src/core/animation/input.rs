@@ -0,0 +1,134 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, BaseAnimation};
+use crate::core::{math, Animatable, DURATION_ZERO};
+use core::{fmt, time::Duration};
+use std::sync::{Arc, Mutex};
+
+/// animation source whose value is pushed from outside, e.g. audio levels, sensor values or
+/// scroll positions, so that live data can modulate time based animations by
+/// [`crate::Animation::zip()`] or [`crate::Animation::map()`].
+///
+/// it never finishes. clones share the same value, so keep one to [`Input::set()`] values and
+/// put another into the animation.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Animation, Input, Options};
+///
+/// let level = Input::new(0.0_f32).smoothing(Duration::from_millis(50));
+/// let timeline = Options::new(1.0_f32, 1.2)
+///     .duration(Duration::from_millis(500))
+///     .build()
+///     .forever()
+///     .zip(level.clone())
+///     .map(|(scale, level)| scale + level)
+///     .begin_animation();
+///
+/// // on every audio frame
+/// level.set(0.8);
+/// ```
+#[derive(Clone)]
+pub struct Input<T> {
+    state: Arc<Mutex<State<T>>>,
+}
+
+struct State<T> {
+    target: T,
+    current: T,
+    /// elapsed time of the previous animate
+    elapsed: Duration,
+    /// time constant of the low-pass filter
+    smoothing: Duration,
+}
+
+impl<T: Animatable> Input<T> {
+    /// input with the initial value
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                target: value.clone(),
+                current: value,
+                elapsed: DURATION_ZERO,
+                smoothing: DURATION_ZERO,
+            })),
+        }
+    }
+
+    /// follow pushed values by a low-pass filter, which covers about 63% of a change within
+    /// `time_constant`; default zero, i.e. no smoothing
+    #[inline]
+    pub fn smoothing(self, time_constant: Duration) -> Self {
+        self.with(|state| state.smoothing = time_constant);
+        self
+    }
+
+    /// push a new value
+    #[inline]
+    pub fn set(&self, value: T) {
+        self.with(|state| state.target = value);
+    }
+
+    /// the latest pushed value
+    #[inline]
+    pub fn get(&self) -> T {
+        self.with(|state| state.target.clone())
+    }
+
+    #[inline]
+    fn with<R>(&self, f: impl FnOnce(&mut State<T>) -> R) -> R {
+        // a panic elsewhere never leaves the state inconsistent
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        f(&mut state)
+    }
+}
+
+impl<T: Animatable> BaseAnimation for Input<T> {
+    type Item = T;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        None
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        self.with(|state| {
+            let delta = elapsed.checked_sub(state.elapsed);
+            state.elapsed = elapsed;
+            state.current = match delta {
+                Some(delta) if state.smoothing > DURATION_ZERO => {
+                    let time =
+                        1.0 - math::exp(-delta.as_secs_f64() / state.smoothing.as_secs_f64());
+                    state.current.animate(&state.target, time)
+                }
+                // no smoothing, or restarted
+                _ => state.target.clone(),
+            };
+            state.current.clone()
+        })
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("input", self.duration())
+    }
+}
+
+impl<T: Animatable + fmt::Debug> fmt::Debug for Input<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with(|state| {
+            f.debug_struct("Input")
+                .field("target", &state.target)
+                .field("current", &state.current)
+                .field("smoothing", &state.smoothing)
+                .finish()
+        })
+    }
+}
@@ -1,11 +1,22 @@
-use crate::{easing, Animatable, DEFAULT_ANIMATION_DURATION, DURATION_ZERO};
-use std::fmt;
-use std::time::Duration;
+use crate::{
+    core::{
+        animatable::{DynInterpolator, Interpolator},
+        physics::affine,
+    },
+    easing, Animatable, MaybeSend, MaybeSync, DEFAULT_ANIMATION_DURATION, DURATION_ZERO,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+use smallvec::SmallVec;
 
-use super::BaseAnimation;
+use super::{AnimNode, BaseAnimation};
 
 /// key time
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KeyTime {
     /// by duration
     Duration(Duration),
@@ -36,14 +47,24 @@ impl From<f32> for KeyTime {
 }
 
 /// key-frame
+///
+/// by default, the easing of a frame eases the segment arriving at it, i.e. from the previous
+/// frame to this one. use [`KeyFrame::easing_out()`] to ease the segment departing from it
+/// instead, the same as CSS `@keyframes`.
 pub struct KeyFrame<T> {
     /// value of key-frame
     pub value: T,
     /// key-time of key-frame
     pub key_time: KeyTime,
-    easing: Box<dyn easing::Function>,
+    easing: FrameEasing,
+    easing_out: Option<FrameEasing>,
+    /// resolves the value from the previous frame's one, see [`KeyFrame::by()`]
+    relative: Option<Relative<T>>,
 }
 
+/// resolves `value` relative to the previous value, if any
+type Relative<T> = fn(Option<&T>, &T) -> T;
+
 impl<T> KeyFrame<T> {
     /// create key-frame
     #[inline]
@@ -51,7 +72,9 @@ impl<T> KeyFrame<T> {
         Self {
             value,
             key_time: DURATION_ZERO.into(),
-            easing: Box::new(easing::linear()),
+            easing: Default::default(),
+            easing_out: None,
+            relative: None,
         }
     }
 
@@ -61,10 +84,30 @@ impl<T> KeyFrame<T> {
         Self {
             value,
             key_time,
-            easing: Box::new(easing::linear()),
+            easing: Default::default(),
+            easing_out: None,
+            relative: None,
         }
     }
 
+    /// create key-frame whose value is `delta` relative to the previous frame's value,
+    /// e.g. move +20px then +40px; resolved in key time order when built, the first frame is
+    /// relative to `T::default()`.
+    ///
+    /// it's exact for float based values, see [`KeyFrame::new()`] for absolute values.
+    #[inline]
+    pub fn by(delta: T) -> Self
+    where
+        T: Animatable + Default,
+    {
+        let mut frame = Self::new(delta);
+        frame.relative = Some(|prev, delta| match prev {
+            Some(prev) => affine((prev, 1.0), (delta, 1.0), (&T::default(), -1.0)),
+            None => delta.clone(),
+        });
+        frame
+    }
+
     /// set value
     #[inline]
     pub fn value(mut self, value: T) -> Self {
@@ -95,10 +138,35 @@ impl<T> KeyFrame<T> {
         self
     }
 
-    /// set easing function
+    /// set easing function of the segment arriving at this frame, same as
+    /// [`KeyFrame::easing_in()`]; built-in ease functions, e.g. [`easing::quad_ease()`], are
+    /// stored without allocation
+    #[inline]
+    pub fn easing(
+        mut self,
+        func: impl easing::Function + Clone + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        self.easing = FrameEasing::new(func);
+        self
+    }
+
+    /// set easing function of the segment arriving at this frame, from the previous frame
+    #[inline]
+    pub fn easing_in(
+        self,
+        func: impl easing::Function + Clone + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        self.easing(func)
+    }
+
+    /// set easing function of the segment departing from this frame, to the next frame;
+    /// it takes precedence over the next frame's [`KeyFrame::easing_in()`]
     #[inline]
-    pub fn easing(mut self, func: impl easing::Function + Clone + 'static) -> Self {
-        self.easing = Box::new(func);
+    pub fn easing_out(
+        mut self,
+        func: impl easing::Function + Clone + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        self.easing_out = Some(FrameEasing::new(func));
         self
     }
 }
@@ -109,7 +177,9 @@ impl<T: Default> Default for KeyFrame<T> {
         Self {
             value: Default::default(),
             key_time: Default::default(),
-            easing: Box::new(easing::linear()),
+            easing: Default::default(),
+            easing_out: None,
+            relative: None,
         }
     }
 }
@@ -119,7 +189,9 @@ impl<T: Clone> Clone for KeyFrame<T> {
         Self {
             value: self.value.clone(),
             key_time: self.key_time,
-            easing: dyn_clone::clone_box(&*self.easing),
+            easing: self.easing.clone(),
+            easing_out: self.easing_out.clone(),
+            relative: self.relative,
         }
     }
 }
@@ -129,15 +201,152 @@ impl<T: fmt::Debug> fmt::Debug for KeyFrame<T> {
         f.debug_struct("KeyFrame")
             .field("value", &self.value)
             .field("key_time", &self.key_time)
+            .field("relative", &self.relative.is_some())
             .field("easing", &"???")
             .finish()
     }
 }
 
+/// key-frames are loaded from asset files with named ease functions, see [`easing::Builtin`];
+/// custom ease functions and relative frames can not be serialized
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::*;
+    use serde::{ser::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct KeyFrameRef<'a, T> {
+        value: &'a T,
+        key_time: KeyTime,
+        easing: easing::Builtin,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        easing_out: Option<easing::Builtin>,
+    }
+
+    #[derive(Deserialize)]
+    struct KeyFrameDef<T> {
+        value: T,
+        #[serde(default)]
+        key_time: KeyTime,
+        #[serde(default)]
+        easing: easing::Builtin,
+        #[serde(default)]
+        easing_out: Option<easing::Builtin>,
+    }
+
+    fn builtin<E: Error>(easing: &FrameEasing) -> Result<easing::Builtin, E> {
+        match easing {
+            FrameEasing::Builtin(func) => Ok(*func),
+            FrameEasing::Custom(_) => Err(E::custom("custom ease functions can not be serialized")),
+        }
+    }
+
+    impl<T: Serialize> Serialize for KeyFrame<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if self.relative.is_some() {
+                return Err(S::Error::custom(
+                    "relative key-frames can not be serialized",
+                ));
+            }
+            KeyFrameRef {
+                value: &self.value,
+                key_time: self.key_time,
+                easing: builtin(&self.easing)?,
+                easing_out: self.easing_out.as_ref().map(builtin).transpose()?,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for KeyFrame<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let def = KeyFrameDef::deserialize(deserializer)?;
+            Ok(KeyFrame {
+                value: def.value,
+                key_time: def.key_time,
+                easing: FrameEasing::Builtin(def.easing),
+                easing_out: def.easing_out.map(FrameEasing::Builtin),
+                relative: None,
+            })
+        }
+    }
+}
+
+/// how key-frames are connected, default [`Interpolation::Linear`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// interpolate between adjacent frames, there are corners at the frames
+    Linear,
+    /// pass through the frames by a uniform Catmull-Rom spline, without corners at the frames;
+    /// it's exact for float based values. the interpolator of the builder is not used
+    CatmullRom,
+}
+
+impl Default for Interpolation {
+    #[inline]
+    fn default() -> Self {
+        Interpolation::Linear
+    }
+}
+
+/// uniform Catmull-Rom spline from `p1` to `p2`, an affine combination of the four points
+#[inline]
+fn catmull_rom<T: Animatable>(p0: &T, p1: &T, p2: &T, p3: &T, t: f64) -> T {
+    let (t2, t3) = (t * t, t * t * t);
+    let w0 = 0.5 * (2.0 * t2 - t - t3);
+    let w1 = 0.5 * (2.0 - 5.0 * t2 + 3.0 * t3);
+    let w2 = 0.5 * (t + 4.0 * t2 - 3.0 * t3);
+    let w3 = 0.5 * (t3 - t2);
+    // the first three points weigh 1 - w3, which stays around 1
+    let rest = 1.0 - w3;
+    affine((p0, w0 / rest), (p1, w1 / rest), (p2, w2 / rest)).animate(p3, w3)
+}
+
+/// ease function of a key-frame; [`easing::Builtin`] is stored without allocation
+#[derive(Clone)]
+pub(super) enum FrameEasing {
+    Builtin(easing::Builtin),
+    Custom(easing::BoxFunction),
+}
+
+impl FrameEasing {
+    #[inline]
+    pub(super) fn new<F>(func: F) -> Self
+    where
+        F: easing::Function + Clone + MaybeSend + MaybeSync + 'static,
+    {
+        match (&func as &dyn Any).downcast_ref::<easing::Builtin>() {
+            Some(builtin) => FrameEasing::Builtin(*builtin),
+            None => FrameEasing::Custom(Box::new(func)),
+        }
+    }
+
+    #[inline]
+    pub(super) fn ease(&self, normalized_time: f64) -> f64 {
+        match self {
+            FrameEasing::Builtin(func) => easing::Function::ease(func, normalized_time),
+            FrameEasing::Custom(func) => func.ease(normalized_time),
+        }
+    }
+}
+
+impl Default for FrameEasing {
+    #[inline]
+    fn default() -> Self {
+        FrameEasing::Builtin(easing::Builtin::Linear)
+    }
+}
+
 struct KeyFrameInner<T> {
     value: T,
     key_time: Duration,
-    easing: Box<dyn easing::Function>,
+    /// seconds since previous frame, precomputed at build time
+    span: f64,
+    /// easing of the segment arriving at this frame, resolved at build time
+    easing: FrameEasing,
+    easing_out: Option<FrameEasing>,
+    relative: Option<Relative<T>>,
 }
 
 impl<T> KeyFrameInner<T> {
@@ -146,7 +355,10 @@ impl<T> KeyFrameInner<T> {
             KeyTime::Duration(duration) => Some(KeyFrameInner {
                 value: src.value,
                 key_time: duration,
+                span: 0.0,
                 easing: src.easing,
+                easing_out: src.easing_out,
+                relative: src.relative,
             }),
             KeyTime::Percent(percent) => {
                 // filter out invalid values
@@ -154,7 +366,10 @@ impl<T> KeyFrameInner<T> {
                 Some(KeyFrameInner {
                     value: src.value,
                     key_time: duration.mul_f32(percent),
+                    span: 0.0,
                     easing: src.easing,
+                    easing_out: src.easing_out,
+                    relative: src.relative,
                 })
             }
         }
@@ -166,7 +381,10 @@ impl<T: Clone> Clone for KeyFrameInner<T> {
         Self {
             value: self.value.clone(),
             key_time: self.key_time,
-            easing: dyn_clone::clone_box(&*self.easing),
+            span: self.span,
+            easing: self.easing.clone(),
+            easing_out: self.easing_out.clone(),
+            relative: self.relative,
         }
     }
 }
@@ -181,16 +399,62 @@ impl<T: fmt::Debug> fmt::Debug for KeyFrameInner<T> {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Default)]
 pub struct KeyFrameAnimation<T> {
-    key_frames: Vec<KeyFrameInner<T>>,
+    /// most animations have only a few frames, keep them inline
+    key_frames: SmallVec<[KeyFrameInner<T>; 4]>,
     duration: Duration,
+    /// index of the last visited segment, speeds up sequential playback
+    hint: AtomicUsize,
+    interpolator: DynInterpolator<T>,
+    interpolation: Interpolation,
 }
 
 impl<T: Animatable> KeyFrameAnimation<T> {
     #[inline]
     pub(super) fn builder(key_frames: Vec<KeyFrame<T>>) -> Builder<T> {
-        Builder { key_frames }
+        Builder {
+            key_frames,
+            initial: None,
+            interpolator: Default::default(),
+            interpolation: Default::default(),
+        }
+    }
+
+    /// index of the first frame whose key time is after `elapsed`
+    #[inline]
+    fn segment(&self, elapsed: Duration) -> usize {
+        let frames = &self.key_frames;
+        let contains = |idx: usize| {
+            idx > 0
+                && idx < frames.len()
+                && frames[idx - 1].key_time <= elapsed
+                && elapsed < frames[idx].key_time
+        };
+        let hint = self.hint.load(Ordering::Relaxed);
+        if contains(hint) {
+            return hint;
+        }
+        // most likely moved forward into next segment
+        if contains(hint + 1) {
+            self.hint.store(hint + 1, Ordering::Relaxed);
+            return hint + 1;
+        }
+        let idx = frames.partition_point(|item| item.key_time <= elapsed);
+        self.hint.store(idx, Ordering::Relaxed);
+        idx
+    }
+}
+
+impl<T: Clone> Clone for KeyFrameAnimation<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key_frames: self.key_frames.clone(),
+            duration: self.duration,
+            hint: AtomicUsize::new(self.hint.load(Ordering::Relaxed)),
+            interpolator: self.interpolator.clone(),
+            interpolation: self.interpolation,
+        }
     }
 }
 
@@ -205,30 +469,45 @@ impl<T: Animatable> BaseAnimation for KeyFrameAnimation<T> {
     #[inline]
     fn animate(&self, elapsed: Duration) -> Self::Item {
         if elapsed < self.duration {
-            let mut last = None;
-            for item in self.key_frames.iter() {
-                if item.key_time <= elapsed {
-                    last = Some(item);
-                    continue;
-                }
-                if let Some(last) = last {
-                    let delta = elapsed - last.key_time;
-                    let total = item.key_time - last.key_time;
-                    let time = delta.as_secs_f64() / total.as_secs_f64();
-                    let time = item.easing.ease(time);
-                    return last.value.animate(&item.value, time);
-                } else {
-                    return item.value.clone();
-                }
+            let idx = self.segment(elapsed);
+            if idx == 0 {
+                return self.key_frames[0].value.clone();
+            }
+            if let Some(item) = self.key_frames.get(idx) {
+                let last = &self.key_frames[idx - 1];
+                let delta = elapsed - last.key_time;
+                let time = delta.as_secs_f64() / item.span;
+                let time = item.easing.ease(time);
+                return match self.interpolation {
+                    Interpolation::Linear => {
+                        self.interpolator
+                            .interpolate(&last.value, &item.value, time)
+                    }
+                    Interpolation::CatmullRom => {
+                        // the end frames are repeated to make up the missing neighbors
+                        let before = &self.key_frames[idx.saturating_sub(2)];
+                        let after = self.key_frames.get(idx + 1).unwrap_or(item);
+                        catmull_rom(&before.value, &last.value, &item.value, &after.value, time)
+                    }
+                };
             }
         }
         let item = self.key_frames.last().unwrap();
         item.value.clone()
     }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("key_frames", self.duration())
+    }
 }
 
 pub struct Builder<T: Animatable> {
     key_frames: Vec<KeyFrame<T>>,
+    /// value to interpolate from if the first frame is not at the beginning
+    initial: Option<T>,
+    interpolator: DynInterpolator<T>,
+    interpolation: Interpolation,
 }
 
 impl<T: Animatable> Builder<T> {
@@ -239,6 +518,31 @@ impl<T: Animatable> Builder<T> {
         self
     }
 
+    /// interpolate from this value if the first frame is not at the beginning;
+    /// otherwise the first frame's value holds until its key time
+    #[inline]
+    pub fn initial(mut self, value: T) -> Self {
+        self.initial = Some(value);
+        self
+    }
+
+    /// interpolate between frames by `interpolator` instead of [`Animatable::animate()`]
+    #[inline]
+    pub fn interpolator(
+        mut self,
+        interpolator: impl Interpolator<T> + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        self.interpolator = DynInterpolator::new(interpolator);
+        self
+    }
+
+    /// how frames are connected, e.g. [`Interpolation::CatmullRom`] to smooth the corners
+    #[inline]
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
     #[inline]
     pub fn build(self) -> KeyFrameAnimation<T> {
         //find max duration, so we can sort frames later
@@ -252,19 +556,57 @@ impl<T: Animatable> Builder<T> {
             .max()
             .unwrap_or(DEFAULT_ANIMATION_DURATION);
 
-        //dbg!(max_duration);
-
         //sort key frames
-        let mut key_frames: Vec<_> = self
+        let mut key_frames: SmallVec<[_; 4]> = self
             .key_frames
             .into_iter()
             .filter_map(|frame| KeyFrameInner::cvt_from(frame, &max_duration))
             .collect();
         assert!(!key_frames.is_empty());
         key_frames.sort_by_key(|x| x.key_time);
+        if let Some(value) = self.initial {
+            if key_frames[0].key_time > DURATION_ZERO {
+                let first = KeyFrameInner {
+                    value,
+                    key_time: DURATION_ZERO,
+                    span: 0.0,
+                    easing: Default::default(),
+                    easing_out: None,
+                    relative: None,
+                };
+                key_frames.insert(0, first);
+            }
+        }
+        //resolve relative values
+        for i in 0..key_frames.len() {
+            if let Some(relative) = key_frames[i].relative.take() {
+                let prev = i.checked_sub(1).map(|prev| &key_frames[prev].value);
+                let value = relative(prev, &key_frames[i].value);
+                key_frames[i].value = value;
+            }
+        }
+        //precompute segments
+        let mut prev = DURATION_ZERO;
+        let mut easing_out = None;
+        for item in key_frames.iter_mut() {
+            item.span = (item.key_time - prev).as_secs_f64();
+            prev = item.key_time;
+            if let Some(easing) = core::mem::replace(&mut easing_out, item.easing_out.take()) {
+                item.easing = easing;
+            }
+        }
+        trace_event!(
+            debug,
+            frames = key_frames.len(),
+            duration = ?max_duration,
+            "key frames built"
+        );
         KeyFrameAnimation {
             key_frames,
             duration: max_duration,
+            hint: AtomicUsize::new(0),
+            interpolator: self.interpolator,
+            interpolation: self.interpolation,
         }
     }
 }
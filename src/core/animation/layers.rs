@@ -0,0 +1,173 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, Animation, BaseAnimation, Boxed};
+use crate::core::{physics::affine, Animatable, MaybeSend};
+use alloc::vec::Vec;
+use core::{fmt, time::Duration};
+
+/// how a layer mixes into the output of the layers below
+enum Blend<T> {
+    /// adds the offset from the reference value
+    Additive(T),
+    /// interpolates towards the value of the layer
+    Override,
+}
+
+struct Layer<T> {
+    blend: Blend<T>,
+    animation: Boxed<T>,
+    weight: Boxed<f64>,
+}
+
+/// mixes a base animation and any number of layers on top of it into one output,
+/// e.g. base position + hover offset + shake.
+///
+/// layers apply in the order they were added; each has its own weight animation,
+/// usually in 0..=1.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{builder, Layers, Options};
+///
+/// let base = Options::new(0.0, 100.0).duration(Duration::from_secs(1)).build();
+/// let shake = Options::new(-5.0, 5.0)
+///     .duration(Duration::from_millis(50))
+///     .auto_reverse(true)
+///     .times(4.0)
+///     .build();
+/// let weight = builder::constant(1.0, Duration::from_secs(0));
+/// let layers = Layers::new(base).additive(shake, weight);
+/// ```
+pub struct Layers<T> {
+    base: Boxed<T>,
+    layers: Vec<Layer<T>>,
+}
+
+impl<T: Animatable> Layers<T> {
+    /// layers on top of `base`
+    #[inline]
+    pub fn new<A>(base: A) -> Self
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+    {
+        Self {
+            base: Boxed::new(base),
+            layers: Vec::new(),
+        }
+    }
+
+    /// add the offset of `animation` from `T::default()`, scaled by `weight`
+    #[inline]
+    pub fn additive<A, W>(self, animation: A, weight: W) -> Self
+    where
+        T: Default,
+        A: Animation<Item = T> + MaybeSend + 'static,
+        W: Animation<Item = f64> + MaybeSend + 'static,
+    {
+        self.additive_from(T::default(), animation, weight)
+    }
+
+    /// add the offset of `animation` from `reference`, scaled by `weight`
+    #[inline]
+    pub fn additive_from<A, W>(self, reference: T, animation: A, weight: W) -> Self
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+        W: Animation<Item = f64> + MaybeSend + 'static,
+    {
+        self.push(Blend::Additive(reference), animation, weight)
+    }
+
+    /// interpolate towards `animation` by `weight`; it replaces the layers below if the weight is 1
+    #[inline]
+    pub fn overlay<A, W>(self, animation: A, weight: W) -> Self
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+        W: Animation<Item = f64> + MaybeSend + 'static,
+    {
+        self.push(Blend::Override, animation, weight)
+    }
+
+    /// number of layers, excluding the base
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// has no layer?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    #[inline]
+    fn push<A, W>(mut self, blend: Blend<T>, animation: A, weight: W) -> Self
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+        W: Animation<Item = f64> + MaybeSend + 'static,
+    {
+        self.layers.push(Layer {
+            blend,
+            animation: Boxed::new(animation),
+            weight: Boxed::new(weight),
+        });
+        self
+    }
+}
+
+impl<T: Animatable> BaseAnimation for Layers<T> {
+    type Item = T;
+
+    /// the longest of all animations
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        let mut duration = self.base.duration()?;
+        for layer in self.layers.iter() {
+            duration = duration
+                .max(layer.animation.duration()?)
+                .max(layer.weight.duration()?);
+        }
+        Some(duration)
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        let mut value = self.base.animate(elapsed);
+        for layer in self.layers.iter() {
+            let weight = layer.weight.animate(elapsed);
+            if weight == 0.0 {
+                continue;
+            }
+            let top = layer.animation.animate(elapsed);
+            value = match &layer.blend {
+                Blend::Additive(reference) => {
+                    affine((&value, 1.0), (&top, weight), (reference, -weight))
+                }
+                Blend::Override => value.animate(&top, weight),
+            };
+        }
+        value
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        let mut node = AnimNode::new("layers", self.duration()).child(self.base.describe());
+        for layer in self.layers.iter() {
+            node = node.child(layer.animation.describe());
+        }
+        node
+    }
+}
+
+impl<T> fmt::Debug for Layers<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layers")
+            .field("base", &self.base)
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
@@ -4,11 +4,12 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
-use std::time::Duration;
+use super::{AnimNode, Animation, BaseAnimation};
+use alloc::vec::Vec;
+use core::{fmt, time::Duration};
 
 /// map from one type to another
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Map<Source, F, T>
 where
     Source: Animation,
@@ -29,6 +30,18 @@ where
     }
 }
 
+impl<Source, F, T> fmt::Debug for Map<Source, F, T>
+where
+    Source: Animation + fmt::Debug,
+    F: Fn(Source::Item) -> T,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map")
+            .field("src", &self.src)
+            .finish_non_exhaustive()
+    }
+}
+
 impl<Source, F, T> BaseAnimation for Map<Source, F, T>
 where
     Source: Animation,
@@ -46,4 +59,28 @@ where
         let v = self.src.animate(elapsed);
         (self.f)(v)
     }
+
+    /// the source animates all the time points at once, then its values are mapped
+    #[inline]
+    fn animate_many(&self, times: &[Duration], output: &mut Vec<Self::Item>) {
+        let mut values = Vec::with_capacity(times.len());
+        self.src.animate_many(times, &mut values);
+        output.reserve(values.len());
+        output.extend(values.into_iter().map(&self.f));
+    }
+
+    #[inline]
+    fn normalized_time(&self, elapsed: Duration) -> Option<(f64, f64)> {
+        self.src.normalized_time(elapsed)
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.src.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("map", self.duration()).child(self.src.describe())
+    }
 }
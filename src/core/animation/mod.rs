@@ -7,36 +7,114 @@
 mod boxed;
 mod cache;
 mod chain;
+mod counter;
 mod delay;
+mod flipbook;
+#[cfg(feature = "std")]
+mod input;
 mod key_frame;
+mod layers;
 mod map;
+mod node;
 mod parallel;
+mod path;
 mod primitive;
+mod probe;
 mod repeat;
 mod scale;
 mod seek;
+mod shake;
+mod shared;
+mod skip;
 mod step;
 mod take;
-
-use crate::{easing, Animatable, Options, RepeatBehavior, Timeline};
-
-pub use self::key_frame::{KeyFrame, KeyTime};
+mod track;
+mod trail;
+mod typewriter;
+
+#[cfg(feature = "std")]
+use crate::Timeline;
+use crate::{
+    core::{math, MaybeSend, MaybeSync},
+    easing, Animatable, Interpolator, Options, RepeatBehavior,
+};
+
+pub use self::counter::Counter;
+pub use self::flipbook::{Flipbook, FrameRect, SpriteGrid, SpriteSheet};
+#[cfg(feature = "std")]
+pub use self::input::Input;
+pub use self::key_frame::{Interpolation, KeyFrame, KeyTime};
+pub use self::layers::Layers;
+pub use self::node::AnimNode;
+pub use self::path::{Path, PathPoint};
 pub use self::seek::SeekFrom;
+pub use self::shake::Shake;
+pub use self::skip::Skip;
 pub use self::step::Cursor;
-pub use self::step::StepAnimation;
-use self::{scale::Scale, step::Infinite};
-pub(crate) use boxed::Boxed;
-pub(crate) use cache::Cache;
-pub(crate) use chain::Chain;
-pub(crate) use delay::Delay;
+use self::step::Infinite;
+pub use self::step::{StepAnimation, StepEnd};
+pub use self::track::Track;
+pub use self::trail::Trail;
+pub use self::typewriter::Typewriter;
+use alloc::vec::Vec;
+pub use boxed::Boxed;
+pub use cache::Cache;
+pub use chain::Chain;
+use core::time::Duration;
+pub use delay::Delay;
 pub(crate) use key_frame::KeyFrameAnimation;
-pub(crate) use map::Map;
-pub(crate) use parallel::Parallel;
+pub use map::Map;
+pub use parallel::Parallel;
 pub(crate) use primitive::Primitive;
-pub(crate) use repeat::Repeat;
-pub(crate) use seek::Seek;
-use std::time::Duration;
-pub(crate) use take::Take;
+pub use probe::Probe;
+pub use repeat::Repeat;
+pub use scale::Scale;
+pub use shared::Shared;
+pub(crate) use skip::{skipped_duration, skipped_elapsed};
+pub use take::Take;
+
+/// run animations of fields in parallel, and build a struct from their outputs, i.e.
+/// [`Animation::zip()`] and [`Animation::map()`] without destructuring nested tuples
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{zip_struct, Animation, Options};
+///
+/// struct Raindrop {
+///     drop_size: f32,
+///     drop_pos: f32,
+///     drop_visible: bool,
+/// }
+///
+/// let timeline = zip_struct!(Raindrop {
+///     drop_size: Options::new(0.0, 10.0).duration(Duration::from_millis(600)).build(),
+///     drop_pos: Options::new(0.0, 100.0).duration(Duration::from_millis(900)).build(),
+///     drop_visible: Options::new(true, false).duration(Duration::from_millis(1500)).build(),
+/// })
+/// .begin_animation();
+/// ```
+#[macro_export]
+macro_rules! zip_struct {
+    (@zip $animation:expr) => {
+        $animation
+    };
+    (@zip $animation:expr, $($rest:expr),+) => {
+        $crate::Animation::zip($animation, $crate::zip_struct!(@zip $($rest),+))
+    };
+    (@pat $field:ident) => {
+        $field
+    };
+    (@pat $field:ident, $($rest:ident),+) => {
+        ($field, $crate::zip_struct!(@pat $($rest),+))
+    };
+    ($($name:ident)::+ { $($field:ident : $animation:expr),+ $(,)? }) => {
+        $crate::Animation::map(
+            $crate::zip_struct!(@zip $($animation),+),
+            |$crate::zip_struct!(@pat $($field),+)| $($name)::+ { $($field),+ },
+        )
+    };
+}
 
 /// build a linear animation(x=t), with which you can get normalized time between 0-1
 ///
@@ -53,7 +131,7 @@ pub(crate) use take::Take;
 pub fn linear(duration: Duration) -> impl Animation<Item = f32> + Clone {
     Options::new(0.0, 1.0)
         .auto_reverse(false)
-        .easing(easing::linear())
+        .with_easing(easing::linear())
         .duration(duration)
         .build()
 }
@@ -78,6 +156,108 @@ pub fn key_frames<T: Animatable>(
     KeyFrameAnimation::builder(frames.into()).build()
 }
 
+/// build key frames animation, which interpolates from `initial` value
+/// if the first frame is not at the beginning.
+///
+/// use `T::default()` as `initial` value if you do not have a specific one.
+///
+/// see [`key_frames`]
+#[inline]
+pub fn key_frames_from<T: Animatable>(
+    initial: T,
+    frames: impl Into<Vec<KeyFrame<T>>>,
+) -> impl Animation<Item = T> + Clone {
+    KeyFrameAnimation::builder(frames.into())
+        .initial(initial)
+        .build()
+}
+
+/// build key frames animation, which interpolates between frames by `interpolator`
+/// instead of [`Animatable::animate()`]
+///
+/// see [`key_frames`]
+#[inline]
+pub fn key_frames_with<T: Animatable>(
+    frames: impl Into<Vec<KeyFrame<T>>>,
+    interpolator: impl Interpolator<T> + MaybeSend + MaybeSync + 'static,
+) -> impl Animation<Item = T> + Clone {
+    KeyFrameAnimation::builder(frames.into())
+        .interpolator(interpolator)
+        .build()
+}
+
+/// build key frames animation, which connects frames by `interpolation`, e.g.
+/// [`Interpolation::CatmullRom`] to pass through them smoothly
+///
+/// see [`key_frames`]
+#[inline]
+pub fn key_frames_by<T: Animatable>(
+    frames: impl Into<Vec<KeyFrame<T>>>,
+    interpolation: Interpolation,
+) -> impl Animation<Item = T> + Clone {
+    KeyFrameAnimation::builder(frames.into())
+        .interpolation(interpolation)
+        .build()
+}
+
+/// cycles through `colors` forever: holds each one for `dwell`, then blends into the next one
+/// within `transition`, and from the last one back to the first; panics if there is no color.
+///
+/// e.g. status indicators and ambient backgrounds; it works for any [`Animatable`] values.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Animation, builder::color_cycle};
+///
+/// // rgb
+/// let colors = vec![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+/// let timeline = color_cycle(colors, Duration::from_millis(500), Duration::from_millis(250))
+///     .begin_animation();
+/// ```
+#[inline]
+pub fn color_cycle<T: Animatable>(
+    colors: impl Into<Vec<T>>,
+    dwell: Duration,
+    transition: Duration,
+) -> impl Animation<Item = T> + Clone {
+    let colors = colors.into();
+    assert!(
+        !colors.is_empty(),
+        "color cycle requires at least one color"
+    );
+    let mut frames = Vec::with_capacity(colors.len() * 2 + 1);
+    let mut time = Duration::from_secs(0);
+    for color in colors.iter() {
+        frames.push(KeyFrame::new(color.clone()).by_duration(time));
+        time += dwell;
+        frames.push(KeyFrame::new(color.clone()).by_duration(time));
+        time += transition;
+    }
+    frames.push(KeyFrame::new(colors[0].clone()).by_duration(time));
+    KeyFrameAnimation::builder(frames).build().forever()
+}
+
+/// 2D offsets shaking left and right randomly `frequency` times per second, e.g. the feedback of
+/// invalid input; the amplitude fades out to zero at the end, faster by larger `decay`.
+///
+/// panics if `frequency` is not positive or `decay` is negative
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Animation, builder::shake};
+///
+/// let timeline = shake(8.0, 12.0, Duration::from_millis(400), 3.0)
+///     .seed(42)
+///     .begin_animation();
+/// let (x, y) = timeline.value();
+/// ```
+#[inline]
+pub fn shake(amplitude: f32, frequency: f32, duration: Duration, decay: f32) -> Shake {
+    Shake::new(amplitude, frequency, duration, decay)
+}
+
 /// infinite or finite steps
 ///
 /// see [`Cursor`]
@@ -123,7 +303,66 @@ pub fn steps_infinite<F: Fn(usize) -> T, T>(
     StepAnimation::new(src).interval(interval)
 }
 
-/// A crate-private base trait,
+/// reveals `text` progressively, `chars_per_second` chars per second;
+/// panics if `chars_per_second` is not positive
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Animation, builder::typewriter};
+///
+/// let timeline = typewriter("hello world", 20.0)
+///     .per_word(true)
+///     .cursor(Duration::from_millis(500))
+///     .begin_animation();
+/// assert_eq!(timeline.value(), (String::new(), true));
+/// ```
+#[inline]
+pub fn typewriter(text: &str, chars_per_second: f64) -> Typewriter {
+    Typewriter::new(text, chars_per_second)
+}
+
+/// moves a point through `points` within `duration` at constant speed, i.e. the time of each
+/// segment is proportional to its arc length; panics if there is no point
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Animation, builder::path, easing};
+///
+/// let timeline = path(vec![(0.0, 0.0), (100.0, 0.0), (100.0, 50.0)], Duration::from_secs(1))
+///     .smooth(true)
+///     .segment_easing(1, easing::cubic_ease())
+///     .begin_animation();
+/// let (x, y) = timeline.value();
+/// ```
+#[inline]
+pub fn path<P: PathPoint>(points: Vec<P>, duration: Duration) -> Path<P> {
+    Path::new(points, duration)
+}
+
+/// counts integers from `from` to `to` within `duration`, e.g. animated statistics;
+/// every integer in the range is shown for the same amount of time, none is skipped or repeated
+/// unless the frames are sparser than the integers
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Animation, builder::count, easing};
+///
+/// let timeline = count(0, 12_345, Duration::from_secs(2))
+///     .easing(easing::cubic_ease())
+///     .grouped(',')
+///     .begin_animation();
+/// let text = timeline.value();
+/// ```
+#[inline]
+pub fn count(from: i64, to: i64, duration: Duration) -> Counter {
+    Counter::new(from, to, duration)
+}
+
+/// base trait of [`Animation`], which is implemented for all of its implementors;
+/// bring it into scope to call its methods, e.g. [`BaseAnimation::describe()`]
 pub trait BaseAnimation {
     /// animated value
     type Item;
@@ -133,6 +372,71 @@ pub trait BaseAnimation {
 
     /// outputs animated value based on the progressing time
     fn animate(&self, elapsed: Duration) -> Self::Item;
+
+    /// outputs animated values of many time points at once, appending them to `output`.
+    ///
+    /// it's useful for baking or benchmarking; composed animations, e.g. [`Animation::map()`],
+    /// [`Animation::chain()`] and [`Animation::zip()`], batch their parts
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use anim::{Animation, BaseAnimation, Options};
+    ///
+    /// let animation = Options::new(0.0, 1.0).build().map(|v| v * 100.0);
+    /// let times: Vec<_> = (0..=60).map(|i| Duration::from_secs(i) / 60).collect();
+    /// let mut values = Vec::new();
+    /// animation.animate_many(&times, &mut values);
+    /// assert_eq!(values.len(), 61);
+    /// ```
+    #[inline]
+    fn animate_many(&self, times: &[Duration], output: &mut Vec<Self::Item>) {
+        output.reserve(times.len());
+        output.extend(times.iter().map(|elapsed| self.animate(*elapsed)));
+    }
+
+    /// duration of one loop if it plays in loops, e.g. [`Animation::forever()`]; `None` otherwise.
+    ///
+    /// loops of an endless animation still have a period, with which drivers can compute the
+    /// loop progress, align looping animations or schedule wakeups
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use anim::{Animation, BaseAnimation, Options};
+    ///
+    /// let animation = Options::new(0.0, 1.0).duration(Duration::from_secs(2)).build();
+    /// assert_eq!(animation.period(), None);
+    /// assert_eq!(animation.forever().period(), Some(Duration::from_secs(2)));
+    /// ```
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        None
+    }
+
+    /// structured description of the animation, e.g. how a composed animation looks like at runtime
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use anim::{Animation, BaseAnimation, Options};
+    ///
+    /// let animation = Options::new(0.0, 1.0).build().delay(Duration::from_secs(1));
+    /// let node = animation.describe();
+    /// assert_eq!(node.kind, "delay");
+    /// assert_eq!(node.children.len(), 1);
+    /// ```
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("animation", self.duration())
+    }
+
+    /// the normalized time before and after easing at `elapsed`, see [`Animation::probe()`];
+    /// `None` if it's unknown, e.g. the animation is not built from [`Options`]
+    #[inline]
+    fn normalized_time(&self, _elapsed: Duration) -> Option<(f64, f64)> {
+        None
+    }
 }
 
 /// your animation, which outputs animated value based on the progressing time.
@@ -160,13 +464,14 @@ pub trait Animation: BaseAnimation {
 
     /// always move forward for specified time when play current animation
     ///
-    /// just a simple wrap on [`Animation::seek`]
+    /// same as `seek(SeekFrom::Begin(progress))`, but works for infinite animations as well;
+    /// skipping beyond the end leaves nothing but the final value
     #[inline]
-    fn skip(self, progress: Duration) -> Seek<Self>
+    fn skip(self, progress: Duration) -> Skip<Self>
     where
         Self: Sized,
     {
-        Seek::new(self, SeekFrom::Begin(progress))
+        Skip::new(self, progress)
     }
 
     /// always move forward for specified time when play current animation
@@ -175,11 +480,12 @@ pub trait Animation: BaseAnimation {
     /// - panics if percent < -1.0 or percent > 1.0
     /// - panics if current animation lasts indefinitely while seeking from end or by percent
     #[inline]
-    fn seek(self, seek: SeekFrom) -> Seek<Self>
+    fn seek(self, seek: SeekFrom) -> Skip<Self>
     where
         Self: Sized,
     {
-        Seek::new(self, seek)
+        let progress = seek.resolve(self.duration());
+        Skip::new(self, progress)
     }
 
     /// always move forward for specified time when play current animation
@@ -190,11 +496,29 @@ pub trait Animation: BaseAnimation {
     /// - panics if percent < -1.0 or percent > 1.0
     /// - panics if current animation lasts indefinitely
     #[inline]
-    fn seek_by(self, percent: f32) -> Seek<Self>
+    fn seek_by(self, percent: f32) -> Skip<Self>
     where
         Self: Sized,
     {
-        Seek::new(self, SeekFrom::Percent(percent))
+        self.seek(SeekFrom::Percent(percent))
+    }
+
+    /// shift the start of the loops by `fraction` of [`BaseAnimation::period()`], e.g. to
+    /// desynchronize many instances of the same looping animation; only the fractional part
+    /// counts, and it has no effect unless your animation plays in loops.
+    ///
+    /// it skips part of the first loop, see [`Animation::skip()`]
+    #[inline]
+    fn phase(self, fraction: f64) -> Skip<Self>
+    where
+        Self: Sized,
+    {
+        let fraction = fraction - math::floor(fraction);
+        let progress = self
+            .period()
+            .map(|period| period.mul_f64(fraction))
+            .unwrap_or_default();
+        Skip::new(self, progress)
     }
 
     /// map from one type to another
@@ -320,6 +644,31 @@ pub trait Animation: BaseAnimation {
         Parallel::new(self, other)
     }
 
+    /// outputs `count` values: the current one, then the ones `spacing`, `2 * spacing`, ...
+    /// ago, clamped at the beginning; e.g. to render ghosts or motion trails.
+    ///
+    /// it lasts until the oldest value reaches the end
+    #[inline]
+    fn trail(self, count: usize, spacing: Duration) -> Trail<Self>
+    where
+        Self: Sized,
+    {
+        Trail::new(self, count, spacing)
+    }
+
+    /// a child track of a parallel composition, which starts at `parent_offset`; the
+    /// composition lasts until the latest track finishes.
+    ///
+    /// it plays the same as [`Animation::delay()`], but tells whether it's playing at some
+    /// point of its parent by [`Track::is_active()`], e.g. to decide its visibility
+    #[inline]
+    fn offset_within(self, parent_offset: Duration) -> Track<Self>
+    where
+        Self: Sized,
+    {
+        Track::new(self, parent_offset)
+    }
+
     /// parallel animations, play at the same time until the longest one finishes.
     ///
     /// alias for [`Animation::parallel()`]
@@ -343,30 +692,65 @@ pub trait Animation: BaseAnimation {
         Cache::new(self)
     }
 
+    /// shares current animation, which can be cheaply cloned into many timelines
+    #[inline]
+    fn shared(self) -> Shared<Self>
+    where
+        Self: Sized,
+    {
+        Shared::new(self)
+    }
+
+    /// outputs `(raw_t, eased_t, value)`, the normalized time before and after easing along with
+    /// the value, e.g. for a debug overlay to plot the curve being applied while tuning motion.
+    ///
+    /// the times are known for animations built from [`Options`], even if mapped or boxed;
+    /// otherwise both are the progress of the whole duration
+    #[inline]
+    fn probe(self) -> Probe<Self>
+    where
+        Self: Sized,
+    {
+        Probe::new(self)
+    }
+
     /// into boxed animation
     #[inline]
     fn boxed(self) -> Boxed<Self::Item>
     where
-        Self: Sized + 'static,
+        Self: Sized + MaybeSend + 'static,
     {
         Boxed::new(self)
     }
 
+    /// do both animations have the same structure? compares [`BaseAnimation::describe()`],
+    /// i.e. kinds, durations and children, but not values nor ease functions;
+    /// handy to check two construction paths in tests
+    #[inline]
+    fn structurally_eq<Other: BaseAnimation>(&self, other: &Other) -> bool
+    where
+        Self: Sized,
+    {
+        self.describe() == other.describe()
+    }
+
     /// build [`Timeline`]
+    #[cfg(feature = "std")]
     #[inline]
     fn to_timeline(self) -> Timeline<Self::Item>
     where
-        Self: Sized + 'static,
+        Self: Sized + MaybeSend + 'static,
         Self::Item: 'static,
     {
         Timeline::new(self)
     }
 
     /// build [`Timeline`] and start to play the animation
+    #[cfg(feature = "std")]
     #[inline]
     fn begin_animation(self) -> Timeline<Self::Item>
     where
-        Self: Sized + 'static,
+        Self: Sized + MaybeSend + 'static,
         Self::Item: 'static,
     {
         let mut timeline = Timeline::new(self);
@@ -398,7 +782,7 @@ impl<T: Animation> IsFinished for T {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::core::{easing, Options, DURATION_ZERO};
+    use crate::core::{easing, AutoReverseMode, EasingScope, Options, OptionsError, DURATION_ZERO};
 
     #[test]
     fn test_constant() {
@@ -430,6 +814,54 @@ mod test {
         assert_eq!(v, 1.0);
     }
 
+    #[test]
+    fn test_primitive_closure_easing() {
+        let animation = Options::new(0.0, 1.0)
+            .easing(|t| t * t * (3.0 - 2.0 * t))
+            .duration(Duration::from_millis(1000))
+            .build();
+        assert_eq!(animation.animate(Duration::from_millis(500)), 0.5);
+        assert_eq!(animation.animate(Duration::from_millis(250)), 0.15625);
+
+        let animation = key_frames(vec![
+            KeyFrame::new(0.0).by_percent(0.0),
+            KeyFrame::new(1.0).by_percent(1.0).easing(|t| t * t),
+        ]);
+        assert_eq!(animation.animate(Duration::from_millis(500)), 0.25);
+    }
+
+    #[test]
+    fn test_primitive_with_easing() {
+        let animation = Options::new(0.0, 1.0)
+            .with_easing(easing::quad_ease())
+            .duration(Duration::from_millis(1000))
+            .build();
+
+        let v = animation.animate(DURATION_ZERO);
+        assert_eq!(v, 0.0);
+
+        let v = animation.animate(Duration::from_millis(500));
+        assert_eq!(v, 0.25);
+
+        let v = animation.animate(Duration::from_millis(1000));
+        assert_eq!(v, 1.0);
+    }
+
+    #[test]
+    fn test_options_try_build() {
+        let res = Options::new(0.0, 1.0).times(-1.0).try_build();
+        assert_eq!(res.err(), Some(OptionsError::InvalidRepeatCount(-1.0)));
+
+        let res = Options::new(0.0, 1.0)
+            .duration(DURATION_ZERO)
+            .forever()
+            .try_build();
+        assert_eq!(res.err(), Some(OptionsError::ZeroDurationForever));
+
+        let animation = Options::new(0.0, 1.0).times(2.0).try_build().unwrap();
+        assert_eq!(animation.duration(), Some(Duration::from_secs(2)));
+    }
+
     #[test]
     fn test_primitive_const() {
         let animation = Options::new(1.0, 1.0)
@@ -498,6 +930,157 @@ mod test {
         assert_eq!(v, 0.0);
     }
 
+    #[test]
+    fn test_primitive_skip_out_of_range() {
+        let opt = Options::new(0.0, 1.0)
+            .easing(easing::linear())
+            .duration(Duration::from_millis(1000))
+            .delay(Duration::from_millis(500))
+            .skip(Duration::from_millis(2000));
+        assert_eq!(opt.effective_duration(), Some(DURATION_ZERO));
+        let animation = opt.build();
+        assert_eq!(animation.duration(), Some(DURATION_ZERO));
+        assert_eq!(animation.animate(DURATION_ZERO), 1.0);
+        assert_eq!(animation.animate(Duration::from_millis(100)), 1.0);
+
+        let opt = Options::new(0.0, 1.0)
+            .easing(easing::linear())
+            .duration(Duration::from_millis(1000))
+            .delay(Duration::from_millis(500))
+            .skip(Duration::from_millis(1000));
+        assert_eq!(opt.effective_duration(), Some(Duration::from_millis(500)));
+        let animation = opt.build();
+        assert_eq!(animation.animate(DURATION_ZERO), 0.5);
+    }
+
+    #[test]
+    fn test_primitive_reverse_fraction() {
+        let modes = [AutoReverseMode::Compress, AutoReverseMode::Extend];
+        // (count, end value)
+        let cases = [
+            (0.5, 1.0),
+            (1.0, 0.0),
+            (1.5, 1.0),
+            (2.0, 0.0),
+            (2.25, 0.25f64),
+        ];
+        for mode in modes.iter() {
+            // duration of one leg
+            let leg = match mode {
+                AutoReverseMode::Compress => Duration::from_millis(500),
+                AutoReverseMode::Extend => Duration::from_millis(1000),
+            };
+            for (count, end) in cases.iter() {
+                let animation = Options::new(0.0, 1.0)
+                    .easing(easing::quad_ease())
+                    .easing_scope(EasingScope::Leg)
+                    .duration(Duration::from_millis(1000))
+                    .auto_reverse(true)
+                    .auto_reverse_mode(*mode)
+                    .times(*count)
+                    .build();
+                let duration = animation.duration().unwrap();
+                assert_eq!(duration, (leg * 2).mul_f32(*count));
+
+                assert_eq!(animation.animate(DURATION_ZERO), 0.0);
+                assert_eq!(animation.animate(leg), 1.0);
+                assert_eq!(animation.animate(duration), *end);
+                assert_eq!(animation.animate(duration * 2), *end);
+
+                // the reverse leg mirrors the forward leg
+                for i in 1..10 {
+                    let forward = animation.animate(leg.mul_f64(i as f64 / 10.0));
+                    let reverse = animation.animate(leg.mul_f64(2.0 - i as f64 / 10.0));
+                    if *count >= 1.0 {
+                        assert!((forward - reverse).abs() < 1e-6);
+                    }
+                    let t = i as f64 / 10.0;
+                    assert!((forward - t * t).abs() < 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_primitive_reverse_easing_scope() {
+        // by default, the ease function spans both legs
+        let animation = Options::new(0.0, 1.0)
+            .easing(easing::quad_ease())
+            .duration(Duration::from_millis(1000))
+            .auto_reverse(true)
+            .times(1.5)
+            .build();
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            let eased = t * t;
+            let expected = if eased > 0.5 {
+                2.0 - eased * 2.0
+            } else {
+                eased * 2.0
+            };
+            let v = animation.animate(Duration::from_millis(1000).mul_f64(t));
+            assert!((v - expected).abs() < 1e-6);
+        }
+        assert_eq!(animation.animate(Duration::from_millis(1500)), 0.5);
+
+        // the same without auto reverse
+        let leg = Options::new(0.0, 1.0)
+            .easing(easing::quad_ease())
+            .easing_scope(EasingScope::Leg)
+            .duration(Duration::from_millis(1000))
+            .build();
+        let iteration = Options::new(0.0, 1.0)
+            .easing(easing::quad_ease())
+            .duration(Duration::from_millis(1000))
+            .build();
+        for i in 0..=10 {
+            let elapsed = Duration::from_millis(100 * i);
+            assert_eq!(leg.animate(elapsed), iteration.animate(elapsed));
+        }
+    }
+
+    #[test]
+    fn test_primitive_reverse_extend() {
+        let animation = Options::new(0.0, 1.0)
+            .easing(easing::linear())
+            .duration(Duration::from_millis(1000))
+            .auto_reverse(true)
+            .auto_reverse_mode(AutoReverseMode::Extend)
+            .build();
+        assert_eq!(animation.duration(), Some(Duration::from_millis(2000)));
+
+        let v = animation.animate(Duration::from_millis(500));
+        assert_eq!(v, 0.5);
+
+        let v = animation.animate(Duration::from_millis(1000));
+        assert_eq!(v, 1.0);
+
+        let v = animation.animate(Duration::from_millis(1500));
+        assert_eq!(v, 0.5);
+
+        let v = animation.animate(Duration::from_millis(2000));
+        assert_eq!(v, 0.0);
+    }
+
+    #[test]
+    fn test_primitive_repeat_easing_scope() {
+        let animation = Options::new(0.0, 1.0)
+            .easing(easing::quad_ease())
+            .duration(Duration::from_millis(1000))
+            .times(2.0)
+            .easing_scope(EasingScope::Whole)
+            .build();
+        let v = animation.animate(DURATION_ZERO);
+        assert_eq!(v, 0.0);
+
+        // eased progress 0.25 of two iterations
+        let v = animation.animate(Duration::from_millis(1000));
+        assert_eq!(v, 0.5);
+
+        let v = animation.animate(Duration::from_millis(2000));
+        assert_eq!(v, 1.0);
+    }
+
     #[test]
     fn test_primitive_repeat() {
         let animation = Options::new(0.0, 1.0)
@@ -526,6 +1109,18 @@ mod test {
         assert_eq!(v, 1.0);
     }
 
+    #[test]
+    fn test_options_around() {
+        let animation = Options::around(1.0, 0.5)
+            .duration(Duration::from_millis(1000))
+            .build();
+        assert_eq!(animation.duration(), None);
+        assert_eq!(animation.animate(DURATION_ZERO), 0.5);
+        assert_eq!(animation.animate(Duration::from_millis(250)), 1.0);
+        assert_eq!(animation.animate(Duration::from_millis(500)), 1.5);
+        assert_eq!(animation.animate(Duration::from_millis(1000)), 0.5);
+    }
+
     #[test]
     fn test_primitive_skip() {
         let animation = Options::new(0.0, 1.0)
@@ -576,6 +1171,144 @@ mod test {
         assert_eq!(v, 1.0);
     }
 
+    #[test]
+    fn test_named_adapters() {
+        struct Fade {
+            opacity: Chain<Delay<Boxed<f32>>, Take<Boxed<f32>>>,
+        }
+
+        let fade = Fade {
+            opacity: linear(Duration::from_millis(100))
+                .boxed()
+                .delay_ms(100)
+                .chain(
+                    linear(Duration::from_millis(100))
+                        .boxed()
+                        .take(Duration::from_millis(50)),
+                ),
+        };
+        assert_eq!(fade.opacity.duration(), Some(Duration::from_millis(250)));
+        assert_eq!(fade.opacity.animate(Duration::from_millis(150)), 0.5);
+    }
+
+    #[test]
+    fn test_structurally_eq() {
+        let a = linear(Duration::from_millis(100))
+            .delay_ms(50)
+            .chain(linear(Duration::from_millis(200)).map(|t| t));
+        let b = linear(Duration::from_millis(100))
+            .boxed()
+            .delay_ms(50)
+            .chain(linear(Duration::from_millis(200)).boxed().map(|t| t * 2.0));
+        assert!(a.structurally_eq(&b));
+        assert!(!a.structurally_eq(&linear(Duration::from_millis(100)).delay_ms(50)));
+
+        let debug = alloc::format!("{:?}", b);
+        assert!(debug.starts_with("Chain { first: Delay { src: Boxed("));
+        assert!(debug.contains("Map { src: "));
+    }
+
+    #[test]
+    fn test_zip_struct() {
+        #[derive(Debug, PartialEq)]
+        struct Raindrop {
+            size: f32,
+            pos: f32,
+            visible: bool,
+        }
+
+        let animation = crate::zip_struct!(Raindrop {
+            size: linear(Duration::from_millis(1000)),
+            pos: linear(Duration::from_millis(2000)).map(|t| t * 100.0),
+            visible: constant(true, Duration::from_millis(500)),
+        });
+        assert_eq!(animation.duration(), Some(Duration::from_millis(2000)));
+        assert_eq!(
+            animation.animate(Duration::from_millis(500)),
+            Raindrop {
+                size: 0.5,
+                pos: 25.0,
+                visible: true,
+            }
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use crate::core::easing::{Builtin, EasingMode};
+
+        let opt: Options<f32, Builtin> = serde_json::from_str(
+            r#"{ "from": 0.0, "to": 10.0, "duration": { "secs": 2, "nanos": 0 },
+                 "repeat": { "CountExact": 2 }, "easing": { "Quad": "Out" } }"#,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&opt).unwrap();
+        let expected = Options::new(0.0, 10.0)
+            .duration(Duration::from_secs(2))
+            .times_exact(2)
+            .with_easing(Builtin::Quad(EasingMode::Out));
+        assert_eq!(json, serde_json::to_string(&expected).unwrap());
+        let (a, b) = (opt.build(), expected.build());
+        assert_eq!(a.duration(), Some(Duration::from_secs(4)));
+        for ms in [0, 500, 1000, 3000].iter() {
+            let elapsed = Duration::from_millis(*ms);
+            assert_eq!(a.animate(elapsed), b.animate(elapsed));
+        }
+
+        let frames: Vec<KeyFrame<f32>> = serde_json::from_str(
+            r#"[{ "value": 0.0, "key_time": { "Percent": 0.0 } },
+                { "value": 1.0, "key_time": { "Percent": 0.5 }, "easing": { "Cubic": "In" } },
+                { "value": 0.0, "key_time": { "Percent": 1.0 } }]"#,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&frames).unwrap();
+        let frames: Vec<KeyFrame<f32>> = serde_json::from_str(&json).unwrap();
+        let animation = key_frames(frames);
+        assert_eq!(animation.animate(Duration::from_millis(250)), 0.125);
+        assert_eq!(animation.animate(Duration::from_millis(750)), 0.5);
+
+        let custom = vec![KeyFrame::new(0.0).easing(easing::custom(|t| t))];
+        assert!(serde_json::to_string(&custom).is_err());
+    }
+
+    #[test]
+    fn test_probe() {
+        let animation = Options::new(0.0, 10.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::quad_ease())
+            .build()
+            .map(|v| v * 2.0)
+            .probe();
+        assert_eq!(
+            animation.animate(Duration::from_millis(500)),
+            (0.5, 0.25, 5.0)
+        );
+        assert_eq!(animation.animate(Duration::from_secs(2)), (1.0, 1.0, 20.0));
+        assert_eq!(animation.describe().kind, "probe");
+
+        let animation = Options::new(0.0, 1.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::quad_ease())
+            .easing_scope(EasingScope::Leg)
+            .auto_reverse(true)
+            .build()
+            .probe();
+        assert_eq!(
+            animation.animate(Duration::from_millis(750)),
+            (0.5, 0.25, 0.25)
+        );
+
+        // progress of the whole duration otherwise
+        let animation = linear(Duration::from_secs(1))
+            .delay(Duration::from_secs(1))
+            .probe();
+        assert_eq!(
+            animation.animate(Duration::from_millis(500)),
+            (0.25, 0.25, 0.0)
+        );
+    }
+
     #[test]
     fn test_map() {
         let animation = Options::new(0.0, 1.0)
@@ -598,6 +1331,34 @@ mod test {
         assert_eq!(v, 2.0);
     }
 
+    #[test]
+    fn test_skip_seek() {
+        let animation = Options::new(0.0, 1.0)
+            .easing(easing::linear())
+            .duration(Duration::from_millis(1000))
+            .auto_reverse(false)
+            .build();
+        let skip = animation.clone().skip(Duration::from_millis(250));
+        let seek = animation
+            .clone()
+            .seek(SeekFrom::End(Duration::from_millis(750)));
+        assert_eq!(skip.progress(), seek.progress());
+        assert_eq!(skip.duration(), Some(Duration::from_millis(750)));
+        assert_eq!(seek.describe().kind, "skip");
+
+        // clamped beyond the end
+        let skip = animation.skip(Duration::from_millis(2000));
+        assert_eq!(skip.duration(), Some(DURATION_ZERO));
+        assert_eq!(skip.animate(DURATION_ZERO), 1.0);
+
+        // works for infinite animations
+        let skip = constant(1.0, Duration::from_millis(100))
+            .forever()
+            .skip(Duration::MAX);
+        assert_eq!(skip.duration(), None);
+        assert_eq!(skip.animate(Duration::from_millis(100)), 1.0);
+    }
+
     #[test]
     fn test_skip() {
         let animation = Options::new(0.0, 1.0)
@@ -788,6 +1549,109 @@ mod test {
         assert_eq!(v, (1.0, 1.0));
     }
 
+    #[test]
+    fn test_primitive_repeat_exact() {
+        let period = Duration::from_nanos(333_333_333);
+        let animation = Options::new(0.0f64, 1.0)
+            .easing(easing::linear())
+            .duration(period)
+            .times_exact(3)
+            .build();
+        assert_eq!(
+            animation.duration(),
+            Some(Duration::from_nanos(999_999_999))
+        );
+
+        assert_eq!(animation.animate(period), 1.0);
+        assert!((animation.animate(period + period / 2) - 0.5).abs() < 1e-6);
+        assert_eq!(animation.animate(period * 3), 1.0);
+        assert_eq!(animation.animate(Duration::from_secs(1)), 1.0);
+
+        let animation = Options::new(0.0, 1.0)
+            .easing(easing::linear())
+            .duration(period)
+            .build()
+            .repeat(RepeatBehavior::CountExact(2));
+        assert_eq!(animation.duration(), Some(period * 2));
+        assert_eq!(animation.animate(period * 2), 1.0);
+    }
+
+    #[test]
+    fn test_period() {
+        let second = Duration::from_secs(1);
+        let options = Options::new(0.0, 1.0).duration(second);
+        assert_eq!(options.clone().build().period(), None);
+        assert_eq!(options.clone().forever().build().period(), Some(second));
+        assert_eq!(
+            options.clone().times_exact(2).build().period(),
+            Some(second)
+        );
+        assert_eq!(
+            options
+                .clone()
+                .times(2.0)
+                .easing_scope(EasingScope::Whole)
+                .build()
+                .period(),
+            None
+        );
+
+        let animation = options
+            .clone()
+            .build()
+            .chain(options.build().forever().speed_up(2.0).delay(second))
+            .map(|v| v * 2.0);
+        assert_eq!(animation.period(), Some(second / 2));
+        assert_eq!(linear(second).times(0.5).period(), None);
+        assert_eq!(linear(second).forever().period(), Some(second));
+    }
+
+    #[test]
+    fn test_phase() {
+        let second = Duration::from_secs(1);
+        let animation = linear(second).forever();
+        for (fraction, value) in [(0.25, 0.25), (1.5, 0.5), (-0.25, 0.75)] {
+            let animation = animation.clone().phase(fraction);
+            assert_eq!(animation.animate(DURATION_ZERO), value);
+            assert_eq!(animation.animate(second), value);
+        }
+        assert_eq!(linear(second).phase(0.5).animate(DURATION_ZERO), 0.0);
+    }
+
+    #[test]
+    fn test_trail() {
+        let at = |ms| Duration::from_millis(ms);
+        let animation = linear(at(1000)).trail(3, at(100));
+        assert_eq!(animation.duration(), Some(at(1200)));
+        assert_eq!(animation.animate(at(150)), vec![0.15, 0.05, 0.0]);
+        assert_eq!(animation.animate(at(1100)), vec![1.0, 1.0, 0.9]);
+        assert!(linear(at(1000))
+            .trail(0, at(100))
+            .animate(at(500))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_offset_within() {
+        let at = |ms| Duration::from_millis(ms);
+        let title = linear(at(300)).offset_within(at(100));
+        let subtitle = linear(at(200)).offset_within(at(300));
+        assert_eq!(title.offset(), at(100));
+        assert!(!title.is_active(at(50)));
+        assert!(title.is_active(at(100)));
+        assert!(!title.is_active(at(400)));
+        assert_eq!(title.animate(at(250)), 0.5);
+
+        let group = title.clone().zip(subtitle.clone());
+        assert_eq!(group.duration(), Some(at(500)));
+        assert_eq!(group.animate(at(400)), (1.0, 0.5));
+        assert!(subtitle.is_active(at(400)));
+        assert!(linear(at(100))
+            .forever()
+            .offset_within(at(100))
+            .is_active(at(1000)));
+    }
+
     #[test]
     fn test_repeat() {
         let animation = Options::new(0.0, 1.0)
@@ -917,6 +1781,461 @@ mod test {
         assert_eq!(v, 1.0);
     }
 
+    #[test]
+    fn test_key_frames_spline() {
+        let frames = vec![
+            KeyFrame::new(0.0).by_percent(0.0),
+            KeyFrame::new(1.0).by_percent(0.5),
+            KeyFrame::new(0.0).by_percent(1.0),
+        ];
+        let linear = key_frames_by(frames.clone(), Interpolation::Linear);
+        let smooth = key_frames_by(frames, Interpolation::CatmullRom);
+        assert_eq!(linear.animate(Duration::from_millis(250)), 0.5);
+        assert_eq!(smooth.animate(Duration::from_millis(250)), 0.5625);
+        // it passes through the frames
+        for ms in [0, 500, 1000] {
+            let elapsed = Duration::from_millis(ms);
+            assert_eq!(smooth.animate(elapsed), linear.animate(elapsed));
+        }
+        // no corner at the peak, it's flat
+        let near = Duration::from_millis(490);
+        assert!(1.0 - linear.animate(near) > 1e-2);
+        assert!(1.0 - smooth.animate(near) < 1e-3);
+
+        // evenly spaced points on a line stay on the line
+        let line = key_frames_by(
+            vec![
+                KeyFrame::new(0.0).by_percent(0.0),
+                KeyFrame::new(1.0).by_percent(1.0 / 3.0),
+                KeyFrame::new(2.0).by_percent(2.0 / 3.0),
+                KeyFrame::new(3.0).by_percent(1.0),
+            ],
+            Interpolation::CatmullRom,
+        );
+        let v: f64 = line.animate(Duration::from_millis(500));
+        assert!((v - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_key_frames_from() {
+        let key_frames = key_frames_from(
+            0.0,
+            vec![
+                KeyFrame::new(0.5).by_percent(0.5),
+                KeyFrame::new(1.0).by_duration(Duration::from_millis(2000)),
+            ],
+        );
+
+        let v = key_frames.animate(Duration::from_millis(0));
+        assert_eq!(v, 0.0);
+
+        let v = key_frames.animate(Duration::from_millis(500));
+        assert_eq!(v, 0.25);
+
+        let v = key_frames.animate(Duration::from_millis(1500));
+        assert_eq!(v, 0.75);
+
+        // the first frame is at the beginning, no need to insert initial value
+        let key_frames = key_frames_from(
+            0.0,
+            vec![
+                KeyFrame::new(0.5).by_percent(0.0),
+                KeyFrame::new(1.0).by_duration(Duration::from_millis(2000)),
+            ],
+        );
+        let v = key_frames.animate(Duration::from_millis(0));
+        assert_eq!(v, 0.5);
+    }
+
+    #[test]
+    fn test_interpolator() {
+        use crate::core::animatable::ShortestArc;
+
+        let animation = Options::new(350.0, 10.0)
+            .duration(Duration::from_millis(1000))
+            .easing(easing::linear())
+            .interpolator(ShortestArc::degrees())
+            .build();
+        assert_eq!(animation.animate(Duration::from_millis(500)), 360.0);
+
+        let key_frames = key_frames_with(
+            vec![
+                KeyFrame::new(10.0).by_percent(0.0),
+                KeyFrame::new(350.0).by_duration(Duration::from_millis(1000)),
+                KeyFrame::new(90.0).by_duration(Duration::from_millis(2000)),
+            ],
+            ShortestArc::degrees(),
+        );
+        assert_eq!(key_frames.animate(Duration::from_millis(500)), 0.0);
+        assert_eq!(key_frames.animate(Duration::from_millis(1500)), 400.0);
+    }
+
+    #[test]
+    fn test_axis_easing() {
+        let animation = Options::new((0.0, 0.0), (100.0, 100.0))
+            .duration(Duration::from_millis(1000))
+            .easing(easing::linear())
+            .axis_easing(easing::linear(), easing::custom(|t| t * t))
+            .build();
+        assert_eq!(animation.animate(Duration::from_millis(500)), (50.0, 25.0));
+        assert_eq!(
+            animation.animate(Duration::from_millis(1000)),
+            (100.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn test_layers() {
+        let linear = |from: f32, to: f32, millis: u64| {
+            Options::new(from, to)
+                .duration(Duration::from_millis(millis))
+                .easing(easing::linear())
+                .build()
+        };
+        let weight = |from: f64, to: f64| {
+            Options::new(from, to)
+                .duration(Duration::from_millis(1000))
+                .easing(easing::linear())
+                .build()
+        };
+        let layers = Layers::new(linear(0.0, 100.0, 1000));
+        assert!(layers.is_empty());
+        assert_eq!(layers.animate(Duration::from_millis(500)), 50.0);
+
+        let layers = layers
+            .additive(linear(0.0, 10.0, 500), weight(1.0, 1.0))
+            .additive_from(1.0, linear(1.0, 3.0, 2000), weight(0.0, 1.0));
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers.duration(), Some(Duration::from_millis(2000)));
+        assert_eq!(layers.animate(Duration::from_millis(0)), 0.0);
+        // 50 + 10 + (1.5 - 1) * 0.5
+        assert_eq!(layers.animate(Duration::from_millis(500)), 60.25);
+
+        let layers = layers.overlay(constant(0.0, DURATION_ZERO), weight(0.0, 1.0));
+        assert_eq!(layers.animate(Duration::from_millis(500)), 30.125);
+        assert_eq!(layers.animate(Duration::from_millis(1000)), 0.0);
+    }
+
+    #[test]
+    fn test_key_frames_easing_out() {
+        let square = easing::custom(|t| t * t);
+        let key_frames = key_frames(vec![
+            KeyFrame::new(0.0).easing_out(square.clone()),
+            KeyFrame::new(1.0).by_duration(Duration::from_millis(1000)),
+            KeyFrame::new(2.0)
+                .by_duration(Duration::from_millis(2000))
+                .easing_in(square),
+            KeyFrame::new(3.0).by_duration(Duration::from_millis(3000)),
+        ]);
+
+        // departing from the first frame
+        let v = key_frames.animate(Duration::from_millis(500));
+        assert_eq!(v, 0.25);
+        // arriving at the third frame
+        let v = key_frames.animate(Duration::from_millis(1500));
+        assert_eq!(v, 1.25);
+        // linear by default
+        let v = key_frames.animate(Duration::from_millis(2500));
+        assert_eq!(v, 2.5);
+    }
+
+    #[test]
+    fn test_key_frames_by() {
+        let at = Duration::from_millis;
+        let relative = key_frames_from(
+            [10.0, 0.0],
+            vec![
+                KeyFrame::by([20.0, 0.0]).by_duration(at(1000)),
+                KeyFrame::by([40.0, -10.0]).by_duration(at(2000)),
+                KeyFrame::new([0.0, 0.0]).by_duration(at(3000)),
+                KeyFrame::by([5.0, 5.0]).by_duration(at(4000)),
+            ],
+        );
+
+        assert_eq!(relative.animate(at(500)), [20.0, 0.0]);
+        assert_eq!(relative.animate(at(1000)), [30.0, 0.0]);
+        assert_eq!(relative.animate(at(2000)), [70.0, -10.0]);
+        assert_eq!(relative.animate(at(3000)), [0.0, 0.0]);
+        assert_eq!(relative.animate(at(4000)), [5.0, 5.0]);
+
+        // the first frame is relative to zero
+        let animation = key_frames(vec![KeyFrame::by(1.0), KeyFrame::by(2.0).by_percent(1.0)]);
+        assert_eq!(animation.animate(Duration::from_millis(1000)), 3.0);
+    }
+
+    #[test]
+    fn test_key_frames_lookup() {
+        let frames: Vec<_> = (0..=100)
+            .map(|i| KeyFrame::new(i as f64).by_duration(Duration::from_millis(i * 10)))
+            .collect();
+        let key_frames = key_frames(frames);
+
+        // forward
+        for i in 0..1000 {
+            let v = key_frames.animate(Duration::from_millis(i));
+            assert!((v - i as f64 / 10.0).abs() < 1e-9);
+        }
+
+        // backward & random access
+        for i in [999, 500, 5, 995, 0, 1000, 1100].iter() {
+            let v = key_frames.animate(Duration::from_millis(*i));
+            let expected = (*i).min(1000) as f64 / 10.0;
+            assert!((v - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_steps_end() {
+        let src = vec![1, 2, 3];
+        let steps = steps(src.clone(), Duration::from_millis(100));
+        assert_eq!(steps.animate(Duration::from_millis(250)), 3);
+        assert_eq!(steps.animate(Duration::from_millis(300)), 3);
+
+        let steps = steps.end(StepEnd::Wrap);
+        assert_eq!(steps.animate(Duration::from_millis(300)), 1);
+        assert_eq!(steps.animate(Duration::from_millis(400)), 2);
+
+        let steps = steps.end(StepEnd::Hold(0));
+        assert_eq!(steps.animate(Duration::from_millis(300)), 0);
+    }
+
+    #[test]
+    fn test_steps_cursors() {
+        let at = |ms| Duration::from_millis(ms);
+        let deque: alloc::collections::VecDeque<_> = vec![1, 2, 3].into();
+        assert_eq!(steps(deque, at(100)).animate(at(150)), 2);
+        let boxed: alloc::boxed::Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+        assert_eq!(steps(boxed, at(100)).animate(at(250)), 3);
+        let src = vec![1, 2, 3];
+        assert_eq!(steps(&src, at(100)).animate(at(50)), 1);
+        let range = steps(10..13, at(100));
+        assert_eq!(range.duration(), Some(at(300)));
+        assert_eq!(range.animate(at(150)), 11);
+        assert_eq!(range.animate(at(500)), 12);
+    }
+
+    #[test]
+    fn test_steps_cycle_frames() {
+        let at = |ms| Duration::from_millis(ms);
+        let steps = steps(vec![1, 2, 3], at(100)).laps(2);
+        assert_eq!(steps.duration(), Some(at(600)));
+        assert_eq!(steps.period(), Some(at(300)));
+        assert_eq!(steps.animate(at(250)), 3);
+        assert_eq!(steps.animate(at(300)), 1);
+        assert_eq!(steps.animate(at(599)), 3);
+        assert_eq!(steps.animate(at(700)), 3);
+
+        let steps = steps.intervals(&[at(100), at(300), at(50)]).cycle_frames();
+        assert_eq!(steps.duration(), None);
+        assert_eq!(steps.animate(at(4500 + 150)), 2);
+        assert_eq!(steps.animate(at(4500 + 420)), 3);
+    }
+
+    #[test]
+    fn test_steps_intervals() {
+        let at = |ms| Duration::from_millis(ms);
+        let steps = steps(vec![1, 2, 3], at(100)).intervals(&[at(100), at(300), at(50)]);
+        assert_eq!(steps.duration(), Some(at(450)));
+        assert_eq!(steps.animate(at(99)), 1);
+        assert_eq!(steps.animate(at(100)), 2);
+        assert_eq!(steps.animate(at(399)), 2);
+        assert_eq!(steps.animate(at(420)), 3);
+        assert_eq!(steps.animate(at(500)), 3);
+
+        let steps = steps.end(StepEnd::Wrap);
+        assert_eq!(steps.animate(at(450)), 1);
+        assert_eq!(steps.animate(at(600)), 2);
+
+        let steps = steps.interval_fn(|n| at(10 * (n as u64 + 1)));
+        assert_eq!(steps.duration(), Some(at(60)));
+        assert_eq!(steps.animate(at(25)), 2);
+    }
+
+    #[test]
+    fn test_typewriter() {
+        let at = |ms| Duration::from_millis(ms);
+        let animation = typewriter("héllo, wörld ", 10.0);
+        assert_eq!(animation.duration(), Some(at(1300)));
+        assert_eq!(animation.animate(DURATION_ZERO), "");
+        assert_eq!(animation.animate(at(250)), "hé");
+        assert_eq!(animation.animate(at(900)), "héllo, wö");
+        assert_eq!(animation.animate(at(5000)), "héllo, wörld ");
+
+        let animation = animation.per_word(true);
+        assert_eq!(animation.animate(at(500)), "");
+        assert_eq!(animation.animate(at(650)), "héllo,");
+        assert_eq!(animation.animate(at(1250)), "héllo, wörld");
+
+        let animation = typewriter("hi", 10.0).cursor(at(300));
+        assert_eq!(animation.duration(), None);
+        assert_eq!(animation.animate(at(250)), ("hi".into(), true));
+        assert_eq!(animation.animate(at(350)), ("hi".into(), false));
+
+        // too fast or too slow
+        let animation = typewriter("hi", 1e12);
+        assert_eq!(animation.duration(), Some(Duration::from_nanos(2)));
+        assert_eq!(animation.animate(Duration::from_nanos(1)), "h");
+        let animation = typewriter("hi", 1e-19);
+        assert_eq!(animation.duration(), Some(Duration::MAX));
+        assert_eq!(animation.animate(Duration::from_secs(u32::MAX as u64)), "");
+        let animation = typewriter("hi", 1e-20);
+        assert_eq!(animation.duration(), Some(Duration::MAX));
+        assert_eq!(animation.animate(Duration::from_secs(u32::MAX as u64)), "");
+    }
+
+    #[test]
+    fn test_path() {
+        let at = |ms| Duration::from_millis(ms);
+        let animation = path(vec![(0.0, 0.0), (3.0, 0.0), (3.0, 4.0)], at(700));
+        assert_eq!(animation.length(), 7.0);
+        assert_eq!(animation.animate(at(150)), (1.5, 0.0));
+        assert_eq!(animation.animate(at(300)), (3.0, 0.0));
+        assert_eq!(animation.animate(at(500)), (3.0, 2.0));
+        assert_eq!(animation.animate(at(1000)), (3.0, 4.0));
+
+        let animation = animation.segment_easing(1, easing::custom(|t| t * t));
+        assert_eq!(animation.animate(at(150)), (1.5, 0.0));
+        assert_eq!(animation.animate(at(500)), (3.0, 1.0));
+
+        let animation = path(vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]], at(1000)).smooth(true);
+        assert_eq!(animation.animate(DURATION_ZERO), [0.0, 0.0]);
+        assert!(animation.animate(at(500)).distance(&[1.0, 1.0]) < 1e-9);
+        assert_eq!(animation.animate(at(1000)), [2.0, 0.0]);
+        // constant speed along the curve
+        let points: Vec<_> = (0..=10).map(|i| animation.animate(at(i * 100))).collect();
+        let step = animation.length() / 10.0;
+        for pair in points.windows(2) {
+            assert!((pair[0].distance(&pair[1]) / step - 1.0).abs() < 0.03);
+        }
+
+        let animation = path(vec![5.0f32], at(1000));
+        assert_eq!(animation.animate(at(500)), 5.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_input() {
+        let at = Duration::from_millis;
+        let input = Input::new(0.0_f64);
+        let animation = constant(1.0, at(100))
+            .zip(input.clone())
+            .map(|(a, b)| a + b);
+        assert_eq!(animation.duration(), None);
+        assert_eq!(animation.animate(at(0)), 1.0);
+        input.set(2.0);
+        assert_eq!(input.get(), 2.0);
+        assert_eq!(animation.animate(at(10)), 3.0);
+
+        // low-pass
+        let input = Input::new(0.0_f64).smoothing(at(1000));
+        assert_eq!(input.animate(at(0)), 0.0);
+        input.set(1.0);
+        let v = input.animate(at(1000));
+        assert!((v - (1.0 - (-1.0_f64).exp())).abs() < 1e-9);
+        let v = input.animate(at(11000));
+        assert!((v - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_color_cycle() {
+        let at = |ms| Duration::from_millis(ms);
+        let animation = color_cycle(vec![0.0, 1.0], at(500), at(250));
+        assert_eq!(animation.duration(), None);
+        let values: Vec<_> = [0, 250, 500, 625, 750, 1250, 1375, 1500, 1750]
+            .iter()
+            .map(|ms| animation.animate(at(*ms)))
+            .collect();
+        assert_eq!(values, vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 0.5, 0.0, 0.0]);
+
+        let animation = color_cycle(vec![0.0, 1.0, 2.0], DURATION_ZERO, at(100));
+        assert_eq!(animation.animate(at(150)), 1.5);
+        assert_eq!(animation.animate(at(250)), 1.0);
+    }
+
+    #[test]
+    fn test_shake() {
+        let at = |ms| Duration::from_millis(ms);
+        let animation = shake(10.0, 10.0, at(1000), 2.0);
+        assert_eq!(animation.animate(DURATION_ZERO), (0.0, 0.0));
+        assert_eq!(animation.animate(at(1000)), (0.0, 0.0));
+        // peaks every half period, alternating and decaying
+        let peaks: Vec<_> = (1..10).map(|i| animation.animate(at(i * 50))).collect();
+        for (i, (x, y)) in peaks.iter().enumerate() {
+            assert_eq!(*x > 0.0, i % 2 == 0);
+            assert!(x.abs() <= 10.0 && y.abs() <= 5.0);
+        }
+        assert!(peaks[8].0.abs() < peaks[0].0.abs());
+        // deterministic
+        assert_eq!(animation.animate(at(123)), animation.animate(at(123)));
+        let other = animation.clone().seed(1);
+        assert_ne!(animation.animate(at(100)), other.animate(at(100)));
+    }
+
+    #[test]
+    fn test_count() {
+        let at = |ms| Duration::from_millis(ms);
+        let animation = count(0, 3, at(1000));
+        let values: Vec<_> = (0..=8).map(|i| animation.animate(at(i * 125))).collect();
+        assert_eq!(values, vec![0, 0, 1, 1, 2, 2, 3, 3, 3]);
+
+        let animation = count(2, -2, at(1000));
+        let values: Vec<_> = (0..=5).map(|i| animation.animate(at(i * 200))).collect();
+        assert_eq!(values, vec![2, 1, 0, -1, -2, -2]);
+
+        // overshoots are clamped
+        let animation = count(0, 10, at(1000)).easing(easing::back_ease(1.0));
+        assert!((0..=10).all(|i| (0..=10).contains(&animation.animate(at(i * 100)))));
+
+        let animation = count(-1_234_567, 1_000, at(1000)).grouped(',');
+        assert_eq!(animation.animate(DURATION_ZERO), "-1,234,567");
+        assert_eq!(animation.animate(at(1000)), "1,000");
+        let animation = count(0, 99, at(1000)).format(|value| format!("{}%", value));
+        assert_eq!(animation.animate(at(1000)), "99%");
+    }
+
+    #[test]
+    fn test_flipbook() {
+        let at = |ms| Duration::from_millis(ms);
+        let flipbook = Flipbook::new(2..5).fps(10.0);
+        assert_eq!(flipbook.duration(), Some(at(300)));
+        let frames: Vec<_> = [0, 150, 250, 300, 1000]
+            .iter()
+            .map(|ms| flipbook.animate(at(*ms)))
+            .collect();
+        assert_eq!(frames, vec![2, 3, 4, 4, 4]);
+
+        let flipbook = flipbook.ping_pong(true).times(2.0);
+        assert_eq!(flipbook.duration(), Some(at(800)));
+        let frames: Vec<_> = (0..8)
+            .map(|i| flipbook.animate(at(i * 100 + 50)))
+            .chain(core::iter::once(flipbook.animate(at(800))))
+            .collect();
+        assert_eq!(frames, vec![2, 3, 4, 3, 2, 3, 4, 3, 2]);
+
+        let sheet = Flipbook::new(0..6).fps(10.0).forever().grid(
+            SpriteGrid::new(4, 16.0, 8.0)
+                .offset(1.0, 2.0)
+                .spacing(1.0, 1.0),
+        );
+        assert_eq!(sheet.duration(), None);
+        let rect = sheet.animate(at(1150));
+        assert_eq!(
+            rect,
+            FrameRect {
+                x: 18.0,
+                y: 11.0,
+                width: 16.0,
+                height: 8.0
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid repeat count")]
+    fn test_flipbook_negative_times() {
+        let _ = Flipbook::new(2..5).times(-1.0);
+    }
+
     #[test]
     fn test_steps_infinite() {
         let steps = steps_infinite(
@@ -996,6 +2315,77 @@ mod test {
         assert_eq!(v, 1.0);
     }
 
+    #[test]
+    fn test_cached_sync() {
+        // Sync regardless of features, unlike animations built from `Options`
+        let animation = std::sync::Arc::new(typewriter("ab", 1.0).cached());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let animation = animation.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        let v = animation.animate(Duration::from_millis(1500));
+                        assert_eq!(v, "a");
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let v = animation.animate(Duration::from_millis(2500));
+        assert_eq!(v, "ab");
+    }
+
+    #[test]
+    fn test_animate_many() {
+        let animation = Options::new(0.0, 1.0)
+            .easing(easing::quad_ease())
+            .duration(Duration::from_millis(1000))
+            .build()
+            .map(|v| v * 2.0)
+            .boxed();
+        let times: Vec<_> = (0..=10).map(|i| Duration::from_millis(i * 100)).collect();
+        let mut values = Vec::new();
+        animation.animate_many(&times, &mut values);
+        assert_eq!(values.len(), times.len());
+        for (elapsed, v) in times.iter().zip(values.iter()) {
+            assert_eq!(*v, animation.animate(*elapsed));
+        }
+
+        // composed animations batch their parts, in any order of time points
+        let animation = constant(0.0, Duration::from_millis(500))
+            .chain(animation)
+            .zip(linear(Duration::from_millis(1000)));
+        let times: Vec<_> = [1200, 0, 700, 1600, 300, 500]
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect();
+        let mut values = vec![(-1.0, -1.0)];
+        animation.animate_many(&times, &mut values);
+        assert_eq!(values.len(), times.len() + 1);
+        for (elapsed, v) in times.iter().zip(values[1..].iter()) {
+            assert_eq!(*v, animation.animate(*elapsed));
+        }
+    }
+
+    #[test]
+    fn test_shared() {
+        let animation = key_frames([
+            KeyFrame::new(0.0).by_percent(0.0),
+            KeyFrame::new(1.0).by_duration(Duration::from_millis(1000)),
+        ])
+        .shared();
+        let timelines: Vec<Timeline<f32>> =
+            (0..10).map(|_| animation.clone().to_timeline()).collect();
+        assert_eq!(timelines.len(), 10);
+        assert_eq!(timelines[0].value(), 0.0);
+
+        let v = animation.animate(Duration::from_millis(500));
+        assert_eq!(v, 0.5);
+        assert_eq!(animation.duration(), Some(Duration::from_millis(1000)));
+    }
+
     #[derive(Debug, PartialEq, Eq, Clone, Copy)]
     enum Action {
         Stand,
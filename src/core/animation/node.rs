@@ -0,0 +1,58 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use alloc::vec::Vec;
+use core::{fmt, time::Duration};
+
+/// structured description of an animation, see [`crate::BaseAnimation::describe()`].
+///
+/// its [`fmt::Display`] prints an indented tree, e.g. for debug overlays or logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnimNode {
+    /// kind of the animation, e.g. `"delay"`
+    pub kind: &'static str,
+    /// the animation lasts for how long; `None` means it's never finished
+    pub duration: Option<Duration>,
+    /// the animations it is composed of
+    pub children: Vec<AnimNode>,
+}
+
+impl AnimNode {
+    /// create a node without children
+    #[inline]
+    pub fn new(kind: &'static str, duration: Option<Duration>) -> Self {
+        Self {
+            kind,
+            duration,
+            children: Vec::new(),
+        }
+    }
+
+    /// add a child
+    #[inline]
+    pub fn child(mut self, child: AnimNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    fn fmt_indent(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        write!(f, "{:width$}{}", "", self.kind, width = indent * 2)?;
+        match self.duration {
+            Some(duration) => writeln!(f, " ({:?})", duration)?,
+            None => writeln!(f, " (forever)")?,
+        }
+        for child in self.children.iter() {
+            child.fmt_indent(f, indent + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AnimNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indent(f, 0)
+    }
+}
@@ -4,8 +4,9 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
-use std::time::Duration;
+use super::{AnimNode, Animation, BaseAnimation};
+use alloc::vec::Vec;
+use core::time::Duration;
 
 /// parallel animations
 #[derive(Debug, Clone)]
@@ -42,4 +43,22 @@ where
         let second = self.second.animate(elapsed);
         (first, second)
     }
+
+    /// each animation animates all the time points at once
+    #[inline]
+    fn animate_many(&self, times: &[Duration], output: &mut Vec<Self::Item>) {
+        let mut first = Vec::with_capacity(times.len());
+        let mut second = Vec::with_capacity(times.len());
+        self.first.animate_many(times, &mut first);
+        self.second.animate_many(times, &mut second);
+        output.reserve(times.len());
+        output.extend(first.into_iter().zip(second));
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("parallel", self.duration())
+            .child(self.first.describe())
+            .child(self.second.describe())
+    }
 }
@@ -0,0 +1,283 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, BaseAnimation};
+use crate::core::{
+    easing::{BoxFunction, Function},
+    math,
+    physics::affine,
+    Animatable, MaybeSend, MaybeSync,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, time::Duration};
+
+/// points which a [`Path`] moves through
+pub trait PathPoint: Animatable {
+    /// euclidean distance between the points
+    fn distance(&self, other: &Self) -> f64;
+}
+
+macro_rules! impl_path_point {
+    ($ty:ident) => {
+        impl PathPoint for $ty {
+            #[inline]
+            fn distance(&self, other: &Self) -> f64 {
+                math::abs(*other as f64 - *self as f64)
+            }
+        }
+
+        impl PathPoint for ($ty, $ty) {
+            #[inline]
+            fn distance(&self, other: &Self) -> f64 {
+                [self.0, self.1].distance(&[other.0, other.1])
+            }
+        }
+
+        impl<const N: usize> PathPoint for [$ty; N] {
+            #[inline]
+            fn distance(&self, other: &Self) -> f64 {
+                let sum: f64 = self
+                    .iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| {
+                        let delta = *b as f64 - *a as f64;
+                        delta * delta
+                    })
+                    .sum();
+                math::sqrt(sum)
+            }
+        }
+    };
+}
+
+impl_path_point!(f32);
+impl_path_point!(f64);
+
+/// samples per curve segment to measure its arc length
+const SAMPLES: usize = 16;
+
+#[derive(Clone)]
+struct Segment<P> {
+    from: P,
+    to: P,
+    /// bezier control points of a smooth path
+    controls: Option<(P, P)>,
+    /// arc length at evenly spaced parameters of a curve, the last one is its length
+    lengths: Vec<f64>,
+    length: f64,
+}
+
+impl<P: PathPoint> Segment<P> {
+    fn new(from: P, to: P, controls: Option<(P, P)>) -> Self {
+        let mut segment = Self {
+            from,
+            to,
+            controls,
+            lengths: Vec::new(),
+            length: 0.0,
+        };
+        if segment.controls.is_some() {
+            let mut prev = segment.from.clone();
+            segment.lengths.push(0.0);
+            for i in 1..=SAMPLES {
+                let point = segment.point(i as f64 / SAMPLES as f64);
+                segment.length += prev.distance(&point);
+                segment.lengths.push(segment.length);
+                prev = point;
+            }
+        } else {
+            segment.length = segment.from.distance(&segment.to);
+        }
+        segment
+    }
+
+    /// point at parameter `t`
+    #[inline]
+    fn point(&self, t: f64) -> P {
+        match &self.controls {
+            Some((c1, c2)) => {
+                // de Casteljau
+                let a = self.from.animate(c1, t);
+                let b = c1.animate(c2, t);
+                let c = c2.animate(&self.to, t);
+                let ab = a.animate(&b, t);
+                let bc = b.animate(&c, t);
+                ab.animate(&bc, t)
+            }
+            None => self.from.animate(&self.to, t),
+        }
+    }
+
+    /// parameter where the arc length is `progress` of the segment
+    #[inline]
+    fn param(&self, progress: f64) -> f64 {
+        if self.controls.is_none() || self.length == 0.0 {
+            return progress;
+        }
+        let distance = progress * self.length;
+        let i = self
+            .lengths
+            .partition_point(|length| *length <= distance)
+            .clamp(1, SAMPLES);
+        let (start, end) = (self.lengths[i - 1], self.lengths[i]);
+        let fraction = if end > start {
+            (distance - start) / (end - start)
+        } else {
+            0.0
+        };
+        (i - 1) as f64 / SAMPLES as f64 + fraction / SAMPLES as f64
+    }
+}
+
+/// moves a point through waypoints at constant speed, see [`crate::builder::path()`]
+#[derive(Clone)]
+pub struct Path<P> {
+    points: Vec<P>,
+    duration: Duration,
+    smooth: bool,
+    /// one per segment
+    easings: Vec<Option<BoxFunction>>,
+    segments: Vec<Segment<P>>,
+    /// arc length at the start of each segment
+    offsets: Vec<f64>,
+    length: f64,
+}
+
+impl<P: PathPoint> Path<P> {
+    /// panics if there is no point
+    #[inline]
+    pub(super) fn new(points: Vec<P>, duration: Duration) -> Self {
+        assert!(!points.is_empty(), "path requires at least one point");
+        let mut path = Self {
+            easings: vec![None; points.len() - 1],
+            points,
+            duration,
+            smooth: false,
+            segments: Vec::new(),
+            offsets: Vec::new(),
+            length: 0.0,
+        };
+        path.prepare();
+        path
+    }
+
+    /// pass the waypoints along a smooth curve(Catmull-Rom spline) instead of straight lines
+    #[inline]
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self.prepare();
+        self
+    }
+
+    /// easing within each segment; default linear, so that the speed is constant
+    #[inline]
+    pub fn easing(
+        mut self,
+        easing: impl Function + Clone + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        for item in self.easings.iter_mut() {
+            *item = Some(Box::new(easing.clone()));
+        }
+        self
+    }
+
+    /// easing within the segment from point `index` to point `index + 1`; panics if out of range
+    #[inline]
+    pub fn segment_easing(
+        mut self,
+        index: usize,
+        easing: impl Function + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        self.easings[index] = Some(Box::new(easing));
+        self
+    }
+
+    /// total arc length
+    #[inline]
+    pub fn length(&self) -> f64 {
+        self.length
+    }
+
+    fn prepare(&mut self) {
+        let points = &self.points;
+        let last = points.len() - 1;
+        self.segments = (0..last)
+            .map(|i| {
+                let (from, to) = (&points[i], &points[i + 1]);
+                let controls = if self.smooth {
+                    let before = &points[i.saturating_sub(1)];
+                    let after = &points[(i + 2).min(last)];
+                    let c1 = affine((from, 1.0), (to, 1.0 / 6.0), (before, -1.0 / 6.0));
+                    let c2 = affine((to, 1.0), (from, 1.0 / 6.0), (after, -1.0 / 6.0));
+                    Some((c1, c2))
+                } else {
+                    None
+                };
+                Segment::new(from.clone(), to.clone(), controls)
+            })
+            .collect();
+        self.offsets.clear();
+        self.length = 0.0;
+        for segment in self.segments.iter() {
+            self.offsets.push(self.length);
+            self.length += segment.length;
+        }
+    }
+}
+
+impl<P: PathPoint> BaseAnimation for Path<P> {
+    type Item = P;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        if self.segments.is_empty() {
+            return self.points[0].clone();
+        }
+        let time = if elapsed >= self.duration {
+            1.0
+        } else {
+            elapsed.as_secs_f64() / self.duration.as_secs_f64()
+        };
+        let distance = time * self.length;
+        let i = self
+            .offsets
+            .partition_point(|offset| *offset <= distance)
+            .max(1)
+            - 1;
+        let segment = &self.segments[i];
+        let progress = if segment.length > 0.0 {
+            ((distance - self.offsets[i]) / segment.length).min(1.0)
+        } else {
+            1.0
+        };
+        let progress = match &self.easings[i] {
+            Some(easing) => easing.ease(progress),
+            None => progress,
+        };
+        segment.point(segment.param(progress))
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("path", self.duration())
+    }
+}
+
+impl<P> fmt::Debug for Path<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Path")
+            .field("points", &self.points.len())
+            .field("duration", &self.duration)
+            .field("smooth", &self.smooth)
+            .field("length", &self.length)
+            .finish()
+    }
+}
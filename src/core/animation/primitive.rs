@@ -4,70 +4,78 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::BaseAnimation;
+use super::{repeat::iteration_elapsed, skipped_elapsed, AnimNode, BaseAnimation};
 use crate::{
-    core::{Animatable, Options, RepeatBehavior},
+    core::{easing, math, Animatable, EasingScope, Options},
     DURATION_ZERO,
 };
-use std::time::Duration;
+use core::{fmt, time::Duration};
 
 /// primitive animation which is built from [`Options`]
-#[derive(Debug, Clone)]
-pub struct Primitive<T: Animatable> {
-    opt: Options<T>,
+#[derive(Clone)]
+pub struct Primitive<T: Animatable, F: easing::Function> {
+    opt: Options<T, F>,
     duration: Option<Duration>,
 }
 
-impl<T: Animatable> Primitive<T> {
+impl<T: Animatable, F: easing::Function> Primitive<T, F> {
     #[inline]
-    pub(crate) fn new(opt: Options<T>) -> Self {
-        let duration = {
-            if opt.duration == DURATION_ZERO {
-                Some(DURATION_ZERO)
-            } else {
-                match opt.repeat {
-                    RepeatBehavior::Count(count) => Some(if count > 0.0 {
-                        opt.duration.mul_f32(count)
-                    } else {
-                        DURATION_ZERO
-                    }),
-                    RepeatBehavior::Forever => None,
-                }
-            }
-        };
+    pub(crate) fn new(opt: Options<T, F>) -> Self {
+        let duration = opt.repeat_duration();
         Self { opt, duration }
     }
+
+    /// an auto reversed iteration is a forward leg followed by a reverse leg,
+    /// map the time within the iteration to the time within the leg
+    #[inline]
+    fn leg_time(&self, time: f64) -> f64 {
+        if !self.opt.auto_reverse {
+            time
+        } else if time > 0.5 {
+            2.0 - time * 2.0
+        } else {
+            time * 2.0
+        }
+    }
 }
 
-impl<T: Animatable> BaseAnimation for Primitive<T> {
+impl<T: Animatable + fmt::Debug, F: easing::Function> fmt::Debug for Primitive<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Primitive")
+            .field("opt", &self.opt)
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+impl<T: Animatable, F: easing::Function> BaseAnimation for Primitive<T, F> {
     type Item = T;
 
     #[inline]
     fn duration(&self) -> Option<Duration> {
-        if let Some(mut duration) = self.duration {
-            //apply delay
-            if let Some(delay) = self.opt.delay {
-                duration += delay;
-            }
-            //apply skip
-            if let Some(skip) = self.opt.skip {
-                if duration > skip {
-                    duration -= skip
-                } else {
-                    duration = DURATION_ZERO;
-                }
+        self.duration
+            .map(|duration| self.opt.apply_offset(duration))
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        match self.normalized_time(elapsed) {
+            Some((_, time)) => {
+                self.opt
+                    .interpolator
+                    .interpolate(&self.opt.from, &self.opt.to, time)
             }
-            Some(duration)
-        } else {
-            None
+            None => self.opt.from.clone(),
         }
     }
 
+    /// the normalized time within the current iteration, or of the whole animation under
+    /// [`EasingScope::Whole`], and the eased one; `None` if it lasts for zero time
     #[inline]
-    fn animate(&self, mut elapsed: Duration) -> Self::Item {
+    fn normalized_time(&self, mut elapsed: Duration) -> Option<(f64, f64)> {
         //apply skip
         if let Some(skip) = self.opt.skip {
-            elapsed += skip;
+            elapsed = skipped_elapsed(elapsed, skip);
         }
         //apply delay
         if let Some(delay) = self.opt.delay {
@@ -82,7 +90,7 @@ impl<T: Animatable> BaseAnimation for Primitive<T> {
         if let Some(duration) = self.duration {
             // opt.duration<=0 || repeat count <=0
             if duration == DURATION_ZERO {
-                return self.opt.from.clone();
+                return None;
             }
             //apply repeat limit
             if elapsed > duration {
@@ -90,23 +98,56 @@ impl<T: Animatable> BaseAnimation for Primitive<T> {
             }
         }
 
-        // calc normalized time
-        let time = elapsed.as_secs_f64() / self.opt.duration.as_secs_f64();
-        let count = time.floor();
-        let mut time = time - count;
-        if count > 0.0 && time == 0.0 {
-            time = 1.0;
-        }
-        time = self.opt.easing.ease(time);
-        if self.opt.auto_reverse {
-            if time > 0.5 {
-                //reverse
-                self.opt.to.animate(&self.opt.from, time * 2.0 - 1.0)
-            } else {
-                self.opt.from.animate(&self.opt.to, time * 2.0)
+        // calc normalized time within current iteration
+        let simple_duration = self.opt.simple_duration();
+        match (self.opt.easing_scope, self.duration) {
+            (EasingScope::Whole, Some(duration)) => {
+                let duration = duration.as_secs_f64();
+                let raw = elapsed.as_secs_f64() / duration;
+                let time = self.opt.easing.ease(raw);
+                let time = time * duration / simple_duration.as_secs_f64();
+                Some((raw, self.leg_time(iteration_time(time))))
+            }
+            // integer math, so that the loop boundaries are exact
+            (scope, _) => {
+                let time = iteration_elapsed(elapsed, simple_duration);
+                let time = time.as_secs_f64() / simple_duration.as_secs_f64();
+                if scope == EasingScope::Leg {
+                    let time = self.leg_time(time);
+                    Some((time, self.opt.easing.ease(time)))
+                } else {
+                    let eased = self.opt.easing.ease(time);
+                    Some((self.leg_time(time), self.leg_time(eased)))
+                }
             }
-        } else {
-            self.opt.from.animate(&self.opt.to, time)
         }
     }
+
+    /// the simple duration, unless the ease function spans all iterations
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        if !self.opt.repeat.loops()
+            || (self.opt.easing_scope == EasingScope::Whole && self.duration.is_some())
+        {
+            return None;
+        }
+        Some(self.opt.simple_duration()).filter(|period| *period > DURATION_ZERO)
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("primitive", self.duration())
+    }
+}
+
+/// normalized time within current iteration
+#[inline]
+fn iteration_time(time: f64) -> f64 {
+    let count = math::floor(time);
+    let time = time - count;
+    if count > 0.0 && time == 0.0 {
+        1.0
+    } else {
+        time
+    }
 }
@@ -0,0 +1,62 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, Animation, BaseAnimation};
+use crate::core::DURATION_ZERO;
+use core::time::Duration;
+
+/// outputs the normalized time before and after easing along with the value,
+/// see [`Animation::probe()`]
+#[derive(Debug, Clone)]
+pub struct Probe<T: Animation> {
+    src: T,
+}
+
+impl<T: Animation> Probe<T> {
+    #[inline]
+    pub(super) fn new(src: T) -> Self {
+        Self { src }
+    }
+}
+
+impl<T: Animation> BaseAnimation for Probe<T> {
+    type Item = (f64, f64, T::Item);
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        self.src.duration()
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        let (raw, eased) = self.normalized_time(elapsed).unwrap_or_else(|| {
+            let progress = match self.src.duration() {
+                Some(duration) if duration > DURATION_ZERO => {
+                    (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0)
+                }
+                Some(_) => 1.0,
+                None => 0.0,
+            };
+            (progress, progress)
+        });
+        (raw, eased, self.src.animate(elapsed))
+    }
+
+    #[inline]
+    fn normalized_time(&self, elapsed: Duration) -> Option<(f64, f64)> {
+        self.src.normalized_time(elapsed)
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.src.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("probe", self.duration()).child(self.src.describe())
+    }
+}
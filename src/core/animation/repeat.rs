@@ -4,14 +4,15 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
+use super::{AnimNode, Animation, BaseAnimation};
 use crate::{core::RepeatBehavior, core::DURATION_ZERO};
-use std::time::Duration;
+use core::time::Duration;
 /// repeat animations
 #[derive(Debug, Clone)]
 pub struct Repeat<T: Animation> {
     src: T,
     duration: Option<Duration>,
+    period: Option<Duration>,
 }
 
 impl<T: Animation> Repeat<T> {
@@ -21,15 +22,19 @@ impl<T: Animation> Repeat<T> {
             if duration == DURATION_ZERO {
                 return Some(DURATION_ZERO);
             }
-            match repeat {
-                RepeatBehavior::Count(count) => {
-                    assert!(count >= 0.0);
-                    Some(duration.mul_f32(count))
-                }
-                RepeatBehavior::Forever => None,
+            if let RepeatBehavior::Count(count) = repeat {
+                assert!(count >= 0.0);
             }
+            repeat.total(duration)
         });
-        Self { src, duration }
+        let period = src
+            .duration()
+            .filter(|period| *period > DURATION_ZERO && repeat.loops());
+        Self {
+            src,
+            duration,
+            period,
+        }
     }
 }
 
@@ -55,12 +60,31 @@ impl<T: Animation> BaseAnimation for Repeat<T> {
             }
         }
 
-        let time = elapsed.as_secs_f64() / simple_duration.as_secs_f64();
-        let count = time.floor();
-        let mut time = time - count;
-        if count > 0.0 && time == 0.0 {
-            time = 1.0
-        };
-        self.src.animate(simple_duration.mul_f64(time))
+        self.src
+            .animate(iteration_elapsed(elapsed, simple_duration))
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.period
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("repeat", self.duration()).child(self.src.describe())
+    }
+}
+
+/// elapsed time within the current iteration, the end of an iteration stays at its end
+#[inline]
+pub(super) fn iteration_elapsed(elapsed: Duration, period: Duration) -> Duration {
+    let (elapsed, period) = (elapsed.as_nanos(), period.as_nanos());
+    if period == 0 {
+        return DURATION_ZERO;
     }
+    let rem = match elapsed % period {
+        0 if elapsed > 0 => period,
+        rem => rem,
+    };
+    Duration::from_nanos(rem as u64)
 }
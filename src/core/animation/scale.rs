@@ -4,10 +4,10 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
+use super::{AnimNode, Animation, BaseAnimation};
 use crate::core::DURATION_ZERO;
-use std::time::Duration;
-/// repeat animations
+use core::time::Duration;
+/// scales the time of the source animation, see [`Animation::scale()`]
 #[derive(Debug, Clone)]
 pub struct Scale<T: Animation> {
     src: T,
@@ -42,4 +42,18 @@ impl<T: Animation> BaseAnimation for Scale<T> {
         let elapsed = elapsed.div_f32(self.scale);
         self.src.animate(elapsed)
     }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        if self.scale == 0.0 {
+            return None;
+        }
+        // animated at `elapsed / scale`
+        self.src.period().map(|period| period.mul_f32(self.scale))
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("scale", self.duration()).child(self.src.describe())
+    }
 }
@@ -0,0 +1,107 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, BaseAnimation};
+use crate::core::{math, DURATION_ZERO};
+use core::time::Duration;
+
+/// decaying randomized 2D offsets, see [`crate::builder::shake()`]
+#[derive(Debug, Clone)]
+pub struct Shake {
+    amplitude: f32,
+    frequency: f32,
+    duration: Duration,
+    decay: f32,
+    seed: u64,
+}
+
+impl Shake {
+    #[inline]
+    pub(super) fn new(amplitude: f32, frequency: f32, duration: Duration, decay: f32) -> Self {
+        assert!(
+            frequency > 0.0 && frequency.is_finite(),
+            "invalid frequency: {}",
+            frequency
+        );
+        assert!(decay >= 0.0, "invalid decay: {}", decay);
+        Self {
+            amplitude,
+            frequency,
+            duration,
+            decay,
+            seed: 0x5eed,
+        }
+    }
+
+    /// seed of the randomness, the same seed shakes the same way
+    #[inline]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// normalized offset at the `k`th half period, alternating between left and right
+    #[inline]
+    fn peak(&self, k: u64) -> (f64, f64) {
+        if k == 0 {
+            return (0.0, 0.0);
+        }
+        let bits = splitmix64(self.seed ^ k.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let x = 0.6 + 0.4 * unit(bits);
+        let y = unit(bits >> 32) - 0.5;
+        let sign = if k & 1 == 0 { -1.0 } else { 1.0 };
+        (sign * x, y)
+    }
+}
+
+impl BaseAnimation for Shake {
+    type Item = (f32, f32);
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        if self.duration == DURATION_ZERO || elapsed >= self.duration {
+            return (0.0, 0.0);
+        }
+        let time = elapsed.as_secs_f64();
+        let progress = time / self.duration.as_secs_f64();
+        let envelope =
+            self.amplitude as f64 * math::exp(-(self.decay as f64) * progress) * (1.0 - progress);
+
+        let half_periods = time * 2.0 * self.frequency as f64;
+        let k = math::floor(half_periods);
+        let t = half_periods - k;
+        // smooth step between peaks
+        let t = t * t * (3.0 - 2.0 * t);
+        let (from, to) = (self.peak(k as u64), self.peak(k as u64 + 1));
+        let x = from.0 + (to.0 - from.0) * t;
+        let y = from.1 + (to.1 - from.1) * t;
+        ((x * envelope) as f32, (y * envelope) as f32)
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("shake", self.duration())
+    }
+}
+
+#[inline]
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// low 32 bits to `[0, 1)`
+#[inline]
+fn unit(bits: u64) -> f64 {
+    (bits & 0xffff_ffff) as f64 / (1u64 << 32) as f64
+}
@@ -0,0 +1,65 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, Animation, BaseAnimation};
+use alloc::{sync::Arc, vec::Vec};
+use core::time::Duration;
+
+/// shares an immutable [`Animation`] definition; cloning it is cheap,
+/// the source animation is never deep-cloned.
+///
+/// it's [`Send`] if the source animation is [`Send`] and [`Sync`], so it can be used
+/// by many [`crate::Timeline`]s
+#[derive(Debug)]
+pub struct Shared<T: Animation>(Arc<T>);
+
+impl<T: Animation> Shared<T> {
+    #[inline]
+    pub(super) fn new(src: T) -> Self {
+        Self(Arc::new(src))
+    }
+}
+
+impl<T: Animation> Clone for Shared<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T: Animation> BaseAnimation for Shared<T> {
+    type Item = T::Item;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        self.0.duration()
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        self.0.animate(elapsed)
+    }
+
+    #[inline]
+    fn animate_many(&self, times: &[Duration], output: &mut Vec<Self::Item>) {
+        self.0.animate_many(times, output)
+    }
+
+    #[inline]
+    fn normalized_time(&self, elapsed: Duration) -> Option<(f64, f64)> {
+        self.0.normalized_time(elapsed)
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.0.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        self.0.describe()
+    }
+}
@@ -0,0 +1,72 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, Animation, BaseAnimation};
+use crate::core::DURATION_ZERO;
+use core::time::Duration;
+
+/// always bypass specified time, only keep the remaining part;
+/// see [`Animation::skip()`] and [`Animation::seek()`]
+///
+/// [`Animation::skip()`] bypasses a fixed time, which works for infinite animations as well;
+/// [`Animation::seek()`] resolves the time from [`super::SeekFrom`] against the duration of the
+/// source animation once built, then behaves the same.
+#[derive(Debug, Clone)]
+pub struct Skip<T: Animation> {
+    src: T,
+    progress: Duration,
+}
+
+impl<T: Animation> Skip<T> {
+    #[inline]
+    pub(super) fn new(src: T, progress: Duration) -> Self {
+        Self { src, progress }
+    }
+
+    /// bypassed time
+    #[inline]
+    pub fn progress(&self) -> Duration {
+        self.progress
+    }
+}
+
+impl<T: Animation> BaseAnimation for Skip<T> {
+    type Item = T::Item;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        self.src
+            .duration()
+            .map(|duration| skipped_duration(duration, self.progress))
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        self.src.animate(skipped_elapsed(elapsed, self.progress))
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.src.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("skip", self.duration()).child(self.src.describe())
+    }
+}
+
+/// what remains of `duration` after skipping `progress`, never negative
+#[inline]
+pub(crate) fn skipped_duration(duration: Duration, progress: Duration) -> Duration {
+    duration.checked_sub(progress).unwrap_or(DURATION_ZERO)
+}
+
+/// elapsed time of the source animation after skipping `progress`
+#[inline]
+pub(crate) fn skipped_elapsed(elapsed: Duration, progress: Duration) -> Duration {
+    elapsed.checked_add(progress).unwrap_or(Duration::MAX)
+}
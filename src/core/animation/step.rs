@@ -1,15 +1,18 @@
-use super::BaseAnimation;
+use super::{AnimNode, BaseAnimation};
 use crate::DURATION_ZERO;
-use std::time::Duration;
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use core::{fmt, ops::Range, time::Duration};
 
 /// like `Iterator`, but does not consume any element
 ///
 /// built-in types that derives [`Cursor`]
 /// - `Vec<T>`
+/// - `VecDeque<T>`
 /// - `[T]`
 /// - `&[T]`
-/// - `Box<T>` where `T:Cursor`
-/// - `&T` where `T:Cursor`
+/// - `Range<usize>`, which outputs the numbers
+/// - `Box<T>` where `T:Cursor`, e.g. `Box<[T]>`
+/// - `&T` where `T:Cursor`, e.g. `&Vec<T>`
 pub trait Cursor {
     /// item of the cursor
     type Item;
@@ -28,7 +31,7 @@ impl<T: Clone> Cursor for [T] {
     }
     #[inline]
     fn index(&self, n: usize) -> T {
-        self[n].to_owned()
+        self[n].clone()
     }
 }
 
@@ -40,7 +43,7 @@ impl<T: Clone> Cursor for &[T] {
     }
     #[inline]
     fn index(&self, n: usize) -> T {
-        self[n].to_owned()
+        self[n].clone()
     }
 }
 
@@ -52,7 +55,32 @@ impl<T: Clone> Cursor for Vec<T> {
     }
     #[inline]
     fn index(&self, n: usize) -> T {
-        self[n].to_owned()
+        self[n].clone()
+    }
+}
+
+impl<T: Clone> Cursor for VecDeque<T> {
+    type Item = T;
+    #[inline]
+    fn size(&self) -> Option<usize> {
+        Some(self.len())
+    }
+    #[inline]
+    fn index(&self, n: usize) -> T {
+        self[n].clone()
+    }
+}
+
+impl Cursor for Range<usize> {
+    type Item = usize;
+    #[inline]
+    fn size(&self) -> Option<usize> {
+        Some(self.len())
+    }
+    #[inline]
+    fn index(&self, n: usize) -> usize {
+        assert!(n < self.len(), "index out of range: {}", n);
+        self.start + n
     }
 }
 
@@ -68,7 +96,7 @@ impl<T: Cursor> Cursor for &T {
     }
 }
 
-impl<T: Cursor> Cursor for Box<T> {
+impl<T: Cursor + ?Sized> Cursor for Box<T> {
     type Item = T::Item;
     #[inline]
     fn size(&self) -> Option<usize> {
@@ -127,6 +155,15 @@ where
     }
 }
 
+impl<F, T> fmt::Debug for Infinite<F, T>
+where
+    F: Fn(usize) -> T,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Infinite").finish_non_exhaustive()
+    }
+}
+
 impl<F, T> Cursor for Infinite<F, T>
 where
     F: Fn(usize) -> T,
@@ -142,10 +179,42 @@ where
     }
 }
 
+/// what a finite [`StepAnimation`] outputs once it's finished, default [`StepEnd::ClampLast`]
+#[derive(Debug, Clone)]
+pub enum StepEnd<T> {
+    /// stays at the last element
+    ClampLast,
+    /// wraps around to the start, as if it keeps stepping
+    Wrap,
+    /// outputs the specified value
+    Hold(T),
+}
+
+impl<T> Default for StepEnd<T> {
+    #[inline]
+    fn default() -> Self {
+        StepEnd::ClampLast
+    }
+}
+
+/// [`StepEnd`] with its clone function captured, so outputting the held value
+/// does not require `Clone` for all steps
+#[derive(Debug, Clone)]
+enum End<T> {
+    ClampLast,
+    Wrap,
+    Hold(T, fn(&T) -> T),
+}
+
 #[derive(Debug, Clone)]
 pub struct StepAnimation<T: Cursor> {
     src: T,
     interval: Duration,
+    /// end time of each step if they last differently
+    ends: Option<Vec<Duration>>,
+    /// how many times to go through the steps, `None` means indefinitely
+    laps: Option<u32>,
+    end: End<T::Item>,
 }
 
 impl<T> StepAnimation<T>
@@ -158,6 +227,9 @@ where
         Self {
             src,
             interval: DURATION_ZERO,
+            ends: None,
+            laps: Some(1),
+            end: End::ClampLast,
         }
     }
 
@@ -165,6 +237,90 @@ where
     #[inline]
     pub fn interval(mut self, interval: Duration) -> Self {
         self.interval = interval;
+        self.ends = None;
+        self
+    }
+
+    /// every step lasts for its own duration, e.g. sprite frames or slides which hold for
+    /// different lengths; panics if the steps are infinite
+    #[inline]
+    pub fn interval_fn(mut self, f: impl Fn(usize) -> Duration) -> Self {
+        let size = self.src.size().expect("infinite steps");
+        let mut end = DURATION_ZERO;
+        self.ends = Some(
+            (0..size)
+                .map(|n| {
+                    end += f(n);
+                    end
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// duration of every step, see [`StepAnimation::interval_fn()`];
+    /// panics if there are less intervals than the steps
+    #[inline]
+    pub fn intervals(self, intervals: &[Duration]) -> Self {
+        self.interval_fn(|n| intervals[n])
+    }
+
+    /// return to the first step after the last one, and keep going indefinitely
+    #[inline]
+    pub fn cycle_frames(mut self) -> Self {
+        self.laps = None;
+        self
+    }
+
+    /// go through the steps `laps` times, returning to the first step after the last one;
+    /// default once, see [`StepAnimation::cycle_frames()`]
+    #[inline]
+    pub fn laps(mut self, laps: u32) -> Self {
+        self.laps = Some(laps);
+        self
+    }
+
+    /// duration of going through the steps once; `None` if they are infinite
+    #[inline]
+    fn lap(&self) -> Option<Duration> {
+        if let Some(ends) = &self.ends {
+            return Some(ends.last().copied().unwrap_or_default());
+        }
+        if self.interval == DURATION_ZERO {
+            return Some(DURATION_ZERO);
+        }
+        self.src
+            .size()
+            .map(|size| self.interval.mul_f64(size as f64))
+    }
+
+    /// index of the step at `elapsed`, which wraps around after a lap
+    #[inline]
+    fn step(&self, elapsed: Duration) -> usize {
+        let elapsed = match self.lap() {
+            Some(lap) => Duration::from_nanos((elapsed.as_nanos() % lap.as_nanos()) as u64),
+            None => elapsed,
+        };
+        match &self.ends {
+            Some(ends) => ends.partition_point(|end| *end <= elapsed),
+            None => {
+                let n = elapsed.as_secs_f64() / self.interval.as_secs_f64();
+                n as usize
+            }
+        }
+    }
+
+    /// what to output once it's finished, default [`StepEnd::ClampLast`]
+    #[inline]
+    pub fn end(mut self, end: StepEnd<T::Item>) -> Self
+    where
+        T::Item: Clone,
+    {
+        self.end = match end {
+            StepEnd::ClampLast => End::ClampLast,
+            StepEnd::Wrap => End::Wrap,
+            StepEnd::Hold(value) => End::Hold(value, Clone::clone),
+        };
         self
     }
 }
@@ -177,24 +333,41 @@ where
 
     #[inline]
     fn duration(&self) -> Option<Duration> {
-        if self.interval == DURATION_ZERO {
-            return Some(DURATION_ZERO);
+        let lap = self.lap()?;
+        match self.laps {
+            _ if lap == DURATION_ZERO => Some(DURATION_ZERO),
+            Some(laps) => Some(lap.checked_mul(laps).unwrap_or(Duration::MAX)),
+            None => None,
         }
-        self.src
-            .size()
-            .map(|size| self.interval.mul_f64(size as f64))
     }
 
     #[inline]
     fn animate(&self, elapsed: Duration) -> Self::Item {
         let n = match self.duration() {
             Some(duration) if duration == DURATION_ZERO => 0,
-            Some(duration) if elapsed >= duration => self.src.size().unwrap(),
-            _ => {
-                let n = elapsed.as_secs_f64() / self.interval.as_secs_f64();
-                n as usize
+            Some(duration) if elapsed >= duration => {
+                let size = self.src.size().unwrap();
+                match &self.end {
+                    End::ClampLast => size.saturating_sub(1),
+                    End::Wrap => self.step(elapsed),
+                    End::Hold(value, clone) => return clone(value),
+                }
             }
+            _ => self.step(elapsed),
         };
         self.src.index(n)
     }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        match self.laps {
+            Some(laps) if laps <= 1 => None,
+            _ => self.lap().filter(|lap| *lap > DURATION_ZERO),
+        }
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("steps", self.duration())
+    }
 }
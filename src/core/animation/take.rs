@@ -4,10 +4,10 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use super::{Animation, BaseAnimation};
+use super::{AnimNode, Animation, BaseAnimation};
 use crate::core::DURATION_ZERO;
-use std::time::Duration;
-/// repeat animations
+use core::time::Duration;
+/// takes the beginning of the source animation, see [`Animation::take()`]
 #[derive(Debug, Clone)]
 pub struct Take<T: Animation> {
     src: T,
@@ -45,4 +45,14 @@ impl<T: Animation> BaseAnimation for Take<T> {
             self.src.animate(elapsed)
         }
     }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.src.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("take", self.duration()).child(self.src.describe())
+    }
 }
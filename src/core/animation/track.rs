@@ -0,0 +1,74 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, Animation, BaseAnimation};
+use crate::core::DURATION_ZERO;
+use core::time::Duration;
+
+/// a child track which starts at an offset within its parent composition,
+/// see [`Animation::offset_within()`]
+#[derive(Debug, Clone)]
+pub struct Track<T: Animation> {
+    src: T,
+    offset: Duration,
+}
+
+impl<T: Animation> Track<T> {
+    #[inline]
+    pub(super) fn new(src: T, offset: Duration) -> Self {
+        Self { src, offset }
+    }
+
+    /// where the track starts within its parent
+    #[inline]
+    pub fn offset(&self) -> Duration {
+        self.offset
+    }
+
+    /// where the track ends within its parent; `None` if it's never finished
+    #[inline]
+    pub fn end(&self) -> Option<Duration> {
+        self.duration()
+    }
+
+    /// is the track playing at `elapsed` of its parent? false before its offset and once
+    /// it's finished, e.g. to decide whether to show it
+    #[inline]
+    pub fn is_active(&self, elapsed: Duration) -> bool {
+        elapsed >= self.offset && !matches!(self.end(), Some(end) if elapsed >= end)
+    }
+}
+
+impl<T: Animation> BaseAnimation for Track<T> {
+    type Item = T::Item;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        self.src.duration().map(|duration| self.offset + duration)
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        let elapsed = elapsed.checked_sub(self.offset).unwrap_or(DURATION_ZERO);
+        self.src.animate(elapsed)
+    }
+
+    #[inline]
+    fn normalized_time(&self, elapsed: Duration) -> Option<(f64, f64)> {
+        let elapsed = elapsed.checked_sub(self.offset).unwrap_or(DURATION_ZERO);
+        self.src.normalized_time(elapsed)
+    }
+
+    #[inline]
+    fn period(&self) -> Option<Duration> {
+        self.src.period()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("track", self.duration()).child(self.src.describe())
+    }
+}
@@ -0,0 +1,61 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{AnimNode, Animation, BaseAnimation};
+use crate::core::DURATION_ZERO;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// values of the recent past along with the current one, see [`Animation::trail()`]
+#[derive(Debug, Clone)]
+pub struct Trail<T: Animation> {
+    src: T,
+    count: usize,
+    spacing: Duration,
+}
+
+impl<T: Animation> Trail<T> {
+    #[inline]
+    pub(super) fn new(src: T, count: usize, spacing: Duration) -> Self {
+        Self {
+            src,
+            count,
+            spacing,
+        }
+    }
+
+    /// how long the oldest value lags behind
+    #[inline]
+    fn lag(&self) -> Duration {
+        self.spacing * self.count.saturating_sub(1) as u32
+    }
+}
+
+impl<T: Animation> BaseAnimation for Trail<T> {
+    type Item = Vec<T::Item>;
+
+    /// until the oldest value reaches the end
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        self.src.duration().map(|duration| duration + self.lag())
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        (0..self.count)
+            .map(|i| {
+                let past = self.spacing * i as u32;
+                self.src
+                    .animate(elapsed.checked_sub(past).unwrap_or(DURATION_ZERO))
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("trail", self.duration()).child(self.src.describe())
+    }
+}
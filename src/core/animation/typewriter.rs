@@ -0,0 +1,115 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{steps_infinite, AnimNode, Animation, BaseAnimation};
+use alloc::{string::String, vec::Vec};
+use core::{convert::TryFrom, time::Duration};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// reveals text progressively, see [`crate::builder::typewriter()`]
+#[derive(Debug, Clone)]
+pub struct Typewriter {
+    text: String,
+    /// byte offset after each char
+    chars: Vec<usize>,
+    /// number of chars at the end of each word
+    words: Vec<usize>,
+    interval: Duration,
+    per_word: bool,
+}
+
+impl Typewriter {
+    /// panics if `chars_per_second` is not positive
+    #[inline]
+    pub(super) fn new(text: &str, chars_per_second: f64) -> Self {
+        assert!(
+            chars_per_second > 0.0 && chars_per_second.is_finite(),
+            "invalid chars per second: {}",
+            chars_per_second
+        );
+        let chars: Vec<_> = text.char_indices().map(|(i, c)| i + c.len_utf8()).collect();
+        let mut words = Vec::new();
+        let mut prev: Option<char> = None;
+        for (n, c) in text.chars().enumerate() {
+            if c.is_whitespace() && matches!(prev, Some(prev) if !prev.is_whitespace()) {
+                words.push(n);
+            }
+            prev = Some(c);
+        }
+        if matches!(prev, Some(prev) if !prev.is_whitespace()) {
+            words.push(chars.len());
+        }
+        Self {
+            text: text.into(),
+            chars,
+            words,
+            // at least 1ns, or nothing is revealed at a very high speed
+            interval: Duration::try_from_secs_f64(1.0 / chars_per_second)
+                .unwrap_or(Duration::MAX)
+                .max(Duration::from_nanos(1)),
+            per_word: false,
+        }
+    }
+
+    /// reveal whole words at once, each at the time its last char would be revealed
+    #[inline]
+    pub fn per_word(mut self, per_word: bool) -> Self {
+        self.per_word = per_word;
+        self
+    }
+
+    /// outputs a blinking cursor along with the text, visible for `blink` then hidden for `blink`;
+    /// it keeps blinking after the text is fully revealed
+    #[inline]
+    pub fn cursor(self, blink: Duration) -> impl Animation<Item = (String, bool)> + Clone {
+        self.zip(steps_infinite(|i| i % 2 == 0, blink))
+    }
+
+    /// number of revealed chars
+    #[inline]
+    fn revealed(&self, elapsed: Duration) -> usize {
+        let count = elapsed.as_nanos() / self.interval.as_nanos();
+        let count = count.min(self.chars.len() as u128) as usize;
+        if !self.per_word {
+            return count;
+        }
+        match self.words.partition_point(|n| *n <= count) {
+            0 => 0,
+            i => self.words[i - 1],
+        }
+    }
+}
+
+impl BaseAnimation for Typewriter {
+    type Item = String;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        let nanos = self
+            .interval
+            .as_nanos()
+            .saturating_mul(self.chars.len() as u128);
+        let duration = u64::try_from(nanos / NANOS_PER_SEC)
+            .map(|secs| Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+            .unwrap_or(Duration::MAX);
+        Some(duration)
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        let end = match self.revealed(elapsed) {
+            0 => 0,
+            n => self.chars[n - 1],
+        };
+        self.text[..end].into()
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("typewriter", self.duration())
+    }
+}
@@ -0,0 +1,312 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{clock::Clock, easing, Animatable, MaybeSend, MaybeSync, DURATION_ZERO};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, time::Duration};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// id of an ease function registered into [`BatchAnimator`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EasingId(usize);
+
+impl EasingId {
+    /// the default ease function of [`BatchAnimator`], [`easing::linear`]
+    pub const LINEAR: EasingId = EasingId(0);
+}
+
+/// animates many values in one pass.
+///
+/// The records are stored in struct-of-arrays form, so thousands of values
+/// can be animated without one boxed [`crate::Timeline`] for each.
+///
+/// ## Example
+/// ```rust
+/// use core::time::Duration;
+/// use anim::batch::{BatchAnimator, EasingId};
+///
+/// let mut batch: BatchAnimator<f32> = BatchAnimator::new();
+/// for i in 0..1000 {
+///     batch.push(0.0, i as f32, Duration::from_millis(300), EasingId::LINEAR);
+/// }
+/// let animating = batch.update();
+/// let values = batch.values();
+/// ```
+pub struct BatchAnimator<
+    T,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    clock: C,
+    easings: Vec<easing::BoxFunction>,
+    from: Vec<T>,
+    to: Vec<T>,
+    start: Vec<C::Time>,
+    duration: Vec<Duration>,
+    easing: Vec<EasingId>,
+    values: Vec<T>,
+    completed: Vec<bool>,
+}
+
+impl<T: Animatable, C: Clock> BatchAnimator<T, C> {
+    /// create an empty batch
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            clock: Default::default(),
+            easings: vec![Box::new(easing::linear())],
+            from: Vec::new(),
+            to: Vec::new(),
+            start: Vec::new(),
+            duration: Vec::new(),
+            easing: Vec::new(),
+            values: Vec::new(),
+            completed: Vec::new(),
+        }
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// register an ease function, which can be shared by many records
+    #[inline]
+    pub fn register_easing(
+        &mut self,
+        func: impl easing::Function + Clone + MaybeSend + MaybeSync + 'static,
+    ) -> EasingId {
+        self.easings.push(Box::new(func));
+        EasingId(self.easings.len() - 1)
+    }
+
+    /// add a record which starts animating from now on, returns its index
+    ///
+    /// panics if the easing was not registered into current batch
+    #[inline]
+    pub fn push(&mut self, from: T, to: T, duration: Duration, easing: EasingId) -> usize {
+        assert!(easing.0 < self.easings.len());
+        let now = self.clock.now();
+        self.values.push(from.clone());
+        self.from.push(from);
+        self.to.push(to);
+        self.start.push(now);
+        self.duration.push(duration);
+        self.easing.push(easing);
+        self.completed.push(false);
+        self.values.len() - 1
+    }
+
+    /// remove the record at specified index; the last record is moved to that index
+    #[inline]
+    pub fn swap_remove(&mut self, index: usize) {
+        self.from.swap_remove(index);
+        self.to.swap_remove(index);
+        self.start.swap_remove(index);
+        self.duration.swap_remove(index);
+        self.easing.swap_remove(index);
+        self.values.swap_remove(index);
+        self.completed.swap_remove(index);
+    }
+
+    /// remove all records
+    #[inline]
+    pub fn clear(&mut self) {
+        self.from.clear();
+        self.to.clear();
+        self.start.clear();
+        self.duration.clear();
+        self.easing.clear();
+        self.values.clear();
+        self.completed.clear();
+    }
+
+    /// number of records
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// has no record?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// evaluates all records, returns the number of records still animating
+    pub fn update(&mut self) -> usize {
+        self.clock.tick();
+        let now = self.clock.now();
+        let Self {
+            easings,
+            from,
+            to,
+            start,
+            duration,
+            easing,
+            values,
+            completed,
+            ..
+        } = self;
+        let mut animating = 0;
+        for (i, (value, completed)) in values.iter_mut().zip(completed.iter_mut()).enumerate() {
+            let record = Record {
+                easing: &*easings[easing[i].0],
+                from: &from[i],
+                to: &to[i],
+                elapsed: C::checked_elapsed(&now, &start[i]).unwrap_or(DURATION_ZERO),
+                duration: duration[i],
+            };
+            if record.eval(value, completed) {
+                animating += 1;
+            }
+        }
+        animating
+    }
+
+    /// animated values, updated by [`BatchAnimator::update()`]
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// animated value at specified index
+    #[inline]
+    pub fn value(&self, index: usize) -> &T {
+        &self.values[index]
+    }
+
+    /// is the record at specified index completed?
+    #[inline]
+    pub fn is_completed(&self, index: usize) -> bool {
+        self.completed[index]
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, C> BatchAnimator<T, C>
+where
+    T: Animatable + Send + Sync,
+    C: Clock,
+    C::Time: Sync,
+{
+    /// evaluates all records across threads, returns the number of records still animating
+    ///
+    /// see [`BatchAnimator::update()`]
+    pub fn par_update(&mut self) -> usize {
+        self.clock.tick();
+        let now = self.clock.now();
+        let Self {
+            easings,
+            from,
+            to,
+            start,
+            duration,
+            easing,
+            values,
+            completed,
+            ..
+        } = self;
+        values
+            .par_iter_mut()
+            .zip(completed.par_iter_mut())
+            .enumerate()
+            .map(|(i, (value, completed))| {
+                let record = Record {
+                    easing: &*easings[easing[i].0],
+                    from: &from[i],
+                    to: &to[i],
+                    elapsed: C::checked_elapsed(&now, &start[i]).unwrap_or(DURATION_ZERO),
+                    duration: duration[i],
+                };
+                record.eval(value, completed)
+            })
+            .filter(|animating| *animating)
+            .count()
+    }
+}
+
+struct Record<'a, T> {
+    easing: &'a dyn easing::Function,
+    from: &'a T,
+    to: &'a T,
+    elapsed: Duration,
+    duration: Duration,
+}
+
+impl<T: Animatable> Record<'_, T> {
+    /// returns true if it's still animating
+    #[inline]
+    fn eval(&self, value: &mut T, completed: &mut bool) -> bool {
+        if *completed {
+            return false;
+        }
+        let time = if self.duration == DURATION_ZERO || self.elapsed >= self.duration {
+            *completed = true;
+            1.0
+        } else {
+            self.elapsed.as_secs_f64() / self.duration.as_secs_f64()
+        };
+        let time = self.easing.ease(time);
+        *value = self.from.animate(self.to, time);
+        !*completed
+    }
+}
+
+impl<T: Animatable, C: Clock> Default for BatchAnimator<T, C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, C: Clock> fmt::Debug for BatchAnimator<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchAnimator")
+            .field("values", &self.values)
+            .field("easings", &self.easings.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::clock::TestClock;
+
+    #[test]
+    fn test_batch() {
+        let mut batch: BatchAnimator<f32, TestClock> = BatchAnimator::new();
+        let quad = batch.register_easing(easing::quad_ease());
+        batch.push(0.0, 1.0, Duration::from_millis(1000), EasingId::LINEAR);
+        batch.push(0.0, 1.0, Duration::from_millis(500), quad);
+        assert_eq!(batch.len(), 2);
+
+        batch.clock_mut().0 = Duration::from_millis(250);
+        assert_eq!(batch.update(), 2);
+        assert_eq!(batch.values(), &[0.25, 0.25]);
+
+        batch.clock_mut().0 = Duration::from_millis(500);
+        assert_eq!(batch.update(), 1);
+        assert_eq!(batch.values(), &[0.5, 1.0]);
+        assert!(batch.is_completed(1));
+
+        #[cfg(feature = "rayon")]
+        assert_eq!(batch.par_update(), 1);
+
+        batch.swap_remove(0);
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.value(0), &1.0);
+    }
+}
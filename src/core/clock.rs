@@ -1,25 +1,141 @@
-use std::{
-    ops::Sub,
-    time::{Duration, Instant},
-};
+use core::{ops::Sub, time::Duration};
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 /// [`Clock`] allow you to control the time
+///
+/// time is not required to be monotonic, if the clock overrides [`Clock::checked_elapsed()`];
+/// then if it goes backwards, no time passes until it catches up. the built-in clocks do
 pub trait Clock: Default {
     /// represents the time
     type Time: Sub<Output = Duration> + Clone;
 
     /// current time
     fn now(&self) -> Self::Time;
+
+    /// called once at the beginning of every update, e.g. [`crate::Timeline::update()`];
+    /// it does nothing by default
+    #[inline]
+    fn tick(&mut self) {}
+
+    /// time passed from `since` to `now`, or `None` if the clock went backwards.
+    ///
+    /// it subtracts the time by default, which might panic if the time goes backwards;
+    /// custom clocks whose time might go backwards must override it
+    #[inline]
+    fn checked_elapsed(now: &Self::Time, since: &Self::Time) -> Option<Duration> {
+        Some(now.clone() - since.clone())
+    }
+
+    /// time passed since the specified time; zero if the clock went backwards
+    #[inline]
+    fn elapsed(&self, since: &Self::Time) -> Duration {
+        Self::checked_elapsed(&self.now(), since).unwrap_or(Duration::from_secs(0))
+    }
+}
+
+/// a deterministic [`Clock`], which advances exactly one step per update regardless of wall time.
+///
+/// it guarantees reproducible outputs, e.g. for golden tests, headless rendering or video export.
+/// default 60 fps.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{easing, FixedClock, Options, Timeline};
+///
+/// let mut timeline: Timeline<f32, FixedClock> = Options::new(0.0, 1.0)
+///     .easing(easing::linear())
+///     .duration(Duration::from_millis(100))
+///     .into();
+/// *timeline.clock_mut() = FixedClock::new(10.0);
+/// timeline.begin();
+/// timeline.update();
+/// assert_eq!(timeline.value(), 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedClock {
+    now: Duration,
+    step: Duration,
+}
+
+impl FixedClock {
+    /// advances `1/fps` per update
+    ///
+    /// panics if fps is not positive
+    #[inline]
+    pub fn new(fps: f64) -> Self {
+        assert!(fps > 0.0);
+        Self::with_step(Duration::from_secs_f64(1.0 / fps))
+    }
+
+    /// advances `step` per update
+    #[inline]
+    pub fn with_step(step: Duration) -> Self {
+        Self {
+            now: Duration::from_secs(0),
+            step,
+        }
+    }
+
+    /// time step per update
+    #[inline]
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// advance the clock manually
+    #[inline]
+    pub fn advance(&mut self, duration: Duration) {
+        self.now += duration;
+    }
+}
+
+impl Default for FixedClock {
+    #[inline]
+    fn default() -> Self {
+        Self::new(60.0)
+    }
+}
+
+impl Clock for FixedClock {
+    type Time = Duration;
+
+    #[inline]
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    #[inline]
+    fn tick(&mut self) {
+        self.now += self.step;
+    }
+
+    #[inline]
+    fn checked_elapsed(now: &Duration, since: &Duration) -> Option<Duration> {
+        now.checked_sub(*since)
+    }
 }
 
 /// a default implementation of [`Clock`]
+#[cfg(feature = "std")]
 #[derive(Debug, Default)]
 pub struct DefaultClock;
 
+#[cfg(feature = "std")]
 impl Clock for DefaultClock {
     type Time = Instant;
     #[inline]
     fn now(&self) -> Instant {
         Instant::now()
     }
+
+    #[inline]
+    fn checked_elapsed(now: &Instant, since: &Instant) -> Option<Duration> {
+        now.checked_duration_since(*since)
+    }
 }
+
+/// a manual [`Clock`] for tests
+#[cfg(test)]
+pub(crate) use crate::test::MockClock as TestClock;
@@ -0,0 +1,173 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use crate::core::{
+    easing::{self, BoxFunction, Function},
+    MaybeSend, MaybeSync, DEFAULT_ANIMATION_DURATION,
+};
+use alloc::boxed::Box;
+use core::{fmt, time::Duration};
+
+/// motion style of an app, consulted by [`crate::Options::new_in()`], so that the defaults are
+/// changed in one place instead of repeating `.duration(...).easing(...)` everywhere.
+///
+/// a scope inherits the defaults of its parent by cloning it, then overrides some of them.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{easing, AnimContext, Options};
+///
+/// let app = AnimContext::default().duration(Duration::from_millis(250));
+/// // dialogs bounce, the others follow the app
+/// let dialog = app.clone().easing(easing::bounce_ease());
+///
+/// let opacity = Options::new_in(&app, 0.0, 1.0);
+/// let offset = Options::new_in(&dialog, 100.0, 0.0);
+/// ```
+#[derive(Clone)]
+pub struct AnimContext {
+    duration: Duration,
+    easing: BoxFunction,
+    reduce_motion: bool,
+    time_scale: f32,
+}
+
+impl Default for AnimContext {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            duration: DEFAULT_ANIMATION_DURATION,
+            easing: Box::new(easing::cubic_ease()),
+            reduce_motion: false,
+            time_scale: 1.0,
+        }
+    }
+}
+
+impl AnimContext {
+    /// default duration of animations, before it's scaled by [`AnimContext::time_scale()`];
+    /// default 1 second
+    #[inline]
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// default ease function, default [`easing::cubic_ease`]
+    #[inline]
+    pub fn easing(mut self, func: impl Function + Clone + MaybeSend + MaybeSync + 'static) -> Self {
+        self.easing = Box::new(func);
+        self
+    }
+
+    /// reduce motion, e.g. to respect the accessibility settings of the platform;
+    /// animations jump to the end immediately
+    #[inline]
+    pub fn reduce_motion(mut self, reduce_motion: bool) -> Self {
+        self.reduce_motion = reduce_motion;
+        self
+    }
+
+    /// how fast animations play, e.g. 0.5 for slow motion, 2.0 for a turbo mode; default 1.0
+    ///
+    /// ## Panic
+    /// panics if `time_scale` is not positive
+    #[inline]
+    pub fn time_scale(mut self, time_scale: f32) -> Self {
+        assert!(time_scale > 0.0, "time scale must be positive");
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// the default duration, scaled
+    #[inline]
+    pub fn default_duration(&self) -> Duration {
+        self.scaled(self.duration)
+    }
+
+    /// the default ease function
+    #[inline]
+    pub fn default_easing(&self) -> &BoxFunction {
+        &self.easing
+    }
+
+    /// are motions reduced?
+    #[inline]
+    pub fn reduces_motion(&self) -> bool {
+        self.reduce_motion
+    }
+
+    /// current time scale
+    #[inline]
+    pub fn current_time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// scale `duration` by the time scale, e.g. for explicit durations
+    #[inline]
+    pub fn scaled(&self, duration: Duration) -> Duration {
+        if self.time_scale == 1.0 {
+            duration
+        } else {
+            duration.div_f64(self.time_scale as f64)
+        }
+    }
+}
+
+impl fmt::Debug for AnimContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnimContext")
+            .field("duration", &self.duration)
+            .field("reduce_motion", &self.reduce_motion)
+            .field("time_scale", &self.time_scale)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{animation::BaseAnimation, Options};
+
+    #[test]
+    fn test_new_in() {
+        let ms = Duration::from_millis;
+        let ctx = AnimContext::default()
+            .duration(ms(200))
+            .easing(easing::linear())
+            .time_scale(2.0);
+        let animation = Options::new_in(&ctx, 0.0, 1.0).build();
+        assert_eq!(animation.duration(), Some(ms(100)));
+        assert_eq!(animation.animate(ms(50)), 0.5);
+
+        // a scope overrides its parent
+        let scope = ctx.clone().duration(ms(400));
+        assert_eq!(scope.default_duration(), ms(200));
+        assert_eq!(ctx.default_duration(), ms(100));
+
+        // explicit settings win, scaled as well
+        let animation = Options::new_in(&ctx, 0.0, 1.0)
+            .duration(ms(300))
+            .delay(ms(100))
+            .build();
+        assert_eq!(animation.duration(), Some(ms(200)));
+
+        let reduced = ctx.reduce_motion(true);
+        let animation = Options::new_in(&reduced, 0.0, 1.0).build();
+        assert_eq!(animation.animate(Duration::from_secs(0)), 1.0);
+        assert_eq!(animation.duration(), Some(Duration::from_secs(0)));
+
+        // repeating animations stay at the end
+        let animation = Options::new_in(&reduced, 0.0, 1.0)
+            .duration(ms(300))
+            .auto_reverse(true)
+            .forever()
+            .build();
+        assert_eq!(animation.animate(Duration::from_secs(0)), 1.0);
+        assert_eq!(animation.animate(ms(150)), 1.0);
+    }
+}
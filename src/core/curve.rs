@@ -0,0 +1,206 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{
+    animation::{AnimNode, Animation, BaseAnimation},
+    easing::Function,
+    DURATION_ZERO,
+};
+use core::{fmt, time::Duration};
+
+/// values over the normalized progress in 0..=1, which is independent of time, e.g. driven
+/// by scroll positions, gestures or audio indices.
+///
+/// closures of `Fn(f64) -> T` are curves; attach a duration by [`Curve::with_duration()`] to
+/// get an [`Animation`], or detach it by [`from_animation()`].
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{curve, Animation, Curve, Options};
+///
+/// let curve = curve::from_animation(Options::new(0.0, 100.0).build());
+/// let value = curve.eval(0.5);
+///
+/// let animation = (|progress: f64| progress * 2.0).with_duration(Duration::from_secs(1));
+/// ```
+pub trait Curve {
+    /// value on the curve
+    type Item;
+
+    /// value at `progress`, which is between 0-1
+    fn eval(&self, progress: f64) -> Self::Item;
+
+    /// map from one type to another
+    #[inline]
+    fn map<F, T>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Self::Item) -> T,
+    {
+        Map { src: self, f }
+    }
+
+    /// ease the progress by `func`
+    #[inline]
+    fn ease<F: Function>(self, func: F) -> Eased<Self, F>
+    where
+        Self: Sized,
+    {
+        Eased { src: self, func }
+    }
+
+    /// an [`Animation`] going through the curve within `duration`
+    #[inline]
+    fn with_duration(self, duration: Duration) -> Timed<Self>
+    where
+        Self: Sized,
+    {
+        Timed {
+            src: self,
+            duration,
+        }
+    }
+}
+
+/// a curve going through a finite `animation`, progress 1 at the end of it
+///
+/// panics if the animation never finishes
+#[inline]
+pub fn from_animation<A: Animation>(animation: A) -> Normalized<A> {
+    let duration = animation
+        .duration()
+        .expect("curve requires a finite animation");
+    Normalized {
+        src: animation,
+        duration,
+    }
+}
+
+impl<T, F: Fn(f64) -> T> Curve for F {
+    type Item = T;
+
+    #[inline]
+    fn eval(&self, progress: f64) -> T {
+        self(progress)
+    }
+}
+
+/// see [`Curve::map()`]
+#[derive(Debug, Clone)]
+pub struct Map<C, F> {
+    src: C,
+    f: F,
+}
+
+impl<C: Curve, F: Fn(C::Item) -> T, T> Curve for Map<C, F> {
+    type Item = T;
+
+    #[inline]
+    fn eval(&self, progress: f64) -> T {
+        (self.f)(self.src.eval(progress))
+    }
+}
+
+/// see [`Curve::ease()`]
+#[derive(Clone)]
+pub struct Eased<C, F> {
+    src: C,
+    func: F,
+}
+
+impl<C: Curve, F: Function> Curve for Eased<C, F> {
+    type Item = C::Item;
+
+    #[inline]
+    fn eval(&self, progress: f64) -> C::Item {
+        self.src.eval(self.func.ease(progress))
+    }
+}
+
+impl<C: fmt::Debug, F> fmt::Debug for Eased<C, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Eased").field("src", &self.src).finish()
+    }
+}
+
+/// see [`Curve::with_duration()`]
+#[derive(Debug, Clone)]
+pub struct Timed<C> {
+    src: C,
+    duration: Duration,
+}
+
+impl<C: Curve> BaseAnimation for Timed<C> {
+    type Item = C::Item;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        let progress = if self.duration == DURATION_ZERO || elapsed >= self.duration {
+            1.0
+        } else {
+            elapsed.as_secs_f64() / self.duration.as_secs_f64()
+        };
+        self.src.eval(progress)
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("curve", self.duration())
+    }
+}
+
+/// see [`from_animation()`]
+#[derive(Debug, Clone)]
+pub struct Normalized<A> {
+    src: A,
+    duration: Duration,
+}
+
+impl<A: Animation> Curve for Normalized<A> {
+    type Item = A::Item;
+
+    /// the progress is clamped into 0..=1
+    #[inline]
+    fn eval(&self, progress: f64) -> A::Item {
+        let progress = progress.clamp(0.0, 1.0);
+        self.src.animate(self.duration.mul_f64(progress))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{easing, Options};
+
+    #[test]
+    fn test_curve() {
+        let curve = from_animation(
+            Options::new(0.0, 100.0)
+                .easing(easing::linear())
+                .duration(Duration::from_millis(500))
+                .build(),
+        );
+        assert_eq!(curve.eval(0.25), 25.0);
+        assert_eq!(curve.eval(2.0), 100.0);
+
+        let curve = curve.ease(easing::quad_ease()).map(|v| v as i32);
+        assert_eq!(curve.eval(0.5), 25);
+
+        let animation = curve.with_duration(Duration::from_secs(2));
+        assert_eq!(animation.duration(), Some(Duration::from_secs(2)));
+        assert_eq!(animation.animate(Duration::from_secs(1)), 25);
+        assert_eq!(animation.animate(Duration::from_secs(3)), 100);
+
+        let animation = (|progress: f64| progress * 2.0).with_duration(Duration::from_secs(1));
+        assert_eq!(animation.animate(Duration::from_millis(500)), 1.0);
+    }
+}
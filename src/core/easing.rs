@@ -4,16 +4,47 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use dyn_clone::DynClone;
 pub use functions::*;
 
+/// reference samples of built-in ease functions
+#[cfg(feature = "test-vectors")]
+pub mod vectors;
+
 /// easing function
 pub trait Function: DynClone {
     /// output time based on normalized time, which is between 0-1
     fn ease(&self, normalized_time: f64) -> f64;
 }
 
-impl<F: Function + Clone> Function for Box<F> {
+/// boxed ease function
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+pub type BoxFunction = Box<dyn Function>;
+/// boxed ease function, which can be shared across threads
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+pub type BoxFunction = Box<dyn Function + Send + Sync>;
+
+dyn_clone::clone_trait_object!(Function);
+
+impl Function for BoxFunction {
+    #[inline]
+    fn ease(&self, normalized_time: f64) -> f64 {
+        (**self).ease(normalized_time)
+    }
+}
+
+/// shared ease function; cloning it does not clone the function, so an expensive one, e.g. a
+/// baked lookup table, can be used by many animations, see [`Options::with_easing()`]
+///
+/// [`Options::with_easing()`]: crate::Options::with_easing()
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+pub type ArcFunction = Arc<dyn Function>;
+/// shared ease function, see [`BoxFunction`]
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+pub type ArcFunction = Arc<dyn Function + Send + Sync>;
+
+impl<F: Function + ?Sized> Function for Arc<F> {
     #[inline]
     fn ease(&self, normalized_time: f64) -> f64 {
         (**self).ease(normalized_time)
@@ -27,7 +58,8 @@ pub trait FunctionClone: Function + Clone {}
 impl<F: Function + Clone> FunctionClone for F {}
 
 /// easing mode, default [`EasingMode::In`]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EasingMode {
     /// ease in
     In,
@@ -87,6 +119,14 @@ impl<F: Fn(f64) -> f64 + Clone> Function for Easing<F> {
     }
 }
 
+/// closures, e.g. `|t| t * t * (3.0 - 2.0 * t)`, are ease functions as they are
+impl<F: Fn(f64) -> f64 + Clone> Function for F {
+    #[inline]
+    fn ease(&self, normalized_time: f64) -> f64 {
+        self(normalized_time)
+    }
+}
+
 impl<F: Fn(f64) -> f64 + Clone + 'static> From<F> for Easing<F> {
     #[inline]
     fn from(f: F) -> Self {
@@ -94,115 +134,353 @@ impl<F: Fn(f64) -> f64 + Clone + 'static> From<F> for Easing<F> {
     }
 }
 
+/// common built-in ease functions, which need no allocation to store;
+/// with `serde` feature, they can be loaded by names, e.g. from asset files
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Builtin {
+    /// see [`linear`]
+    Linear,
+    /// see [`sine_ease`]
+    Sine(EasingMode),
+    /// see [`quad_ease`]
+    Quad(EasingMode),
+    /// see [`cubic_ease`]
+    Cubic(EasingMode),
+    /// see [`quart_ease`]
+    Quart(EasingMode),
+    /// see [`qunit_ease`]
+    Qunit(EasingMode),
+    /// see [`expo_ease`]
+    Expo(EasingMode),
+    /// see [`circle_ease`]
+    Circle(EasingMode),
+    /// see [`elastic_ease`]
+    Elastic(EasingMode),
+    /// see [`bounce_ease`]
+    Bounce(EasingMode),
+}
+
+impl Default for Builtin {
+    #[inline]
+    fn default() -> Self {
+        Builtin::Linear
+    }
+}
+
+impl Builtin {
+    /// set ease mod, see [`EasingMode`]; [`Builtin::Linear`] stays as it is
+    #[inline]
+    pub fn mode(self, mode: EasingMode) -> Self {
+        match self {
+            Builtin::Linear => Builtin::Linear,
+            Builtin::Sine(_) => Builtin::Sine(mode),
+            Builtin::Quad(_) => Builtin::Quad(mode),
+            Builtin::Cubic(_) => Builtin::Cubic(mode),
+            Builtin::Quart(_) => Builtin::Quart(mode),
+            Builtin::Qunit(_) => Builtin::Qunit(mode),
+            Builtin::Expo(_) => Builtin::Expo(mode),
+            Builtin::Circle(_) => Builtin::Circle(mode),
+            Builtin::Elastic(_) => Builtin::Elastic(mode),
+            Builtin::Bounce(_) => Builtin::Bounce(mode),
+        }
+    }
+}
+
+impl Function for Builtin {
+    #[inline]
+    fn ease(&self, normalized_time: f64) -> f64 {
+        let t = normalized_time;
+        match *self {
+            Builtin::Linear => EasingMode::In.apply(t, &|t| t),
+            Builtin::Sine(mode) => mode.apply(t, &curves::sine),
+            Builtin::Quad(mode) => mode.apply(t, &|t| curves::pow(t, 2.0)),
+            Builtin::Cubic(mode) => mode.apply(t, &|t| curves::pow(t, 3.0)),
+            Builtin::Quart(mode) => mode.apply(t, &|t| curves::pow(t, 4.0)),
+            Builtin::Qunit(mode) => mode.apply(t, &|t| curves::pow(t, 5.0)),
+            Builtin::Expo(mode) => mode.apply(t, &curves::expo),
+            Builtin::Circle(mode) => mode.apply(t, &curves::circle),
+            Builtin::Elastic(mode) => mode.apply(t, &curves::elastic),
+            Builtin::Bounce(mode) => mode.apply(t, &curves::bounce),
+        }
+    }
+}
+
+/// `n` evenly spaced `(time, output)` samples of `func`, including both ends, e.g. for
+/// previews and snapshot tests; see `vectors` with `test-vectors` feature
+///
+/// ## Example
+/// ```rust
+/// use anim::easing;
+///
+/// let samples = easing::sample(&easing::quad_ease(), 3);
+/// assert_eq!(samples, vec![(0.0, 0.0), (0.5, 0.25), (1.0, 1.0)]);
+/// ```
+#[inline]
+pub fn sample<F: Function + ?Sized>(func: &F, n: usize) -> Vec<(f64, f64)> {
+    let last = n.saturating_sub(1).max(1) as f64;
+    (0..n)
+        .map(|i| {
+            let time = i as f64 / last;
+            (time, func.ease(time))
+        })
+        .collect()
+}
+
+/// curves of [`Builtin`] ease functions, in [`EasingMode::In`]
+mod curves {
+    use crate::core::math;
+    use core::f64::consts::PI;
+
+    #[inline]
+    pub(super) fn sine(t: f64) -> f64 {
+        1.0 - math::cos((t * PI) / 2.0)
+    }
+
+    #[inline]
+    pub(super) fn pow(t: f64, power: f64) -> f64 {
+        math::powf(t, power)
+    }
+
+    #[inline]
+    pub(super) fn expo(t: f64) -> f64 {
+        if t == 0.0 {
+            0.0
+        } else {
+            math::powf(2.0, 10.0 * t - 10.0)
+        }
+    }
+
+    #[inline]
+    pub(super) fn circle(t: f64) -> f64 {
+        1.0 - math::sqrt(1.0 - math::powi(t, 2))
+    }
+
+    #[inline]
+    pub(super) fn elastic(t: f64) -> f64 {
+        const C4: f64 = (2.0 * PI) / 3.0;
+        if t == 0.0 {
+            0.0
+        } else if math::abs(1.0 - t) < f64::EPSILON {
+            1.0
+        } else {
+            -(math::powf(2.0, 10.0 * t - 10.0) * math::sin((t * 10.0 - 10.75) * C4))
+        }
+    }
+
+    #[inline]
+    pub(super) fn bounce(t: f64) -> f64 {
+        const N1: f64 = 7.5625;
+        const D1: f64 = 2.75;
+        let v = if t < 1.0 / D1 {
+            N1 * t * t
+        } else if t < 2.0 / D1 {
+            let t = t - 1.5 / D1;
+            N1 * t * t + 0.75
+        } else if t < 2.5 / D1 {
+            let t = t - 2.25 / D1;
+            N1 * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / D1;
+            N1 * t * t + 0.984375
+        };
+        1.0 - v
+    }
+}
+
 /// please refer to:
 /// - https://easings.net
 /// - http://robertpenner.com/easing/
 /// - https://docs.microsoft.com/en-us/dotnet/desktop/wpf/graphics-multimedia/easing-functions?redirectedfrom=MSDN&view=netframeworkdesktop-4.8
 mod functions {
-    use super::Easing;
-    use std::f64::consts::PI;
+    use super::{Builtin, Easing, Function};
+    use crate::core::math;
+    use core::f64::consts::PI;
 
     /// linear x=t
     #[inline]
-    pub fn linear() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        custom(|t| t)
+    pub fn linear() -> Builtin {
+        Builtin::Linear
     }
 
     /// sine ease
     #[inline]
-    pub fn sine_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        custom(move |t| 1.0 - ((t * PI) / 2.0).cos())
+    pub fn sine_ease() -> Builtin {
+        Builtin::Sine(Default::default())
     }
 
     /// pow ease
     #[inline]
     pub fn pow_ease(power: f32) -> Easing<impl Fn(f64) -> f64 + Clone> {
         let power = power as f64;
-        custom(move |t| t.powf(power))
+        custom(move |t| math::powf(t, power))
     }
 
     /// quadratic ease
     #[inline]
-    pub fn quad_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        pow_ease(2.0)
+    pub fn quad_ease() -> Builtin {
+        Builtin::Quad(Default::default())
     }
 
     /// cubic ease
     #[inline]
-    pub fn cubic_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        pow_ease(3.0)
+    pub fn cubic_ease() -> Builtin {
+        Builtin::Cubic(Default::default())
     }
 
     /// quart ease
     #[inline]
-    pub fn quart_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        pow_ease(4.0)
+    pub fn quart_ease() -> Builtin {
+        Builtin::Quart(Default::default())
     }
 
     /// qunit ease
     #[inline]
-    pub fn qunit_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        pow_ease(5.0)
+    pub fn qunit_ease() -> Builtin {
+        Builtin::Qunit(Default::default())
     }
 
     /// expo ease
     #[inline]
-    pub fn expo_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        custom(|t| {
-            if t == 0.0 {
-                0.0
-            } else {
-                (2.0_f64).powf(10.0 * t - 10.0)
-            }
-        })
+    pub fn expo_ease() -> Builtin {
+        Builtin::Expo(Default::default())
     }
 
     /// circle ease
     #[inline]
-    pub fn circle_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        custom(|t| 1.0 - (1.0 - t.powi(2)).sqrt())
+    pub fn circle_ease() -> Builtin {
+        Builtin::Circle(Default::default())
     }
 
     /// back ease
     #[inline]
     pub fn back_ease(amplitude: f64) -> Easing<impl Fn(f64) -> f64 + Clone> {
-        custom(move |t| t.powi(3) - t * amplitude * (t * PI).sin())
+        custom(move |t| math::powi(t, 3) - t * amplitude * math::sin(t * PI))
     }
 
     /// elastic ease
     #[inline]
-    pub fn elastic_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        const C4: f64 = (2.0 * PI) / 3.0;
-        custom(|t| {
-            if t == 0.0 {
-                0.0
-            } else if (1.0 - t).abs() < f64::EPSILON {
-                1.0
-            } else {
-                -(2.0_f64.powf(10.0 * t - 10.0) * ((t * 10.0 - 10.75) * C4).sin())
-            }
-        })
+    pub fn elastic_ease() -> Builtin {
+        Builtin::Elastic(Default::default())
     }
 
     /// bounce ease
     #[inline]
-    pub fn bounce_ease() -> Easing<impl Fn(f64) -> f64 + Clone> {
-        const N1: f64 = 7.5625;
-        const D1: f64 = 2.75;
-        custom(|t| {
-            let v = if t < 1.0 / D1 {
-                N1 * t * t
-            } else if t < 2.0 / D1 {
-                let t = t - 1.5 / D1;
-                N1 * t * t + 0.75
-            } else if t < 2.5 / D1 {
-                let t = t - 2.25 / D1;
-                N1 * t * t + 0.9375
+    pub fn bounce_ease() -> Builtin {
+        Builtin::Bounce(Default::default())
+    }
+
+    /// cubic bezier ease, the same as CSS `cubic-bezier(x1, y1, x2, y2)`, so curves from web tools
+    /// can be used as they are, e.g. `cubic_bezier(0.25, 0.1, 0.25, 1.0)` is CSS `ease`.
+    ///
+    /// the curve starts at (0, 0) and ends at (1, 1); panics if `x1` or `x2` is out of 0-1
+    #[inline]
+    pub fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64) -> Easing<impl Fn(f64) -> f64 + Clone> {
+        assert!(
+            (0.0..=1.0).contains(&x1) && (0.0..=1.0).contains(&x2),
+            "x of control points out of 0-1: {}, {}",
+            x1,
+            x2
+        );
+        let x = BezierAxis::new(x1, x2);
+        let y = BezierAxis::new(y1, y2);
+        custom(move |t| y.sample(x.solve(t)))
+    }
+
+    /// one axis of a cubic bezier curve from 0 to 1, in polynomial form
+    #[derive(Debug, Clone, Copy)]
+    struct BezierAxis {
+        a: f64,
+        b: f64,
+        c: f64,
+    }
+
+    impl BezierAxis {
+        fn new(p1: f64, p2: f64) -> Self {
+            let c = 3.0 * p1;
+            let b = 3.0 * (p2 - p1) - c;
+            Self {
+                a: 1.0 - c - b,
+                b,
+                c,
+            }
+        }
+
+        #[inline]
+        fn sample(&self, s: f64) -> f64 {
+            ((self.a * s + self.b) * s + self.c) * s
+        }
+
+        #[inline]
+        fn derivative(&self, s: f64) -> f64 {
+            (3.0 * self.a * s + 2.0 * self.b) * s + self.c
+        }
+
+        /// the parameter where the axis is `value`; newton's method, or bisection if it
+        /// does not converge, e.g. on flat spots
+        fn solve(&self, value: f64) -> f64 {
+            const EPSILON: f64 = 1e-7;
+            let mut s = value;
+            for _ in 0..8 {
+                let diff = self.sample(s) - value;
+                if math::abs(diff) < EPSILON {
+                    return s;
+                }
+                let derivative = self.derivative(s);
+                if math::abs(derivative) < 1e-6 {
+                    break;
+                }
+                s -= diff / derivative;
+            }
+            let (mut lo, mut hi) = (0.0, 1.0);
+            s = value;
+            while hi - lo > EPSILON {
+                let diff = self.sample(s) - value;
+                if math::abs(diff) < EPSILON {
+                    break;
+                }
+                if diff > 0.0 {
+                    hi = s;
+                } else {
+                    lo = s;
+                }
+                s = (lo + hi) / 2.0;
+            }
+            s
+        }
+    }
+
+    /// weighted mix of two ease functions, `weight` is the share of `b`, e.g. a softened bounce
+    /// by `mix(bounce_ease(), linear(), 0.3)`
+    #[inline]
+    pub fn mix<A, B>(a: A, b: B, weight: f64) -> Easing<impl Fn(f64) -> f64 + Clone>
+    where
+        A: Function + Clone + 'static,
+        B: Function + Clone + 'static,
+    {
+        custom(move |t| a.ease(t) * (1.0 - weight) + b.ease(t) * weight)
+    }
+
+    /// hands over from `a` to `b`: the share of `b` rises from 0 to 1 along the time, and is
+    /// half at `switch_at`, which is between 0-1 exclusive
+    #[inline]
+    pub fn crossfade<A, B>(a: A, b: B, switch_at: f64) -> Easing<impl Fn(f64) -> f64 + Clone>
+    where
+        A: Function + Clone + 'static,
+        B: Function + Clone + 'static,
+    {
+        assert!(
+            switch_at > 0.0 && switch_at < 1.0,
+            "invalid switch time: {}",
+            switch_at
+        );
+        custom(move |t| {
+            let weight = if t < switch_at {
+                0.5 * t / switch_at
             } else {
-                let t = t - 2.625 / D1;
-                N1 * t * t + 0.984375
+                0.5 + 0.5 * (t - switch_at) / (1.0 - switch_at)
             };
-            1.0 - v
+            a.ease(t) * (1.0 - weight) + b.ease(t) * weight
         })
     }
 
@@ -219,6 +497,44 @@ mod functions {
 #[cfg(test)]
 mod test {
     use super::*;
+
+    #[test]
+    fn test_mix() {
+        let soft = mix(bounce_ease(), linear(), 0.3);
+        let (bounce, linear) = (bounce_ease(), linear());
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            let expected = bounce.ease(t) * 0.7 + linear.ease(t) * 0.3;
+            assert!((soft.ease(t) - expected).abs() < 1e-9);
+        }
+
+        let handover = crossfade(quad_ease(), custom(|t| 2.0 * t), 0.25);
+        assert_eq!(handover.ease(0.0), 0.0);
+        assert_eq!(handover.ease(0.25), (0.0625 + 0.5) / 2.0);
+        assert_eq!(handover.ease(1.0), 2.0);
+    }
+
+    #[test]
+    fn test_cubic_bezier() {
+        let linear = cubic_bezier(0.0, 0.0, 1.0, 1.0);
+        let ease = cubic_bezier(0.25, 0.1, 0.25, 1.0);
+        let overshoot = cubic_bezier(0.3, -0.5, 0.7, 1.5);
+        for func in [&linear as &dyn Function, &ease, &overshoot].iter() {
+            assert_eq!(func.ease(0.0), 0.0);
+            assert_eq!(func.ease(1.0), 1.0);
+        }
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!((linear.ease(t) - t).abs() < 1e-6);
+        }
+        // CSS `ease`
+        assert!((ease.ease(0.5) - 0.802403).abs() < 1e-5);
+        assert!(overshoot.ease(0.1) < 0.0);
+        assert!(overshoot.ease(0.9) > 1.0);
+        // flat spots
+        let steep = cubic_bezier(1.0, 0.0, 0.0, 1.0);
+        assert!((steep.ease(0.5) - 0.5).abs() < 1e-6);
+    }
     #[test]
     fn test_linear() {
         let modes = [EasingMode::In, EasingMode::Out, EasingMode::InOut];
@@ -238,6 +554,57 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_builtin() {
+        let modes = [EasingMode::In, EasingMode::Out, EasingMode::InOut];
+        for mode in modes.iter() {
+            // built-in constructors need no allocation
+            assert_eq!(quad_ease().mode(*mode), Builtin::Quad(*mode));
+            assert_eq!(linear().mode(*mode), Builtin::Linear);
+            let f = custom(|t| t * t).mode(*mode);
+            for t in [0.0, 0.25, 0.5, 0.75, 1.0].iter() {
+                assert_eq!(quad_ease().mode(*mode).ease(*t), f.ease(*t));
+            }
+        }
+        assert_eq!(Builtin::Linear.ease(0.3), 0.3);
+    }
+
+    #[test]
+    fn test_arc() {
+        use crate::core::{animation::BaseAnimation, Options, DEFAULT_ANIMATION_DURATION};
+
+        let shared: ArcFunction = Arc::new(quad_ease());
+        let copy = shared.clone();
+        assert_eq!(copy.ease(0.5), 0.25);
+        assert_eq!(Arc::strong_count(&shared), 2);
+
+        let options = Options::new(0.0, 1.0).with_easing(shared.clone());
+        let animation = options.build();
+        assert_eq!(animation.animate(DEFAULT_ANIMATION_DURATION / 2), 0.25);
+        assert_eq!(Arc::strong_count(&shared), 3);
+    }
+
+    #[test]
+    fn test_sample() {
+        assert!(sample(&linear(), 0).is_empty());
+        assert_eq!(sample(&linear(), 1), vec![(0.0, 0.0)]);
+        let samples = sample(&quad_ease(), 5);
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[1], (0.25, 0.0625));
+        assert_eq!(samples[4], (1.0, 1.0));
+    }
+
+    #[cfg(feature = "test-vectors")]
+    #[test]
+    fn test_vectors() {
+        for (func, expected) in vectors::BUILTIN.iter() {
+            let samples = sample(func, vectors::SAMPLES);
+            for ((_, actual), expected) in samples.iter().zip(expected.iter()) {
+                assert_eq!(actual, expected, "{:?}", func);
+            }
+        }
+    }
+
     #[test]
     fn test_quad_in() {
         let f = quad_ease().mode(EasingMode::In);
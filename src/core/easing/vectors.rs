@@ -0,0 +1,466 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+//! reference samples of all [`Builtin`] ease functions, so downstream snapshot tests and visual
+//! tools can check that their pipelines don't distort the curves.
+//!
+//! ## Example
+//! ```rust
+//! use anim::easing::{self, vectors};
+//!
+//! for (func, expected) in vectors::BUILTIN.iter() {
+//!     let samples = easing::sample(func, vectors::SAMPLES);
+//!     for ((_, actual), expected) in samples.iter().zip(expected.iter()) {
+//!         assert!((actual - expected).abs() < 1e-9);
+//!     }
+//! }
+//! ```
+
+use super::{Builtin, EasingMode};
+
+/// number of samples per function, evenly spaced in 0..=1, see [`super::sample()`]
+pub const SAMPLES: usize = 11;
+
+/// every built-in function in every mode, with its outputs at `i / (SAMPLES - 1)`
+#[allow(clippy::approx_constant)]
+pub static BUILTIN: [(Builtin, [f64; SAMPLES]); 28] = [
+    (
+        Builtin::Linear,
+        [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0],
+    ),
+    (
+        Builtin::Sine(EasingMode::In),
+        [
+            0.0,
+            0.01231165940486223,
+            0.04894348370484647,
+            0.1089934758116321,
+            0.19098300562505255,
+            0.2928932188134524,
+            0.41221474770752686,
+            0.5460095002604533,
+            0.6909830056250525,
+            0.843565534959769,
+            0.9999999999999999,
+        ],
+    ),
+    (
+        Builtin::Sine(EasingMode::Out),
+        [
+            1.1102230246251565e-16,
+            0.15643446504023095,
+            0.30901699437494745,
+            0.45399049973954675,
+            0.5877852522924731,
+            0.7071067811865476,
+            0.8090169943749475,
+            0.8910065241883679,
+            0.9510565162951536,
+            0.9876883405951378,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Sine(EasingMode::InOut),
+        [
+            0.0,
+            0.024471741852423234,
+            0.09549150281252627,
+            0.20610737385376343,
+            0.3454915028125263,
+            0.5,
+            0.6545084971874737,
+            0.7938926261462365,
+            0.9045084971874737,
+            0.9755282581475768,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Quad(EasingMode::In),
+        [
+            0.0,
+            0.010000000000000002,
+            0.04000000000000001,
+            0.09,
+            0.16000000000000003,
+            0.25,
+            0.36,
+            0.48999999999999994,
+            0.6400000000000001,
+            0.81,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Quad(EasingMode::Out),
+        [
+            0.0,
+            0.18999999999999995,
+            0.3599999999999999,
+            0.51,
+            0.64,
+            0.75,
+            0.84,
+            0.9099999999999999,
+            0.96,
+            0.99,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Quad(EasingMode::InOut),
+        [
+            0.0,
+            0.020000000000000004,
+            0.08000000000000002,
+            0.18,
+            0.32000000000000006,
+            0.5,
+            0.6799999999999999,
+            0.82,
+            0.92,
+            0.98,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Cubic(EasingMode::In),
+        [
+            0.0,
+            0.0010000000000000002,
+            0.008000000000000002,
+            0.026999999999999996,
+            0.06400000000000002,
+            0.125,
+            0.21599999999999997,
+            0.3429999999999999,
+            0.5120000000000001,
+            0.7290000000000001,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Cubic(EasingMode::Out),
+        [
+            0.0,
+            0.2709999999999999,
+            0.4879999999999999,
+            0.657,
+            0.784,
+            0.875,
+            0.9359999999999999,
+            0.973,
+            0.992,
+            0.999,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Cubic(EasingMode::InOut),
+        [
+            0.0,
+            0.004000000000000001,
+            0.03200000000000001,
+            0.10799999999999998,
+            0.25600000000000006,
+            0.5,
+            0.744,
+            0.8919999999999999,
+            0.968,
+            0.996,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Quart(EasingMode::In),
+        [
+            0.0,
+            0.00010000000000000002,
+            0.0016000000000000003,
+            0.0081,
+            0.025600000000000005,
+            0.0625,
+            0.1296,
+            0.24009999999999995,
+            0.4096000000000001,
+            0.6561,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Quart(EasingMode::Out),
+        [
+            0.0,
+            0.3439,
+            0.5903999999999999,
+            0.7599,
+            0.8704000000000001,
+            0.9375,
+            0.9744,
+            0.9919,
+            0.9984,
+            0.9999,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Quart(EasingMode::InOut),
+        [
+            0.0,
+            0.0008000000000000001,
+            0.012800000000000002,
+            0.0648,
+            0.20480000000000004,
+            0.5,
+            0.7951999999999999,
+            0.9351999999999999,
+            0.9872,
+            0.9992,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Qunit(EasingMode::In),
+        [
+            0.0,
+            1.0000000000000003e-5,
+            0.0003200000000000001,
+            0.0024299999999999994,
+            0.010240000000000003,
+            0.03125,
+            0.07775999999999998,
+            0.16806999999999994,
+            0.3276800000000001,
+            0.5904900000000001,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Qunit(EasingMode::Out),
+        [
+            0.0,
+            0.40950999999999993,
+            0.6723199999999999,
+            0.8319300000000001,
+            0.9222400000000001,
+            0.96875,
+            0.98976,
+            0.99757,
+            0.99968,
+            0.99999,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Qunit(EasingMode::InOut),
+        [
+            0.0,
+            0.00016000000000000004,
+            0.005120000000000001,
+            0.03887999999999999,
+            0.16384000000000004,
+            0.5,
+            0.83616,
+            0.96112,
+            0.99488,
+            0.99984,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Expo(EasingMode::In),
+        [
+            0.0,
+            0.001953125,
+            0.00390625,
+            0.0078125,
+            0.015625,
+            0.03125,
+            0.0625,
+            0.125,
+            0.25,
+            0.5,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Expo(EasingMode::Out),
+        [
+            0.0,
+            0.5,
+            0.75,
+            0.875,
+            0.9375,
+            0.96875,
+            0.984375,
+            0.9921875,
+            0.99609375,
+            0.998046875,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Expo(EasingMode::InOut),
+        [
+            0.0,
+            0.001953125,
+            0.0078125,
+            0.03125,
+            0.125,
+            0.5,
+            0.875,
+            0.96875,
+            0.9921875,
+            0.998046875,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Circle(EasingMode::In),
+        [
+            0.0,
+            0.005012562893380035,
+            0.020204102886728803,
+            0.04606079858305434,
+            0.08348486100883201,
+            0.1339745962155614,
+            0.19999999999999996,
+            0.285857157145715,
+            0.40000000000000013,
+            0.5641101056459328,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Circle(EasingMode::Out),
+        [
+            0.0,
+            0.4358898943540672,
+            0.5999999999999999,
+            0.714142842854285,
+            0.8,
+            0.8660254037844386,
+            0.916515138991168,
+            0.9539392014169457,
+            0.9797958971132712,
+            0.99498743710662,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Circle(EasingMode::InOut),
+        [
+            0.0,
+            0.010102051443364402,
+            0.041742430504416006,
+            0.09999999999999998,
+            0.20000000000000007,
+            0.5,
+            0.7999999999999999,
+            0.8999999999999999,
+            0.958257569495584,
+            0.9898979485566356,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Elastic(EasingMode::In),
+        [
+            0.0,
+            0.001953125,
+            -0.0019531250000000126,
+            -0.0039062499999999918,
+            0.015625,
+            -0.015625000000000045,
+            -0.031249999999999948,
+            0.125,
+            -0.1250000000000001,
+            -0.24999999999999986,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Elastic(EasingMode::Out),
+        [
+            0.0,
+            1.2499999999999998,
+            1.125,
+            0.875,
+            1.03125,
+            1.015625,
+            0.984375,
+            1.00390625,
+            1.001953125,
+            0.998046875,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Elastic(EasingMode::InOut),
+        [
+            0.0,
+            -0.0009765625000000063,
+            0.0078125,
+            -0.015624999999999974,
+            -0.06250000000000006,
+            0.5,
+            1.0625,
+            1.015625,
+            0.9921875,
+            1.0009765625,
+            1.0,
+        ],
+    ),
+    (
+        Builtin::Bounce(EasingMode::In),
+        [
+            1.0,
+            0.924375,
+            0.6975,
+            0.3193750000000001,
+            0.09000000000000019,
+            0.234375,
+            0.22750000000000004,
+            0.06937499999999996,
+            0.06000000000000005,
+            0.01187500000000008,
+            0.0,
+        ],
+    ),
+    (
+        Builtin::Bounce(EasingMode::Out),
+        [
+            1.0,
+            0.9881249999999999,
+            0.94,
+            0.930625,
+            0.7725,
+            0.765625,
+            0.9099999999999998,
+            0.6806250000000001,
+            0.3024999999999999,
+            0.07562499999999994,
+            0.0,
+        ],
+    ),
+    (
+        Builtin::Bounce(EasingMode::InOut),
+        [
+            0.5,
+            0.34875,
+            0.045000000000000095,
+            0.11375000000000002,
+            0.030000000000000027,
+            1.0,
+            0.97,
+            0.88625,
+            0.9550000000000001,
+            0.6512499999999999,
+            0.5,
+        ],
+    ),
+];
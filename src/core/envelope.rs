@@ -0,0 +1,237 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{clock::Clock, timeline::Status, DURATION_ZERO};
+use core::{fmt, time::Duration};
+
+/// phase of an [`Envelope`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// at level zero, not triggered or released completely
+    Idle,
+    /// rising to the peak after triggered
+    Attack,
+    /// falling from the peak to the sustain level
+    Decay,
+    /// holding the sustain level until released
+    Sustain,
+    /// falling to zero after released
+    Release,
+}
+
+/// ADSR envelope: attack, decay, sustain and release.
+///
+/// once triggered, the level rises to the peak, then falls to the sustain level and holds it;
+/// once released, it falls to zero from wherever it is. e.g. audio parameter automation or
+/// press-and-hold effects.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::Envelope;
+///
+/// let mut envelope: Envelope = Envelope::new(
+///     Duration::from_millis(50),
+///     Duration::from_millis(100),
+///     0.6,
+///     Duration::from_millis(300),
+/// );
+/// // on press
+/// envelope.trigger();
+/// // on every frame
+/// let status = envelope.update();
+/// let level = envelope.value();
+/// // on release
+/// envelope.release();
+/// ```
+pub struct Envelope<
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    attack: Duration,
+    decay: Duration,
+    sustain: f64,
+    release: Duration,
+    peak: f64,
+    pressed: bool,
+    /// level when the current phase began, i.e. when triggered or released
+    from: f64,
+    start: C::Time,
+    clock: C,
+}
+
+impl<C: Clock> Envelope<C> {
+    /// idle envelope; `sustain` is the level to hold, relative to the peak
+    #[inline]
+    pub fn new(attack: Duration, decay: Duration, sustain: f64, release: Duration) -> Self {
+        let clock = C::default();
+        Self {
+            attack,
+            decay,
+            sustain,
+            release,
+            peak: 1.0,
+            pressed: false,
+            from: 0.0,
+            start: clock.now(),
+            clock,
+        }
+    }
+
+    /// peak level, default 1.0
+    #[inline]
+    pub fn peak(mut self, peak: f64) -> Self {
+        self.peak = peak;
+        self
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// start the attack from the current level
+    #[inline]
+    pub fn trigger(&mut self) {
+        self.from = self.value();
+        self.pressed = true;
+        self.start = self.clock.now();
+        trace_event!(debug, from = self.from, "envelope triggered");
+    }
+
+    /// start the release from the current level; it has no effect unless triggered
+    #[inline]
+    pub fn release(&mut self) {
+        if !self.pressed {
+            return;
+        }
+        self.from = self.value();
+        self.pressed = false;
+        self.start = self.clock.now();
+        trace_event!(debug, from = self.from, "envelope released");
+    }
+
+    /// current phase
+    #[inline]
+    pub fn phase(&self) -> Phase {
+        let elapsed = self.clock.elapsed(&self.start);
+        if !self.pressed {
+            if self.from == 0.0 || elapsed >= self.release {
+                Phase::Idle
+            } else {
+                Phase::Release
+            }
+        } else if elapsed < self.attack {
+            Phase::Attack
+        } else if elapsed < self.attack + self.decay {
+            Phase::Decay
+        } else {
+            Phase::Sustain
+        }
+    }
+
+    /// current level
+    #[inline]
+    pub fn value(&self) -> f64 {
+        let elapsed = self.clock.elapsed(&self.start);
+        let sustain = self.sustain * self.peak;
+        match self.phase() {
+            Phase::Idle => 0.0,
+            Phase::Attack => ramp(self.from, self.peak, elapsed, self.attack),
+            Phase::Decay => ramp(self.peak, sustain, elapsed - self.attack, self.decay),
+            Phase::Sustain => sustain,
+            Phase::Release => ramp(self.from, 0.0, elapsed, self.release),
+        }
+    }
+
+    /// update the clock, returns [`Status::Paused`] while sustaining,
+    /// [`Status::Completed`] once idle
+    #[inline]
+    pub fn update(&mut self) -> Status {
+        self.clock.tick();
+        match self.phase() {
+            Phase::Idle => Status::Completed,
+            Phase::Sustain => Status::Paused,
+            _ => Status::Animating,
+        }
+    }
+}
+
+/// linear from `from` to `to` within `duration`
+#[inline]
+fn ramp(from: f64, to: f64, elapsed: Duration, duration: Duration) -> f64 {
+    if duration == DURATION_ZERO || elapsed >= duration {
+        return to;
+    }
+    let time = elapsed.as_secs_f64() / duration.as_secs_f64();
+    from + (to - from) * time
+}
+
+impl<C: Clock> fmt::Debug for Envelope<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Envelope")
+            .field("attack", &self.attack)
+            .field("decay", &self.decay)
+            .field("sustain", &self.sustain)
+            .field("release", &self.release)
+            .field("peak", &self.peak)
+            .field("phase", &self.phase())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::clock::TestClock;
+
+    #[test]
+    fn test_envelope() {
+        let at = |ms| Duration::from_millis(ms);
+        let mut envelope: Envelope<TestClock> = Envelope::new(at(100), at(200), 0.5, at(400));
+        assert_eq!(envelope.phase(), Phase::Idle);
+        assert_eq!(envelope.value(), 0.0);
+        assert_eq!(envelope.update(), Status::Completed);
+
+        envelope.trigger();
+        envelope.clock_mut().0 = at(50);
+        assert_eq!(envelope.update(), Status::Animating);
+        assert_eq!(envelope.phase(), Phase::Attack);
+        assert_eq!(envelope.value(), 0.5);
+        envelope.clock_mut().0 = at(200);
+        assert_eq!(envelope.phase(), Phase::Decay);
+        assert_eq!(envelope.value(), 0.75);
+        envelope.clock_mut().0 = at(1000);
+        assert_eq!(envelope.update(), Status::Paused);
+        assert_eq!(envelope.value(), 0.5);
+
+        // release from the sustain level
+        envelope.release();
+        envelope.clock_mut().0 = at(1200);
+        assert_eq!(envelope.phase(), Phase::Release);
+        assert_eq!(envelope.value(), 0.25);
+
+        // re-trigger from the current level
+        envelope.trigger();
+        envelope.clock_mut().0 = at(1250);
+        assert_eq!(envelope.value(), 0.625);
+
+        // release during the attack
+        envelope.release();
+        envelope.clock_mut().0 = at(1450);
+        assert_eq!(envelope.value(), 0.3125);
+        envelope.clock_mut().0 = at(1650);
+        assert_eq!(envelope.update(), Status::Completed);
+        assert_eq!(envelope.value(), 0.0);
+    }
+}
@@ -0,0 +1,104 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use crate::core::{animation::BaseAnimation, MaybeSend, DURATION_ZERO};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{fmt, time::Duration};
+
+/// events of a [`crate::Timeline`], delivered during [`crate::Timeline::update()`]
+///
+/// see [`crate::Timeline::on_event()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnimEvent {
+    /// the timeline began, see [`crate::Timeline::begin()`]
+    Started,
+    /// the timeline was paused, see [`crate::Timeline::pause()`]
+    Paused,
+    /// a marker was passed, see [`crate::Timeline::add_marker()`]
+    MarkerReached(String),
+    /// an iteration was completed and the next one began, with the number of completed iterations;
+    /// unless the timeline is built from [`crate::Options`], iterations are counted from the
+    /// beginning by [`crate::Timeline::period()`]
+    Repeated(usize),
+    /// the animation was finished; it's not emitted by [`crate::Timeline::stop()`]
+    Completed,
+    /// the animation was stopped since it ran out of time, see [`crate::Timeline::with_timeout()`]
+    TimedOut,
+}
+
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+type Listener = Box<dyn FnMut(&AnimEvent) + Send>;
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+type Listener = Box<dyn FnMut(&AnimEvent)>;
+
+/// callback registry
+#[derive(Default)]
+pub(crate) struct Listeners(Vec<Listener>);
+
+impl Listeners {
+    #[inline]
+    pub(crate) fn push(&mut self, listener: impl FnMut(&AnimEvent) + MaybeSend + 'static) {
+        self.0.push(Box::new(listener));
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    #[inline]
+    pub(crate) fn emit(&mut self, event: AnimEvent) {
+        trace_event!(trace, ?event, "timeline event");
+        for listener in self.0.iter_mut() {
+            listener(&event);
+        }
+    }
+}
+
+impl fmt::Debug for Listeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Listeners({})", self.0.len())
+    }
+}
+
+/// iterations of a repeating animation
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Iterations {
+    pub(crate) delay: Duration,
+    pub(crate) skip: Duration,
+    /// never zero
+    pub(crate) period: Duration,
+}
+
+impl Iterations {
+    /// iterations of `animation` by its period, counted from the beginning
+    #[inline]
+    pub(crate) fn of<T: BaseAnimation + ?Sized>(animation: &T) -> Option<Self> {
+        animation
+            .period()
+            .filter(|period| *period > DURATION_ZERO)
+            .map(|period| Self {
+                delay: DURATION_ZERO,
+                skip: DURATION_ZERO,
+                period,
+            })
+    }
+
+    /// number of completed iterations at `elapsed`
+    #[inline]
+    pub(crate) fn completed(&self, elapsed: Duration) -> usize {
+        let time = elapsed + self.skip;
+        match time.checked_sub(self.delay) {
+            Some(time) => (time.as_nanos() / self.period.as_nanos()) as usize,
+            None => 0,
+        }
+    }
+}
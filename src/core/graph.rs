@@ -0,0 +1,249 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{
+    animation::{Animation, BaseAnimation, Boxed, IsFinished},
+    clock::Clock,
+    timeline::Status,
+    Animatable, MaybeSend, DURATION_ZERO,
+};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+use core::{fmt, time::Duration};
+
+/// cross-blending into a new state
+struct Blend<T, Time> {
+    /// output when the state was changed
+    from: T,
+    time: Time,
+    duration: Duration,
+}
+
+/// animation state machine, e.g. the normal/hover/pressed/disabled motion of a button.
+///
+/// every named state has its own animation; once the state changes, it cross-blends from
+/// the current output to the animation of the new state, which plays from its beginning.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{builder, AnimGraph, Options};
+///
+/// let mut graph: AnimGraph<f32> = AnimGraph::new("normal", builder::constant(1.0, Duration::from_secs(0)))
+///     .state("hover", builder::constant(1.2, Duration::from_secs(0)))
+///     .state("pressed", Options::new(1.2, 0.9).duration(Duration::from_millis(100)).build())
+///     .transition("hover", "normal", Duration::from_millis(300));
+/// // on mouse enter
+/// graph.set_state("hover");
+/// // on every frame
+/// let status = graph.update();
+/// let scale = graph.value();
+/// ```
+pub struct AnimGraph<
+    T,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    states: BTreeMap<String, Boxed<T>>,
+    transitions: BTreeMap<(String, String), Duration>,
+    default_blend: Duration,
+    current: String,
+    time: C::Time,
+    blend: Option<Blend<T, C::Time>>,
+    clock: C,
+}
+
+impl<T: Animatable, C: Clock> AnimGraph<T, C> {
+    /// starts in `state`, which plays `animation`
+    #[inline]
+    pub fn new<A>(state: &str, animation: A) -> Self
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+    {
+        let clock = C::default();
+        let mut states = BTreeMap::new();
+        states.insert(state.to_string(), Boxed::new(animation));
+        Self {
+            states,
+            transitions: BTreeMap::new(),
+            default_blend: Duration::from_millis(200),
+            current: state.to_string(),
+            time: clock.now(),
+            blend: None,
+            clock,
+        }
+    }
+
+    /// add or replace a state
+    #[inline]
+    pub fn state<A>(mut self, state: &str, animation: A) -> Self
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+    {
+        self.states.insert(state.to_string(), Boxed::new(animation));
+        self
+    }
+
+    /// blend duration from state `from` to state `to`
+    #[inline]
+    pub fn transition(mut self, from: &str, to: &str, blend: Duration) -> Self {
+        self.transitions
+            .insert((from.to_string(), to.to_string()), blend);
+        self
+    }
+
+    /// blend duration of transitions not specified by [`AnimGraph::transition()`]; default 200ms
+    #[inline]
+    pub fn default_blend(mut self, blend: Duration) -> Self {
+        self.default_blend = blend;
+        self
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// current state
+    #[inline]
+    pub fn current_state(&self) -> &str {
+        &self.current
+    }
+
+    /// change to `state`, returns false if it's unknown.
+    ///
+    /// nothing happens if it's the current state
+    pub fn set_state(&mut self, state: &str) -> bool {
+        if !self.states.contains_key(state) {
+            return false;
+        }
+        if self.current == state {
+            return true;
+        }
+        let duration = self
+            .transitions
+            .get(&(self.current.clone(), state.to_string()))
+            .copied()
+            .unwrap_or(self.default_blend);
+        let now = self.clock.now();
+        self.blend = if duration > DURATION_ZERO {
+            Some(Blend {
+                from: self.value(),
+                time: now.clone(),
+                duration,
+            })
+        } else {
+            None
+        };
+        trace_event!(debug, from = %self.current, to = state, "graph transition");
+        self.current = state.to_string();
+        self.time = now;
+        true
+    }
+
+    /// current value
+    pub fn value(&self) -> T {
+        let value = self.states[&self.current].animate(self.clock.elapsed(&self.time));
+        match &self.blend {
+            Some(blend) => {
+                let elapsed = self.clock.elapsed(&blend.time);
+                if elapsed >= blend.duration {
+                    value
+                } else {
+                    let time = elapsed.as_secs_f64() / blend.duration.as_secs_f64();
+                    blend.from.animate(&value, time)
+                }
+            }
+            None => value,
+        }
+    }
+
+    /// update the clock, returns [`Status::Completed`] once it's not blending and
+    /// the animation of current state is finished
+    pub fn update(&mut self) -> Status {
+        self.clock.tick();
+        if let Some(blend) = &self.blend {
+            if self.clock.elapsed(&blend.time) >= blend.duration {
+                self.blend = None;
+            }
+        }
+        let finished = self.states[&self.current].is_finished(self.clock.elapsed(&self.time));
+        if self.blend.is_none() && finished {
+            Status::Completed
+        } else {
+            Status::Animating
+        }
+    }
+}
+
+impl<T, C: Clock> fmt::Debug for AnimGraph<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnimGraph")
+            .field("states", &self.states.keys())
+            .field("current", &self.current)
+            .field("blending", &self.blend.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{animation::constant, clock::TestClock, easing, Options};
+
+    #[test]
+    fn test_graph() {
+        let mut graph: AnimGraph<f32, TestClock> =
+            AnimGraph::new("normal", constant(0.0, DURATION_ZERO))
+                .state("hover", constant(100.0, DURATION_ZERO))
+                .state(
+                    "pressed",
+                    Options::new(100.0, 50.0)
+                        .duration(Duration::from_millis(250))
+                        .easing(easing::linear())
+                        .build(),
+                )
+                .transition("normal", "hover", Duration::from_millis(1000))
+                .transition("hover", "normal", Duration::from_millis(1000))
+                .default_blend(DURATION_ZERO);
+        assert_eq!(graph.update(), Status::Completed);
+        assert!(!graph.set_state("unknown"));
+        assert_eq!(graph.current_state(), "normal");
+
+        assert!(graph.set_state("hover"));
+        graph.clock_mut().0 = Duration::from_millis(500);
+        assert_eq!(graph.update(), Status::Animating);
+        assert_eq!(graph.value(), 50.0);
+
+        // interrupted, blends from the current output
+        graph.set_state("normal");
+        assert_eq!(graph.value(), 50.0);
+        graph.clock_mut().0 = Duration::from_millis(1000);
+        assert_eq!(graph.update(), Status::Animating);
+        assert_eq!(graph.value(), 25.0);
+        graph.clock_mut().0 = Duration::from_millis(1500);
+        assert_eq!(graph.update(), Status::Completed);
+        assert_eq!(graph.value(), 0.0);
+
+        // no blending
+        graph.set_state("pressed");
+        graph.clock_mut().0 = Duration::from_millis(1625);
+        assert_eq!(graph.update(), Status::Animating);
+        assert_eq!(graph.value(), 75.0);
+        graph.clock_mut().0 = Duration::from_millis(1750);
+        assert_eq!(graph.update(), Status::Completed);
+        assert_eq!(graph.value(), 50.0);
+    }
+}
@@ -0,0 +1,302 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{
+    clock::Clock,
+    timeline::{Status, TimelineId},
+    Timeline,
+};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// a group of independent [`Timeline`]s, which are controlled together
+///
+/// with `rayon` feature, you can update them across threads by [`TimelineGroup::par_update()`];
+/// animations and callbacks of timelines are required to be [`Send`] then, see [`crate::MaybeSend`]
+pub struct TimelineGroup<
+    T,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    timelines: Vec<Timeline<T, C>>,
+    time_scale: f32,
+    suspended: bool,
+}
+
+impl<T, C: Clock> TimelineGroup<T, C> {
+    /// create an empty group
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            timelines: Vec::new(),
+            time_scale: 1.0,
+            suspended: false,
+        }
+    }
+
+    /// add a timeline into current group, it follows the time scale and suspension of the group
+    #[inline]
+    pub fn push(&mut self, mut timeline: Timeline<T, C>) -> TimelineId {
+        let id = timeline.id();
+        timeline.set_time_scale(self.time_scale);
+        timeline.set_suspended(self.suspended);
+        self.timelines.push(timeline);
+        id
+    }
+
+    /// time scale of all timelines, default 1.0
+    #[inline]
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// change the time scale of all timelines, e.g. for slow motion;
+    /// see [`Timeline::set_time_scale()`]
+    #[inline]
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.timelines
+            .iter_mut()
+            .for_each(|item| item.set_time_scale(scale));
+        self.time_scale = scale;
+    }
+
+    /// is the time frozen for all timelines?
+    #[inline]
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// freeze the time of all timelines while the app is hidden or unfocused, then continue
+    /// without a time jump; see [`Timeline::set_suspended()`]
+    #[inline]
+    pub fn set_suspended(&mut self, suspended: bool) {
+        self.timelines
+            .iter_mut()
+            .for_each(|item| item.set_suspended(suspended));
+        self.suspended = suspended;
+    }
+
+    /// remove a timeline from current group
+    #[inline]
+    pub fn remove(&mut self, id: TimelineId) -> Option<Timeline<T, C>> {
+        let idx = self.timelines.iter().position(|item| item.id() == id)?;
+        Some(self.timelines.remove(idx))
+    }
+
+    /// get timeline by id
+    #[inline]
+    pub fn get(&self, id: TimelineId) -> Option<&Timeline<T, C>> {
+        self.timelines.iter().find(|item| item.id() == id)
+    }
+
+    /// get timeline by id
+    #[inline]
+    pub fn get_mut(&mut self, id: TimelineId) -> Option<&mut Timeline<T, C>> {
+        self.timelines.iter_mut().find(|item| item.id() == id)
+    }
+
+    /// number of timelines
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.timelines.len()
+    }
+
+    /// has no timeline?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.timelines.is_empty()
+    }
+
+    /// iterate timelines
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Timeline<T, C>> {
+        self.timelines.iter()
+    }
+
+    /// iterate timelines
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Timeline<T, C>> {
+        self.timelines.iter_mut()
+    }
+
+    /// start all timelines
+    #[inline]
+    pub fn begin(&mut self) {
+        self.timelines.iter_mut().for_each(|item| item.begin());
+    }
+
+    /// stop all timelines
+    #[inline]
+    pub fn stop(&mut self) {
+        self.timelines.iter_mut().for_each(|item| item.stop());
+    }
+
+    /// pause all timelines
+    #[inline]
+    pub fn pause(&mut self) {
+        self.timelines.iter_mut().for_each(|item| item.pause());
+    }
+
+    /// resume all timelines
+    #[inline]
+    pub fn resume(&mut self) {
+        self.timelines.iter_mut().for_each(|item| item.resume());
+    }
+
+    /// update all timelines, returns the overall status
+    ///
+    /// see [`TimelineGroup::status()`]
+    #[inline]
+    pub fn update(&mut self) -> Status {
+        self.timelines
+            .iter_mut()
+            .map(|item| item.update())
+            .fold(None, merge)
+            .unwrap_or(Status::Idle)
+    }
+
+    /// overall status of the group
+    ///
+    /// - `Animating` if any of the timelines is animating
+    /// - `Paused` if any of the timelines is paused, and none is animating
+    /// - `Completed` if all of the timelines are completed
+    /// - otherwise `Idle`
+    #[inline]
+    pub fn status(&self) -> Status {
+        self.timelines
+            .iter()
+            .map(|item| item.status())
+            .fold(None, merge)
+            .unwrap_or(Status::Idle)
+    }
+
+    /// current values of all timelines
+    #[inline]
+    pub fn values(&self) -> Vec<T> {
+        self.timelines.iter().map(|item| item.value()).collect()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, C> TimelineGroup<T, C>
+where
+    C: Clock,
+    Timeline<T, C>: Send,
+{
+    /// update all timelines across threads, returns the overall status
+    ///
+    /// see [`TimelineGroup::update()`]
+    #[inline]
+    pub fn par_update(&mut self) -> Status {
+        self.timelines
+            .par_iter_mut()
+            .map(|item| Some(item.update()))
+            .reduce(|| None, |a, b| b.map_or(a, |b| merge(a, b)))
+            .unwrap_or(Status::Idle)
+    }
+}
+
+impl<T, C: Clock> Default for TimelineGroup<T, C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C: Clock> fmt::Debug for TimelineGroup<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.timelines.iter().map(|item| (item.id(), item.status())))
+            .finish()
+    }
+}
+
+impl<T, C: Clock> Extend<Timeline<T, C>> for TimelineGroup<T, C> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = Timeline<T, C>>>(&mut self, iter: I) {
+        for timeline in iter {
+            self.push(timeline);
+        }
+    }
+}
+
+#[inline]
+fn merge(acc: Option<Status>, status: Status) -> Option<Status> {
+    let res = match (acc, status) {
+        (None, status) => status,
+        (Some(Status::Animating), _) | (_, Status::Animating) => Status::Animating,
+        (Some(Status::Paused), _) | (_, Status::Paused) => Status::Paused,
+        (Some(Status::Completed), Status::Completed) => Status::Completed,
+        _ => Status::Idle,
+    };
+    Some(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Animation, Options};
+    use core::time::Duration;
+
+    #[test]
+    fn test_group() {
+        let mut group: TimelineGroup<f32> = TimelineGroup::new();
+        assert_eq!(group.update(), Status::Idle);
+        let first = group.push(Options::new(0.0, 1.0).build().to_timeline());
+        group.push(
+            Options::new(1.0, 2.0)
+                .duration(Duration::from_secs(10))
+                .build()
+                .to_timeline(),
+        );
+        assert_eq!(group.len(), 2);
+        assert_eq!(group.update(), Status::Idle);
+
+        group.begin();
+        assert_eq!(group.update(), Status::Animating);
+        group.get_mut(first).unwrap().stop();
+        assert_eq!(group.update(), Status::Animating);
+        group.stop();
+        assert_eq!(group.update(), Status::Completed);
+        assert_eq!(group.values().len(), 2);
+
+        assert!(group.remove(first).is_some());
+        assert_eq!(group.len(), 1);
+    }
+
+    #[test]
+    fn test_group_time_scale() {
+        let mut group: TimelineGroup<f32> = TimelineGroup::new();
+        group.push(Options::new(0.0, 1.0).build().to_timeline());
+        group.set_time_scale(0.5);
+        group.push(Options::new(0.0, 1.0).build().to_timeline());
+        assert_eq!(group.time_scale(), 0.5);
+        assert!(group.iter().all(|item| item.time_scale() == 0.5));
+
+        group.set_suspended(true);
+        group.push(Options::new(0.0, 1.0).build().to_timeline());
+        assert!(group.is_suspended());
+        assert!(group.iter().all(|item| item.is_suspended()));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_group_par_update() {
+        let mut group: TimelineGroup<f32> = TimelineGroup::new();
+        for i in 0..100 {
+            group.push(Options::new(0.0, i as f32).build().to_timeline());
+        }
+        assert_eq!(group.par_update(), Status::Idle);
+        group.begin();
+        assert_eq!(group.par_update(), Status::Animating);
+        group.stop();
+        assert_eq!(group.par_update(), Status::Completed);
+    }
+}
@@ -0,0 +1,89 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+//! float functions, backed by `std` if available, otherwise by `libm`
+
+#[cfg(feature = "std")]
+mod imp {
+    #[inline(always)]
+    pub fn floor(x: f64) -> f64 {
+        x.floor()
+    }
+    #[inline(always)]
+    pub fn abs(x: f64) -> f64 {
+        x.abs()
+    }
+    #[inline(always)]
+    pub fn powf(x: f64, n: f64) -> f64 {
+        x.powf(n)
+    }
+    #[inline(always)]
+    pub fn powi(x: f64, n: i32) -> f64 {
+        x.powi(n)
+    }
+    #[inline(always)]
+    pub fn sqrt(x: f64) -> f64 {
+        x.sqrt()
+    }
+    #[inline(always)]
+    pub fn sin(x: f64) -> f64 {
+        x.sin()
+    }
+    #[inline(always)]
+    pub fn cos(x: f64) -> f64 {
+        x.cos()
+    }
+    #[inline(always)]
+    pub fn exp(x: f64) -> f64 {
+        x.exp()
+    }
+    #[inline(always)]
+    pub fn ln(x: f64) -> f64 {
+        x.ln()
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod imp {
+    #[inline(always)]
+    pub fn floor(x: f64) -> f64 {
+        libm::floor(x)
+    }
+    #[inline(always)]
+    pub fn abs(x: f64) -> f64 {
+        libm::fabs(x)
+    }
+    #[inline(always)]
+    pub fn powf(x: f64, n: f64) -> f64 {
+        libm::pow(x, n)
+    }
+    #[inline(always)]
+    pub fn powi(x: f64, n: i32) -> f64 {
+        libm::pow(x, n as f64)
+    }
+    #[inline(always)]
+    pub fn sqrt(x: f64) -> f64 {
+        libm::sqrt(x)
+    }
+    #[inline(always)]
+    pub fn sin(x: f64) -> f64 {
+        libm::sin(x)
+    }
+    #[inline(always)]
+    pub fn cos(x: f64) -> f64 {
+        libm::cos(x)
+    }
+    #[inline(always)]
+    pub fn exp(x: f64) -> f64 {
+        libm::exp(x)
+    }
+    #[inline(always)]
+    pub fn ln(x: f64) -> f64 {
+        libm::log(x)
+    }
+}
+
+pub(crate) use imp::*;
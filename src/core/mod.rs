@@ -7,27 +7,89 @@
 /// make a type animatable
 pub mod animatable;
 pub(crate) mod animation;
+/// batch animations
+pub mod batch;
+/// progress based curves
+pub mod curve;
 /// ease functions
 pub mod easing;
+/// ADSR envelopes
+pub mod envelope;
 mod options;
+/// physics based animations, which can be interrupted
+pub mod physics;
+/// scrub animations by gestures
+pub mod scrubber;
 /// timeline definitions
 pub mod timeline;
 /// utilities
 pub mod utils;
 
 mod clock;
+mod context;
+mod event;
+mod graph;
+mod group;
+pub(crate) mod math;
+mod pool;
+mod property;
+mod scheduler;
+mod scroll;
+mod sync;
 
-use std::time::Duration;
+use core::time::Duration;
 
 #[doc(inline)]
-pub use animatable::Animatable;
+pub use animatable::{Animatable, Interpolator};
+#[cfg(feature = "std")]
 #[doc(inline)]
-pub use animation::{Animation, Cursor, KeyFrame, KeyTime, SeekFrom};
+pub use animation::Input;
+#[doc(inline)]
+pub use animation::{
+    AnimNode, Animation, BaseAnimation, Counter, Cursor, Flipbook, FrameRect, Interpolation,
+    KeyFrame, KeyTime, Layers, Path, PathPoint, SeekFrom, Shake, Skip, SpriteGrid, SpriteSheet,
+    StepEnd, Track, Trail, Typewriter,
+};
+/// adapters returned by [`Animation`] methods, so composed animations can be named,
+/// e.g. as struct fields
+#[doc(inline)]
+pub use animation::{
+    Boxed, Cache, Chain, Delay, Map, Parallel, Probe, Repeat, Scale, Shared, Take,
+};
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use clock::DefaultClock;
+#[doc(inline)]
+pub use clock::{Clock, FixedClock};
+#[doc(inline)]
+pub use context::AnimContext;
+#[doc(inline)]
+pub use curve::Curve;
 #[doc(inline)]
 pub use easing::Function;
 #[doc(inline)]
+pub use envelope::Envelope;
+#[doc(inline)]
+pub use event::AnimEvent;
+#[doc(inline)]
+pub use graph::AnimGraph;
+#[doc(inline)]
+pub use group::TimelineGroup;
+#[doc(inline)]
 pub use options::*;
 #[doc(inline)]
+pub use pool::TimelinePool;
+#[doc(inline)]
+pub use property::PropertyAnimator;
+#[doc(inline)]
+pub use scheduler::{FrameReport, FrameScheduler, Priority};
+#[doc(inline)]
+pub use scroll::ScrollDriver;
+#[doc(inline)]
+pub use scrubber::Scrubber;
+#[doc(inline)]
+pub use sync::{SyncClock, SyncGroup};
+#[doc(inline)]
 pub use timeline::Timeline;
 
 /// deprecated, please use [`builder::linear`] instead
@@ -35,6 +97,34 @@ pub use timeline::Timeline;
 #[doc(hidden)]
 pub use animation::linear;
 
+/// [`Send`] with `rayon` or `bevy-backend` feature, so that timelines can be updated across
+/// threads; boxed animations and callbacks are required to implement it
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+pub trait MaybeSend: Send {}
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// [`Send`] with `rayon` or `bevy-backend` feature, so that timelines can be updated across
+/// threads; without them, it's implemented for all types
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+pub trait MaybeSend {}
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// [`Sync`] with `rayon` or `bevy-backend` feature, so that ease functions can be shared across
+/// threads; boxed ease functions are required to implement it
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+pub trait MaybeSync: Sync {}
+#[cfg(any(feature = "rayon", feature = "bevy-backend"))]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// [`Sync`] with `rayon` or `bevy-backend` feature, so that ease functions can be shared across
+/// threads; without them, it's implemented for all types
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+pub trait MaybeSync {}
+#[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+impl<T: ?Sized> MaybeSync for T {}
+
 /// [`Duration`]::ZERO
 pub const DURATION_ZERO: Duration = Duration::from_secs(0);
 
@@ -44,5 +134,8 @@ pub const DEFAULT_ANIMATION_DURATION: Duration = Duration::from_secs(1);
 /// animation builders
 pub mod builder {
     #[doc(inline)]
-    pub use super::animation::{constant, key_frames, linear, steps, steps_infinite};
+    pub use super::animation::{
+        color_cycle, constant, count, key_frames, key_frames_by, key_frames_from, key_frames_with,
+        linear, path, shake, steps, steps_infinite, typewriter,
+    };
 }
@@ -4,21 +4,71 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
+#[cfg(feature = "std")]
+use crate::Timeline;
 use crate::{
-    core::{animation::Primitive, easing, Animatable},
-    Animation, Timeline, DEFAULT_ANIMATION_DURATION,
+    core::{
+        animatable::{AxisEasing, DynInterpolator, Interpolator},
+        animation::{skipped_duration, Primitive},
+        context::AnimContext,
+        easing, math,
+        physics::affine,
+        Animatable, MaybeSend, MaybeSync,
+    },
+    Animation, DEFAULT_ANIMATION_DURATION, DURATION_ZERO,
 };
-use std::{fmt, time::Duration};
+use alloc::boxed::Box;
+use core::{fmt, time::Duration};
 
 /// how an [`Animation`] repeats its simple duration
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RepeatBehavior {
     /// specifies the number of times the simple duration of a an [`Animation`] plays. default 1.0
     Count(f32),
+    /// plays exactly the number of times, the loop boundaries are computed in integers;
+    /// prefer it unless a partial loop is intended
+    CountExact(u32),
     /// The [`Animation`] repeats indefinitely
     Forever,
 }
 
+impl RepeatBehavior {
+    /// plays more than once?
+    #[inline]
+    pub(crate) fn loops(self) -> bool {
+        match self {
+            RepeatBehavior::Count(count) => count > 1.0,
+            RepeatBehavior::CountExact(count) => count > 1,
+            RepeatBehavior::Forever => true,
+        }
+    }
+
+    /// duration of all iterations; `None` means indefinitely
+    #[inline]
+    pub(crate) fn total(self, period: Duration) -> Option<Duration> {
+        match self {
+            // whole counts are exact too
+            RepeatBehavior::Count(count) if count >= 0.0 && count <= u32::MAX as f32 => {
+                if math::floor(count as f64) == count as f64 {
+                    RepeatBehavior::CountExact(count as u32).total(period)
+                } else {
+                    Some(period.mul_f32(count))
+                }
+            }
+            RepeatBehavior::Count(count) => Some(if count > 0.0 {
+                period.mul_f32(count)
+            } else {
+                DURATION_ZERO
+            }),
+            RepeatBehavior::CountExact(count) => {
+                Some(period.checked_mul(count).unwrap_or(Duration::MAX))
+            }
+            RepeatBehavior::Forever => None,
+        }
+    }
+}
+
 impl Default for RepeatBehavior {
     #[inline]
     fn default() -> Self {
@@ -26,16 +76,90 @@ impl Default for RepeatBehavior {
     }
 }
 
+/// how [`Options::auto_reverse()`] affects the duration, default [`AutoReverseMode::Compress`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AutoReverseMode {
+    /// from -> to -> from plays within the simple duration, so each leg is twice as fast
+    Compress,
+    /// each leg lasts for the simple duration, the total duration is doubled
+    Extend,
+}
+
+impl Default for AutoReverseMode {
+    #[inline]
+    fn default() -> Self {
+        AutoReverseMode::Compress
+    }
+}
+
+/// how the ease function applies to a repeated animation, default [`EasingScope::Iteration`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EasingScope {
+    /// easing restarts for every iteration
+    Iteration,
+    /// easing spans the entire repeated duration; it falls back to
+    /// [`EasingScope::Iteration`] if the animation repeats indefinitely
+    Whole,
+    /// easing restarts for every leg of an auto reversed iteration, the reverse leg plays
+    /// the forward leg backwards; the same as [`EasingScope::Iteration`] without auto reverse
+    Leg,
+}
+
+impl Default for EasingScope {
+    #[inline]
+    fn default() -> Self {
+        EasingScope::Iteration
+    }
+}
+
+/// invalid [`Options`], see [`Options::try_build()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionsError {
+    /// repeat count is negative, NaN or infinite; use [`RepeatBehavior::Forever`] for infinite
+    InvalidRepeatCount(f32),
+    /// an animation of zero duration can not repeat indefinitely
+    ZeroDurationForever,
+}
+
+impl fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionsError::InvalidRepeatCount(count) => {
+                write!(f, "invalid repeat count: {}", count)
+            }
+            OptionsError::ZeroDurationForever => {
+                write!(f, "zero duration animation can not repeat forever")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OptionsError {}
+
 /// options to build an [`Animation`]
-pub struct Options<T: Animatable> {
+///
+/// The ease function is boxed by default; use [`Options::with_easing()`] to
+/// keep its concrete type, so that it's called directly for hot paths.
+pub struct Options<T: Animatable, F: easing::Function = easing::BoxFunction> {
     pub(crate) from: T,
     pub(crate) to: T,
     pub(crate) auto_reverse: bool,
+    pub(crate) auto_reverse_mode: AutoReverseMode,
     pub(crate) skip: Option<Duration>,
     pub(crate) delay: Option<Duration>,
     pub(crate) duration: Duration,
     pub(crate) repeat: RepeatBehavior,
-    pub(crate) easing: Box<dyn easing::Function>,
+    pub(crate) easing: F,
+    pub(crate) easing_scope: EasingScope,
+    pub(crate) interpolator: DynInterpolator<T>,
+    pub(crate) from_current: bool,
+    /// see [`Options::new_in()`]
+    time_scale: f32,
+    /// see [`Options::new_in()`]
+    reduce_motion: bool,
 }
 
 impl<T: Animatable + Default> Default for Options<T> {
@@ -44,11 +168,17 @@ impl<T: Animatable + Default> Default for Options<T> {
             from: Default::default(),
             to: Default::default(),
             auto_reverse: false,
+            auto_reverse_mode: Default::default(),
             skip: None,
             delay: None,
             duration: DEFAULT_ANIMATION_DURATION,
             repeat: Default::default(),
             easing: Box::new(easing::linear()),
+            easing_scope: Default::default(),
+            interpolator: Default::default(),
+            from_current: false,
+            time_scale: 1.0,
+            reduce_motion: false,
         }
     }
 }
@@ -61,14 +191,80 @@ impl<T: Animatable> Options<T> {
             from,
             to,
             auto_reverse: false,
+            auto_reverse_mode: Default::default(),
             skip: None,
             delay: None,
             duration: DEFAULT_ANIMATION_DURATION,
             repeat: Default::default(),
             easing: Box::new(easing::cubic_ease()),
+            easing_scope: Default::default(),
+            interpolator: Default::default(),
+            from_current: false,
+            time_scale: 1.0,
+            reduce_motion: false,
         }
     }
 
+    /// create new [`Options`] from range, with the defaults of `ctx`; settings made later
+    /// override them, while durations set later are scaled by the time scale of `ctx` too.
+    ///
+    /// if `ctx` reduces motion, it jumps to `to` in no time; a repeating animation stays at `to`
+    #[inline]
+    pub fn new_in(ctx: &AnimContext, from: T, to: T) -> Self {
+        let mut options = Options::new(from, to);
+        options.easing = ctx.default_easing().clone();
+        options.time_scale = ctx.current_time_scale();
+        options.reduce_motion = ctx.reduces_motion();
+        if options.reduce_motion {
+            options.from = options.to.clone();
+            options.duration = DURATION_ZERO;
+        } else {
+            options.duration = ctx.default_duration();
+        }
+        options
+    }
+
+    /// set ease function, default [`easing::linear`]
+    #[inline]
+    pub fn easing(
+        mut self,
+        func: impl easing::Function + Clone + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        self.easing = Box::new(func);
+        self
+    }
+}
+
+impl<T: Animatable + Default> Options<T> {
+    /// oscillate around `center` from `center - amplitude` to `center + amplitude` and back
+    /// forever, e.g. idle breathing or pulse effects; eased by sine in & out.
+    ///
+    /// it's exact for float based values; all the settings can be changed as usual.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use anim::{Animation, Options};
+    ///
+    /// // scale between 0.95 and 1.05 every two seconds
+    /// let timeline = Options::around(1.0, 0.05)
+    ///     .duration(Duration::from_secs(2))
+    ///     .begin_animation();
+    /// ```
+    #[inline]
+    pub fn around(center: T, amplitude: T) -> Self {
+        let zero = T::default();
+        let from = affine((&center, 1.0), (&amplitude, -1.0), (&zero, 1.0));
+        let to = affine((&center, 1.0), (&amplitude, 1.0), (&zero, -1.0));
+        Options::new(from, to)
+            .easing(easing::sine_ease().mode(easing::EasingMode::InOut))
+            .easing_scope(EasingScope::Leg)
+            .auto_reverse(true)
+            .forever()
+    }
+}
+
+impl<T: Animatable, F: easing::Function> Options<T, F> {
     /// animation from value
     #[inline]
     pub fn from(mut self, value: T) -> Self {
@@ -83,20 +279,43 @@ impl<T: Animatable> Options<T> {
         self
     }
 
+    /// start from the present value of the timeline it's played by, see
+    /// [`Timeline::animate_to()`]; otherwise it starts from [`Options::from()`]
+    #[inline]
+    pub fn from_current(mut self) -> Self {
+        self.from_current = true;
+        self
+    }
+
     /// auto reverse animation when it reaches the end; default false.
-    /// Note: it will not increase the duration or repeat times.
+    /// Note: it will not increase the duration or repeat times, unless
+    /// [`AutoReverseMode::Extend`] is specified by [`Options::auto_reverse_mode()`].
     ///
     /// auto_reverse | effect
     /// ------------- | -------------------
     /// false             | from -> to
     /// true              | from -> to -> from
     ///
+    /// With auto reverse, one iteration is the forward leg followed by the reverse leg,
+    /// the ease function spans both legs; see [`EasingScope::Leg`] to ease every leg instead.
+    /// A fractional repeat count applies to the full iteration, e.g. `times(1.5)`
+    /// plays from -> to -> from -> to.
+    ///
     #[inline]
     pub fn auto_reverse(mut self, auto_reverse: bool) -> Self {
         self.auto_reverse = auto_reverse;
         self
     }
 
+    /// how auto reverse affects the duration, default [`AutoReverseMode::Compress`]
+    ///
+    /// see [`Options::auto_reverse()`]
+    #[inline]
+    pub fn auto_reverse_mode(mut self, mode: AutoReverseMode) -> Self {
+        self.auto_reverse_mode = mode;
+        self
+    }
+
     /// deprecated, use [`Options::skip()`] instead
     #[deprecated()]
     #[inline]
@@ -106,10 +325,14 @@ impl<T: Animatable> Options<T> {
 
     /// play animation from the specified progress, same effect as [`Animation::skip()`]
     ///
+    /// skip is applied after delay. If it exceeds the rest of the animation, the duration
+    /// is clamped to zero, the same as [`crate::Skip`], and the animation holds its final value;
+    /// check it by [`Options::effective_duration()`].
+    ///
     /// see [`Animation::skip()`]
     #[inline]
     pub fn skip(mut self, skip: Duration) -> Self {
-        self.skip = Some(skip);
+        self.skip = Some(self.scaled(skip));
         self
     }
 
@@ -119,7 +342,7 @@ impl<T: Animatable> Options<T> {
     /// see [`Animation::delay()`]
     #[inline]
     pub fn delay(mut self, delay: Duration) -> Self {
-        self.delay = Some(delay);
+        self.delay = Some(self.scaled(delay));
         self
     }
 
@@ -128,16 +351,27 @@ impl<T: Animatable> Options<T> {
     /// If [`Options::repeat()`] is specified, the animation might play more than once.
     #[inline]
     pub fn duration(mut self, duration: Duration) -> Self {
-        self.duration = duration;
+        self.duration = self.scaled(duration);
         self
     }
 
+    /// `duration` scaled by the context the options are created in, see [`Options::new_in()`]
+    #[inline]
+    fn scaled(&self, duration: Duration) -> Duration {
+        if self.reduce_motion {
+            DURATION_ZERO
+        } else if self.time_scale == 1.0 {
+            duration
+        } else {
+            duration.div_f64(self.time_scale as f64)
+        }
+    }
+
     /// repeat behavior
+    ///
+    /// the count must be finite and not negative, see [`Options::try_build()`]
     #[inline]
     pub fn repeat(mut self, behavior: RepeatBehavior) -> Self {
-        if let RepeatBehavior::Count(count) = behavior {
-            assert!(count >= 0.0);
-        }
         self.repeat = behavior;
         self
     }
@@ -161,29 +395,162 @@ impl<T: Animatable> Options<T> {
     ///
     /// see [`Options::repeat()`]
     ///
-    /// panics if count<=0
+    /// the count must be finite and not negative, see [`Options::try_build()`]
     #[inline]
     pub fn times(mut self, count: f32) -> Self {
-        assert!(count >= 0.0);
         self.repeat = RepeatBehavior::Count(count);
         self
     }
 
-    /// set ease function, default [`easing::linear`]
+    /// your [`Animation`] repeats for exactly `count` times, see [`RepeatBehavior::CountExact`]
     #[inline]
-    pub fn easing(mut self, func: impl easing::Function + Clone + 'static) -> Self {
-        self.easing = Box::new(func);
+    pub fn times_exact(mut self, count: u32) -> Self {
+        self.repeat = RepeatBehavior::CountExact(count);
         self
     }
 
+    /// whether the ease function restarts for every iteration or spans the entire repeated duration,
+    /// default [`EasingScope::Iteration`]
+    ///
+    /// see [`Options::repeat()`]
+    #[inline]
+    pub fn easing_scope(mut self, scope: EasingScope) -> Self {
+        self.easing_scope = scope;
+        self
+    }
+
+    /// interpolate by `interpolator` instead of [`Animatable::animate()`], e.g. shortest arc for angles
+    #[inline]
+    pub fn interpolator(
+        mut self,
+        interpolator: impl Interpolator<T> + MaybeSend + MaybeSync + 'static,
+    ) -> Self {
+        self.interpolator = DynInterpolator::new(interpolator);
+        self
+    }
+
+    /// ease x and y axes of 2D values separately, e.g. x linear and y bouncing for arc-like motion;
+    /// it applies on top of [`Options::easing()`], which is better kept linear.
+    ///
+    /// see [`AxisEasing`]
+    #[inline]
+    pub fn axis_easing<X, Y>(self, x: X, y: Y) -> Self
+    where
+        X: easing::Function + MaybeSend + MaybeSync + 'static,
+        Y: easing::Function + MaybeSend + MaybeSync + 'static,
+        AxisEasing<X, Y>: Interpolator<T>,
+    {
+        self.interpolator(AxisEasing::new(x, y))
+    }
+
+    /// set ease function without boxing it, see [`Options::easing()`]
+    #[inline]
+    pub fn with_easing<G: easing::Function>(self, func: G) -> Options<T, G> {
+        Options {
+            from: self.from,
+            to: self.to,
+            auto_reverse: self.auto_reverse,
+            auto_reverse_mode: self.auto_reverse_mode,
+            skip: self.skip,
+            delay: self.delay,
+            duration: self.duration,
+            repeat: self.repeat,
+            easing: func,
+            easing_scope: self.easing_scope,
+            interpolator: self.interpolator,
+            from_current: self.from_current,
+            time_scale: self.time_scale,
+            reduce_motion: self.reduce_motion,
+        }
+    }
+
     /// build [`Animation`]
+    ///
+    /// panics if the options are invalid, see [`Options::try_build()`]
+    #[inline]
+    pub fn build(self) -> impl Animation<Item = T> + Clone
+    where
+        F: Clone,
+    {
+        match self.try_build() {
+            Ok(animation) => animation,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// build [`Animation`], or tell why the options are invalid
+    #[inline]
+    pub fn try_build(self) -> Result<impl Animation<Item = T> + Clone, OptionsError>
+    where
+        F: Clone,
+    {
+        self.validate()?;
+        Ok(Primitive::new(self))
+    }
+
     #[inline]
-    pub fn build(self) -> impl Animation<Item = T> + Clone {
-        Primitive::new(self)
+    fn validate(&self) -> Result<(), OptionsError> {
+        match self.repeat {
+            RepeatBehavior::Count(count) if !count.is_finite() || count < 0.0 => {
+                Err(OptionsError::InvalidRepeatCount(count))
+            }
+            // motion is reduced to a constant
+            RepeatBehavior::Forever if self.duration == DURATION_ZERO && !self.reduce_motion => {
+                Err(OptionsError::ZeroDurationForever)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// how long the built animation lasts, taking repeat, delay and skip into account;
+    /// `None` means it repeats indefinitely.
+    ///
+    /// it's zero if skip exceeds the rest of the animation, see [`Options::skip()`]
+    #[inline]
+    pub fn effective_duration(&self) -> Option<Duration> {
+        self.repeat_duration()
+            .map(|duration| self.apply_offset(duration))
+    }
+
+    /// duration of all iterations, without delay and skip
+    #[inline]
+    pub(crate) fn repeat_duration(&self) -> Option<Duration> {
+        let simple = self.simple_duration();
+        if simple == DURATION_ZERO {
+            return Some(DURATION_ZERO);
+        }
+        self.repeat.total(simple)
+    }
+
+    /// applies delay, then skip to the duration; skip is clamped
+    #[inline]
+    pub(crate) fn apply_offset(&self, mut duration: Duration) -> Duration {
+        if let Some(delay) = self.delay {
+            duration += delay;
+        }
+        if let Some(skip) = self.skip {
+            duration = skipped_duration(duration, skip);
+        }
+        duration
+    }
+
+    /// duration of one iteration, taking [`AutoReverseMode`] into account
+    #[inline]
+    pub(crate) fn simple_duration(&self) -> Duration {
+        if self.auto_reverse && self.auto_reverse_mode == AutoReverseMode::Extend {
+            self.duration * 2
+        } else {
+            self.duration
+        }
     }
 }
 
-impl<T: Animatable + 'static> Options<T> {
+#[cfg(feature = "std")]
+impl<T, F> Options<T, F>
+where
+    T: Animatable + MaybeSend + 'static,
+    F: easing::Function + Clone + MaybeSend + 'static,
+{
     /// build [`Timeline`] and start animation
     #[inline]
     pub fn begin_animation(self) -> Timeline<T> {
@@ -193,32 +560,143 @@ impl<T: Animatable + 'static> Options<T> {
     }
 }
 
-impl<T: Animatable + fmt::Debug> fmt::Debug for Options<T> {
+impl<T: Animatable + fmt::Debug, F: easing::Function> fmt::Debug for Options<T, F> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Options")
             .field("from", &self.from)
             .field("to", &self.to)
             .field("auto_reverse", &self.auto_reverse)
+            .field("auto_reverse_mode", &self.auto_reverse_mode)
             .field("begin_time", &self.skip)
             .field("duration", &self.duration)
             .field("repeat", &self.repeat)
             .field("easing", &"???")
+            .field("easing_scope", &self.easing_scope)
+            .field("interpolator", &self.interpolator)
+            .field("from_current", &self.from_current)
+            .field("time_scale", &self.time_scale)
+            .field("reduce_motion", &self.reduce_motion)
             .finish()
     }
 }
 
-impl<T: Animatable> Clone for Options<T> {
+impl<T: Animatable, F: easing::Function + Clone> Clone for Options<T, F> {
     #[inline]
     fn clone(&self) -> Self {
         Self {
             from: self.from.clone(),
             to: self.to.clone(),
             auto_reverse: self.auto_reverse,
+            auto_reverse_mode: self.auto_reverse_mode,
             skip: self.skip,
             delay: self.delay,
             duration: self.duration,
             repeat: self.repeat,
-            easing: dyn_clone::clone_box(&*self.easing),
+            easing: self.easing.clone(),
+            easing_scope: self.easing_scope,
+            interpolator: self.interpolator.clone(),
+            from_current: self.from_current,
+            time_scale: self.time_scale,
+            reduce_motion: self.reduce_motion,
+        }
+    }
+}
+
+/// options are loaded from asset files with named ease functions, i.e. `Options<T, Builtin>`;
+/// the interpolator is not (de)serialized
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct OptionsRef<'a, T, F> {
+        from: &'a T,
+        to: &'a T,
+        auto_reverse: bool,
+        auto_reverse_mode: AutoReverseMode,
+        skip: Option<Duration>,
+        delay: Option<Duration>,
+        duration: Duration,
+        repeat: RepeatBehavior,
+        easing: &'a F,
+        easing_scope: EasingScope,
+        from_current: bool,
+    }
+
+    #[derive(Deserialize)]
+    struct OptionsDef<T, F> {
+        from: T,
+        to: T,
+        #[serde(default)]
+        auto_reverse: bool,
+        #[serde(default)]
+        auto_reverse_mode: AutoReverseMode,
+        #[serde(default)]
+        skip: Option<Duration>,
+        #[serde(default)]
+        delay: Option<Duration>,
+        #[serde(default = "default_duration")]
+        duration: Duration,
+        #[serde(default)]
+        repeat: RepeatBehavior,
+        easing: F,
+        #[serde(default)]
+        easing_scope: EasingScope,
+        #[serde(default)]
+        from_current: bool,
+    }
+
+    fn default_duration() -> Duration {
+        DEFAULT_ANIMATION_DURATION
+    }
+
+    impl<T, F> Serialize for Options<T, F>
+    where
+        T: Animatable + Serialize,
+        F: easing::Function + Serialize,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            OptionsRef {
+                from: &self.from,
+                to: &self.to,
+                auto_reverse: self.auto_reverse,
+                auto_reverse_mode: self.auto_reverse_mode,
+                skip: self.skip,
+                delay: self.delay,
+                duration: self.duration,
+                repeat: self.repeat,
+                easing: &self.easing,
+                easing_scope: self.easing_scope,
+                from_current: self.from_current,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, T, F> Deserialize<'de> for Options<T, F>
+    where
+        T: Animatable + Deserialize<'de>,
+        F: easing::Function + Deserialize<'de>,
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let def = OptionsDef::deserialize(deserializer)?;
+            Ok(Options {
+                from: def.from,
+                to: def.to,
+                auto_reverse: def.auto_reverse,
+                auto_reverse_mode: def.auto_reverse_mode,
+                skip: def.skip,
+                delay: def.delay,
+                duration: def.duration,
+                repeat: def.repeat,
+                easing: def.easing,
+                easing_scope: def.easing_scope,
+                interpolator: Default::default(),
+                from_current: def.from_current,
+                time_scale: 1.0,
+                reduce_motion: false,
+            })
         }
     }
 }
@@ -0,0 +1,264 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::{SpringAnimation, SpringConfig};
+use crate::core::{
+    animation::{AnimNode, BaseAnimation},
+    math, DURATION_ZERO,
+};
+use core::time::Duration;
+
+/// fling animation: the velocity decays exponentially until the value comes to rest,
+/// e.g. momentum scrolling or fling-to-dismiss.
+///
+/// with [`Decay::bounds()`], it bounces back by a spring once it goes out of bounds.
+///
+/// ## Example
+/// ```rust
+/// use anim::{physics::Decay, Animation};
+///
+/// // the velocity of a drag gesture, 1000 pixels per second
+/// let fling = Decay::new(0.0, 1000.0).bounds(0.0, 200.0);
+/// let timeline = fling.begin_animation();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decay {
+    from: f64,
+    velocity: f64,
+    time_constant: f64,
+    rest_delta: f64,
+    rest_speed: Option<f64>,
+    bounds: Option<(f64, f64)>,
+    bounce: SpringConfig,
+    /// when it goes out of bounds, and the spring bouncing back from there
+    bouncing: Option<(Duration, SpringAnimation<f64>)>,
+    duration: Duration,
+}
+
+impl Decay {
+    /// fling from `from` with initial `velocity` per second
+    #[inline]
+    pub fn new(from: f64, velocity: f64) -> Self {
+        Self {
+            from,
+            velocity,
+            time_constant: 0.35,
+            rest_delta: 0.5,
+            rest_speed: None,
+            bounds: None,
+            bounce: SpringConfig::default().stiffness(500.0).damping(30.0),
+            bouncing: None,
+            duration: DURATION_ZERO,
+        }
+        .prepare()
+    }
+
+    /// how fast the velocity decays, it's the time to lose about 63% of the velocity; default 350ms
+    ///
+    /// panics if it's zero
+    #[inline]
+    pub fn time_constant(mut self, time_constant: Duration) -> Self {
+        assert!(time_constant > DURATION_ZERO);
+        self.time_constant = time_constant.as_secs_f64();
+        self.prepare()
+    }
+
+    /// it comes to rest once the remaining distance is less than this value, default 0.5
+    ///
+    /// panics if it's not positive
+    #[inline]
+    pub fn rest_delta(mut self, rest_delta: f64) -> Self {
+        assert!(rest_delta > 0.0);
+        self.rest_delta = rest_delta;
+        self.prepare()
+    }
+
+    /// besides [`Decay::rest_delta()`], it comes to rest only once the speed per second is less
+    /// than this value; not limited by default
+    ///
+    /// panics if it's not positive
+    #[inline]
+    pub fn rest_speed(mut self, rest_speed: f64) -> Self {
+        assert!(rest_speed > 0.0);
+        self.rest_speed = Some(rest_speed);
+        self.prepare()
+    }
+
+    /// bounce back into `min..=max` once it goes out of bounds
+    ///
+    /// panics if min>max
+    #[inline]
+    pub fn bounds(mut self, min: f64, max: f64) -> Self {
+        assert!(min <= max);
+        self.bounds = Some((min, max));
+        self.prepare()
+    }
+
+    /// the spring bouncing back into bounds, see [`Decay::bounds()`]
+    #[inline]
+    pub fn bounce(mut self, config: SpringConfig) -> Self {
+        self.bounce = config;
+        self.prepare()
+    }
+
+    /// where it would come to rest without bounds
+    #[inline]
+    pub fn target(&self) -> f64 {
+        self.from + self.velocity * self.time_constant
+    }
+
+    /// where it comes to rest, the target clamped into bounds; it might rest before it reaches
+    /// the edge, but only within [`Decay::rest_delta()`]
+    #[inline]
+    fn rest_value(&self) -> f64 {
+        let target = self.target();
+        match self.bounds {
+            Some((min, max)) => target.max(min).min(max),
+            None => target,
+        }
+    }
+
+    /// position at `t` seconds, without bounds
+    #[inline]
+    fn position(&self, t: f64) -> f64 {
+        let amplitude = self.velocity * self.time_constant;
+        self.from + amplitude * (1.0 - math::exp(-t / self.time_constant))
+    }
+
+    fn prepare(mut self) -> Self {
+        let amplitude = self.velocity * self.time_constant;
+        // time to come to rest without bounds
+        let mut rest = if math::abs(amplitude) > self.rest_delta {
+            self.time_constant * math::ln(math::abs(amplitude) / self.rest_delta)
+        } else {
+            0.0
+        };
+        if let Some(rest_speed) = self.rest_speed {
+            if math::abs(self.velocity) > rest_speed {
+                let time = self.time_constant * math::ln(math::abs(self.velocity) / rest_speed);
+                rest = rest.max(time);
+            }
+        }
+        self.bouncing = self.bounds.and_then(|(min, max)| {
+            let (time, edge) = if self.from < min {
+                (0.0, min)
+            } else if self.from > max {
+                (0.0, max)
+            } else {
+                let target = self.target();
+                let edge = if target < min {
+                    min
+                } else if target > max {
+                    max
+                } else {
+                    return None;
+                };
+                // solve position(t) == edge
+                let time = -self.time_constant * math::ln(1.0 - (edge - self.from) / amplitude);
+                if time > rest {
+                    // comes to rest before it reaches the edge
+                    return None;
+                }
+                (time, edge)
+            };
+            let velocity = self.velocity * math::exp(-time / self.time_constant);
+            let from = if time == 0.0 { self.from } else { edge };
+            let spring = SpringAnimation::with_state(self.bounce, from, from + velocity, edge);
+            Some((Duration::from_secs_f64(time), spring))
+        });
+        self.duration = match &self.bouncing {
+            Some((time, spring)) => *time + spring.duration().unwrap_or(DURATION_ZERO),
+            None => Duration::from_secs_f64(rest),
+        };
+        self
+    }
+}
+
+impl BaseAnimation for Decay {
+    type Item = f64;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        if let Some((time, spring)) = &self.bouncing {
+            if elapsed >= *time {
+                return spring.animate(elapsed - *time);
+            }
+        } else if elapsed >= self.duration {
+            return self.rest_value();
+        }
+        self.position(elapsed.as_secs_f64())
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("decay", self.duration())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values(decay: &Decay) -> alloc::vec::Vec<f64> {
+        let duration = decay.duration().unwrap();
+        let mut values = vec![];
+        let mut elapsed = DURATION_ZERO;
+        while elapsed < duration {
+            values.push(decay.animate(elapsed));
+            elapsed += Duration::from_millis(10);
+        }
+        values.push(decay.animate(duration));
+        values
+    }
+
+    #[test]
+    fn test_decay() {
+        let decay = Decay::new(0.0, 1000.0);
+        assert_eq!(decay.target(), 350.0);
+        let values = values(&decay);
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(*values.last().unwrap(), 350.0);
+        assert!(350.0 - values[values.len() - 2] < 1.0);
+
+        let decay = Decay::new(100.0, -1000.0);
+        assert_eq!(decay.animate(decay.duration().unwrap()), -250.0);
+
+        // the speed decays to 1 after 0.35 * ln(1000) seconds
+        let decay = Decay::new(0.0, 1000.0).rest_delta(10.0).rest_speed(1.0);
+        let duration = decay.duration().unwrap().as_secs_f64();
+        assert!((duration - 0.35 * 1000f64.ln()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_decay_bounds() {
+        // bounces back
+        let decay = Decay::new(0.0, 1000.0).bounds(0.0, 100.0);
+        let values = values(&decay);
+        assert!(values.iter().any(|v| *v > 100.0));
+        assert_eq!(*values.last().unwrap(), 100.0);
+
+        // comes to rest in bounds
+        let decay = Decay::new(0.0, 100.0).bounds(0.0, 100.0);
+        assert_eq!(decay.animate(decay.duration().unwrap()), 35.0);
+
+        // comes to rest right before the edge
+        let decay = Decay::new(0.0, 100.3 / 0.35).bounds(0.0, 100.0);
+        assert!(decay.target() > 100.0);
+        let rested = self::values(&decay);
+        assert!(rested.iter().all(|v| *v <= 100.0));
+        assert_eq!(*rested.last().unwrap(), 100.0);
+
+        // starts out of bounds
+        let decay = Decay::new(-50.0, 0.0).bounds(0.0, 100.0);
+        assert_eq!(decay.animate(DURATION_ZERO), -50.0);
+        assert_eq!(decay.animate(decay.duration().unwrap()), 0.0);
+    }
+}
@@ -0,0 +1,31 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+mod decay;
+mod spring;
+
+use crate::core::{math, Animatable};
+pub use decay::Decay;
+pub use spring::{Spring, SpringAnimation, SpringConfig};
+
+/// affine combination `a * wa + b * wb + c * wc`, where `wa + wb + wc == 1`.
+///
+/// [`Animatable`] values are interpolated linearly, so any affine combination of three values
+/// can be composed from two interpolations; it's exact for float based values.
+#[inline]
+pub(crate) fn affine<T: Animatable>(a: (&T, f64), b: (&T, f64), c: (&T, f64)) -> T {
+    let ab = a.1 + b.1;
+    let ac = a.1 + c.1;
+    let bc = b.1 + c.1;
+    // the pair with the largest weight keeps the interpolations stable
+    if math::abs(ab) >= math::abs(ac) && math::abs(ab) >= math::abs(bc) {
+        a.0.animate(b.0, b.1 / ab).animate(c.0, c.1)
+    } else if math::abs(ac) >= math::abs(bc) {
+        a.0.animate(c.0, c.1 / ac).animate(b.0, b.1)
+    } else {
+        b.0.animate(c.0, c.1 / bc).animate(a.0, a.1)
+    }
+}
@@ -0,0 +1,644 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+use super::affine;
+#[cfg(feature = "std")]
+use crate::core::clock::DefaultClock;
+use crate::core::{
+    animation::{AnimNode, BaseAnimation, PathPoint},
+    clock::Clock,
+    math,
+    timeline::Status,
+    Animatable, DURATION_ZERO,
+};
+use core::{fmt, time::Duration};
+
+/// physical parameters of a spring
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringConfig {
+    stiffness: f64,
+    damping: f64,
+    mass: f64,
+    precision: f64,
+}
+
+impl Default for SpringConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+            precision: 0.001,
+        }
+    }
+}
+
+impl SpringConfig {
+    /// spring stiffness, default 170
+    ///
+    /// panics if it's not positive
+    #[inline]
+    pub fn stiffness(mut self, stiffness: f64) -> Self {
+        assert!(stiffness > 0.0);
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// damping force, default 26; the spring oscillates if it's less than `2*sqrt(stiffness*mass)`
+    ///
+    /// panics if it's negative
+    #[inline]
+    pub fn damping(mut self, damping: f64) -> Self {
+        assert!(damping >= 0.0);
+        self.damping = damping;
+        self
+    }
+
+    /// mass of the moving object, default 1
+    ///
+    /// panics if it's not positive
+    #[inline]
+    pub fn mass(mut self, mass: f64) -> Self {
+        assert!(mass > 0.0);
+        self.mass = mass;
+        self
+    }
+
+    /// the spring comes to rest once its motion decays below this fraction
+    /// of the initial displacement, default 0.001
+    ///
+    /// panics if it's not in range (0,1)
+    #[inline]
+    pub fn precision(mut self, precision: f64) -> Self {
+        assert!(precision > 0.0 && precision < 1.0);
+        self.precision = precision;
+        self
+    }
+
+    /// undamped angular frequency
+    #[inline]
+    fn omega(&self) -> f64 {
+        math::sqrt(self.stiffness / self.mass)
+    }
+
+    /// damping ratio
+    #[inline]
+    fn zeta(&self) -> f64 {
+        self.damping / (2.0 * math::sqrt(self.stiffness * self.mass))
+    }
+
+    /// `(a, b, da, db)`, the displacement from target at time `t` is `a * y0 + b * v0`,
+    /// and the velocity is `da * y0 + db * v0`, where `y0` and `v0` are the initial
+    /// displacement and velocity
+    fn coefficients(&self, t: f64) -> (f64, f64, f64, f64) {
+        let w0 = self.omega();
+        let zeta = self.zeta();
+        if zeta < 1.0 {
+            // under damped
+            let wd = w0 * math::sqrt(1.0 - zeta * zeta);
+            let decay = zeta * w0;
+            let e = math::exp(-decay * t);
+            let (sin, cos) = (math::sin(wd * t), math::cos(wd * t));
+            (
+                e * (cos + decay / wd * sin),
+                e * sin / wd,
+                -e * sin * w0 * w0 / wd,
+                e * (cos - decay / wd * sin),
+            )
+        } else if zeta == 1.0 {
+            // critically damped
+            let e = math::exp(-w0 * t);
+            (
+                e * (1.0 + w0 * t),
+                e * t,
+                -e * w0 * w0 * t,
+                e * (1.0 - w0 * t),
+            )
+        } else {
+            // over damped
+            let d = w0 * math::sqrt(zeta * zeta - 1.0);
+            let (r1, r2) = (-zeta * w0 + d, -zeta * w0 - d);
+            let (e1, e2) = (math::exp(r1 * t), math::exp(r2 * t));
+            let d = r1 - r2;
+            (
+                (r1 * e2 - r2 * e1) / d,
+                (e1 - e2) / d,
+                r1 * r2 * (e2 - e1) / d,
+                (r1 * e1 - r2 * e2) / d,
+            )
+        }
+    }
+
+    /// how long it takes to come to rest
+    fn settle_time(&self) -> Duration {
+        let w0 = self.omega();
+        let zeta = self.zeta();
+        // the slowest decay rate
+        let decay = if zeta < 1.0 {
+            zeta * w0
+        } else {
+            w0 * (zeta - math::sqrt(zeta * zeta - 1.0))
+        };
+        if decay <= 0.0 {
+            // undamped, it never comes to rest
+            return Duration::from_secs(u32::MAX as u64);
+        }
+        let mut t = math::ln(1.0 / self.precision) / decay;
+        // the decay is not purely exponential, walk forward until the motion is small enough
+        for _ in 0..100 {
+            let (a, b, da, db) = self.coefficients(t);
+            let motion = math::abs(a) + math::abs(b) * w0 + math::abs(da) / w0 + math::abs(db);
+            if motion < self.precision {
+                break;
+            }
+            t *= 1.1;
+        }
+        Duration::from_secs_f64(t)
+    }
+
+    /// how long it takes until the distance to the target stays below `delta` and the speed
+    /// stays below `speed`, for initial displacement `y0` and velocity `v0`
+    fn rest_time(&self, y0: f64, v0: f64, delta: f64, speed: f64) -> Duration {
+        let settled = |t: f64| {
+            let (a, b, da, db) = self.coefficients(t);
+            math::abs(a) * y0 + math::abs(b) * v0 < delta
+                && math::abs(da) * y0 + math::abs(db) * v0 < speed
+        };
+        // a time when it's surely at rest, then the earliest one it stays at rest from
+        let mut limit = self.settle_time().as_secs_f64();
+        for _ in 0..32 {
+            if settled(limit) {
+                break;
+            }
+            limit *= 2.0;
+        }
+        const STEPS: u32 = 200;
+        let mut n = STEPS;
+        while n > 0 && settled(limit * (n - 1) as f64 / STEPS as f64) {
+            n -= 1;
+        }
+        Duration::from_secs_f64(limit * n as f64 / STEPS as f64)
+    }
+}
+
+/// absolute rest criteria, see [`SpringAnimation::rest_delta()`]
+struct Rest<T> {
+    delta: f64,
+    speed: f64,
+    distance: fn(&T, &T) -> f64,
+}
+
+impl<T> Clone for Rest<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Rest<T> {}
+
+impl<T> fmt::Debug for Rest<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rest")
+            .field("delta", &self.delta)
+            .field("speed", &self.speed)
+            .finish()
+    }
+}
+
+/// a spring [`crate::Animation`] from a snapshot of position and velocity to the target.
+///
+/// it works best for float based values; it can be played by [`crate::Timeline`].
+/// see [`Spring`] for retargeting.
+#[derive(Debug, Clone)]
+pub struct SpringAnimation<T> {
+    config: SpringConfig,
+    from: T,
+    /// where it would be after one second, if it kept its initial velocity
+    ahead: T,
+    to: T,
+    duration: Duration,
+    /// absolute rest criteria instead of [`SpringConfig::precision()`]
+    rest: Option<Rest<T>>,
+}
+
+impl<T: Animatable> SpringAnimation<T> {
+    /// spring animation from `from` to `to`, which starts at rest
+    #[inline]
+    pub fn new(config: SpringConfig, from: T, to: T) -> Self {
+        let ahead = from.clone();
+        Self::with_state(config, from, ahead, to)
+    }
+
+    /// already at rest at `value`
+    #[inline]
+    fn rest(config: SpringConfig, value: T) -> Self {
+        Self {
+            config,
+            from: value.clone(),
+            ahead: value.clone(),
+            to: value,
+            duration: DURATION_ZERO,
+            rest: None,
+        }
+    }
+
+    #[inline]
+    pub(super) fn with_state(config: SpringConfig, from: T, ahead: T, to: T) -> Self {
+        Self {
+            duration: config.settle_time(),
+            config,
+            from,
+            ahead,
+            to,
+            rest: None,
+        }
+    }
+
+    /// the same motion, which comes to rest by the criteria
+    #[inline]
+    fn with_rest(mut self, rest: Option<Rest<T>>) -> Self {
+        self.rest = rest;
+        if let Some(rest) = rest {
+            let y0 = (rest.distance)(&self.from, &self.to);
+            let v0 = (rest.distance)(&self.ahead, &self.from);
+            self.duration = self.config.rest_time(y0, v0, rest.delta, rest.speed);
+        }
+        self
+    }
+
+    /// target value
+    #[inline]
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// position, and where it would be after one second if it kept its velocity
+    fn state(&self, elapsed: Duration) -> (T, T) {
+        if elapsed >= self.duration {
+            return (self.to.clone(), self.to.clone());
+        }
+        let (a, b, da, db) = self.config.coefficients(elapsed.as_secs_f64());
+        // displacement y0 = from - to, velocity v0 = ahead - from
+        let position = affine((&self.to, 1.0 - a), (&self.from, a - b), (&self.ahead, b));
+        let (a, b) = (a + da, b + db);
+        let ahead = affine((&self.to, 1.0 - a), (&self.from, a - b), (&self.ahead, b));
+        (position, ahead)
+    }
+
+    /// new animation from the state at `elapsed`, which moves towards `to` keeping current velocity
+    #[inline]
+    pub fn retarget(&self, elapsed: Duration, to: T) -> Self {
+        let (from, ahead) = self.state(elapsed);
+        Self::with_state(self.config, from, ahead, to).with_rest(self.rest)
+    }
+}
+
+impl<T: PathPoint> SpringAnimation<T> {
+    /// it comes to rest once the distance to the target stays below `rest_delta`, instead of
+    /// [`SpringConfig::precision()`]; see [`SpringAnimation::rest_speed()`]
+    ///
+    /// panics if it's not positive
+    #[inline]
+    pub fn rest_delta(self, rest_delta: f64) -> Self {
+        assert!(rest_delta > 0.0);
+        let speed = self.rest.map_or(f64::INFINITY, |rest| rest.speed);
+        self.rest_criteria(rest_delta, speed)
+    }
+
+    /// it comes to rest once the speed per second stays below `rest_speed`, instead of
+    /// [`SpringConfig::precision()`]; see [`SpringAnimation::rest_delta()`]
+    ///
+    /// panics if it's not positive
+    #[inline]
+    pub fn rest_speed(self, rest_speed: f64) -> Self {
+        assert!(rest_speed > 0.0);
+        let delta = self.rest.map_or(f64::INFINITY, |rest| rest.delta);
+        self.rest_criteria(delta, rest_speed)
+    }
+
+    #[inline]
+    fn rest_criteria(self, delta: f64, speed: f64) -> Self {
+        let distance = T::distance;
+        self.with_rest(Some(Rest {
+            delta,
+            speed,
+            distance,
+        }))
+    }
+}
+
+impl<T: Animatable> BaseAnimation for SpringAnimation<T> {
+    type Item = T;
+
+    #[inline]
+    fn duration(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+
+    #[inline]
+    fn animate(&self, elapsed: Duration) -> Self::Item {
+        if elapsed >= self.duration {
+            return self.to.clone();
+        }
+        let (a, b, _, _) = self.config.coefficients(elapsed.as_secs_f64());
+        affine((&self.to, 1.0 - a), (&self.from, a - b), (&self.ahead, b))
+    }
+
+    #[inline]
+    fn describe(&self) -> AnimNode {
+        AnimNode::new("spring", self.duration())
+    }
+}
+
+/// spring physics, which can change its target at any time while keeping its position and velocity.
+///
+/// it's the foundation of interruptible motion; it works best for float based values.
+///
+/// ## Example
+/// ```rust
+/// use anim::physics::{Spring, SpringConfig};
+///
+/// let mut spring: Spring<f32> = Spring::with_config(0.0, SpringConfig::default().damping(10.0));
+/// spring.set_target(100.0);
+/// // on every frame
+/// let status = spring.update();
+/// let value = spring.value();
+/// // later, it moves smoothly towards the new target
+/// spring.set_target(50.0);
+/// ```
+pub struct Spring<
+    T,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    animation: SpringAnimation<T>,
+    start: C::Time,
+    clock: C,
+}
+
+impl<T: Animatable, C: Clock> Spring<T, C> {
+    /// spring at rest at `value`
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self::with_config(value, Default::default())
+    }
+
+    /// spring at rest at `value`
+    #[inline]
+    pub fn with_config(value: T, config: SpringConfig) -> Self {
+        let clock = C::default();
+        Self {
+            animation: SpringAnimation::rest(config, value),
+            start: clock.now(),
+            clock,
+        }
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// physical parameters
+    #[inline]
+    pub fn config(&self) -> &SpringConfig {
+        &self.animation.config
+    }
+
+    /// change physical parameters, keeping current position and velocity
+    #[inline]
+    pub fn set_config(&mut self, config: SpringConfig) {
+        let (from, ahead) = self.animation.state(self.elapsed());
+        let to = self.animation.to.clone();
+        let rest = self.animation.rest;
+        self.animation = SpringAnimation::with_state(config, from, ahead, to).with_rest(rest);
+        self.start = self.clock.now();
+    }
+
+    /// target value
+    #[inline]
+    pub fn target(&self) -> &T {
+        &self.animation.to
+    }
+
+    /// move towards a new target, keeping current position and velocity
+    #[inline]
+    pub fn set_target(&mut self, target: T) {
+        self.animation = self.animation.retarget(self.elapsed(), target);
+        self.start = self.clock.now();
+    }
+
+    /// jump to `value` and stay at rest there
+    #[inline]
+    pub fn set_value(&mut self, value: T) {
+        let rest = self.animation.rest;
+        self.animation = SpringAnimation::rest(self.animation.config, value);
+        self.animation.rest = rest;
+        self.start = self.clock.now();
+    }
+
+    /// current value
+    #[inline]
+    pub fn value(&self) -> T {
+        self.animation.animate(self.elapsed())
+    }
+
+    /// update the clock, returns [`Status::Completed`] once it's at rest
+    #[inline]
+    pub fn update(&mut self) -> Status {
+        self.clock.tick();
+        if self.is_resting() {
+            Status::Completed
+        } else {
+            Status::Animating
+        }
+    }
+
+    /// is it at rest?
+    #[inline]
+    pub fn is_resting(&self) -> bool {
+        self.elapsed() >= self.animation.duration
+    }
+
+    /// the rest of the motion from now on, which can be played by [`crate::Timeline`]
+    #[inline]
+    pub fn animation(&self) -> SpringAnimation<T> {
+        let to = self.animation.to.clone();
+        self.animation.retarget(self.elapsed(), to)
+    }
+
+    #[inline]
+    fn elapsed(&self) -> Duration {
+        let elapsed = self.clock.elapsed(&self.start);
+        if elapsed > self.animation.duration {
+            self.animation.duration
+        } else {
+            elapsed
+        }
+    }
+}
+
+impl<T: Animatable + Default, C: Clock> Spring<T, C> {
+    /// current velocity per second, relative to `T::default()`
+    #[inline]
+    pub fn velocity(&self) -> T {
+        let (position, ahead) = self.animation.state(self.elapsed());
+        affine((&T::default(), 1.0), (&ahead, 1.0), (&position, -1.0))
+    }
+
+    /// change current velocity per second, relative to `T::default()`, e.g. the velocity of a gesture
+    #[inline]
+    pub fn set_velocity(&mut self, velocity: T) {
+        let (position, _) = self.animation.state(self.elapsed());
+        let ahead = affine((&position, 1.0), (&velocity, 1.0), (&T::default(), -1.0));
+        let to = self.animation.to.clone();
+        let (config, rest) = (self.animation.config, self.animation.rest);
+        self.animation = SpringAnimation::with_state(config, position, ahead, to).with_rest(rest);
+        self.start = self.clock.now();
+    }
+}
+
+impl<T: PathPoint, C: Clock> Spring<T, C> {
+    /// it comes to rest once the distance to the target stays below `rest_delta`,
+    /// see [`SpringAnimation::rest_delta()`]
+    #[inline]
+    pub fn set_rest_delta(&mut self, rest_delta: f64) {
+        self.animation = self.animation.clone().rest_delta(rest_delta);
+    }
+
+    /// it comes to rest once the speed per second stays below `rest_speed`,
+    /// see [`SpringAnimation::rest_speed()`]
+    #[inline]
+    pub fn set_rest_speed(&mut self, rest_speed: f64) {
+        self.animation = self.animation.clone().rest_speed(rest_speed);
+    }
+}
+
+impl<T: Animatable + Default, C: Clock> Default for Spring<T, C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug, C: Clock> fmt::Debug for Spring<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Spring")
+            .field("animation", &self.animation)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::clock::TestClock;
+
+    const FRAME: Duration = Duration::from_millis(10);
+
+    fn run(spring: &mut Spring<f64, TestClock>) -> alloc::vec::Vec<f64> {
+        let mut values = vec![spring.value()];
+        while spring.update() == Status::Animating {
+            spring.clock_mut().0 += FRAME;
+            values.push(spring.value());
+            assert!(values.len() < 1000);
+        }
+        values
+    }
+
+    #[test]
+    fn test_spring() {
+        let mut spring: Spring<f64, TestClock> = Spring::new(0.0);
+        assert!(spring.is_resting());
+        spring.set_target(1.0);
+        let values = run(&mut spring);
+        assert_eq!(*values.last().unwrap(), 1.0);
+        assert_eq!(spring.value(), 1.0);
+
+        // under damped, overshoots
+        let mut spring: Spring<f64, TestClock> =
+            Spring::with_config(0.0, SpringConfig::default().damping(5.0));
+        spring.set_target(1.0);
+        let values = run(&mut spring);
+        assert!(values.iter().any(|v| *v > 1.1));
+
+        // critically & over damped, never overshoots
+        for damping in [2.0 * 170f64.sqrt(), 60.0].iter() {
+            let mut spring: Spring<f64, TestClock> =
+                Spring::with_config(0.0, SpringConfig::default().damping(*damping));
+            spring.set_target(1.0);
+            let values = run(&mut spring);
+            assert!(values.iter().all(|v| *v <= 1.0 + 1e-9));
+            assert!(values.windows(2).all(|w| w[0] <= w[1] + 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_spring_retarget() {
+        let mut spring: Spring<f64, TestClock> = Spring::new(0.0);
+        spring.set_target(1.0);
+        spring.clock_mut().0 = Duration::from_millis(100);
+        let value = spring.value();
+        let velocity = spring.velocity();
+        assert!(velocity > 0.0);
+
+        // velocity matches the motion
+        spring.clock_mut().0 = Duration::from_millis(101);
+        let next = spring.value();
+        assert!(((next - value) * 1000.0 - velocity).abs() < velocity * 0.01);
+
+        spring.clock_mut().0 = Duration::from_millis(100);
+        spring.set_target(-1.0);
+        assert!((spring.value() - value).abs() < 1e-9);
+        assert!((spring.velocity() - velocity).abs() < 1e-9);
+
+        // keeps moving forward for a while
+        spring.clock_mut().0 = Duration::from_millis(110);
+        assert!(spring.value() > value);
+
+        let values = run(&mut spring);
+        assert_eq!(*values.last().unwrap(), -1.0);
+    }
+
+    #[test]
+    fn test_spring_rest() {
+        let animation = SpringAnimation::new(SpringConfig::default(), 0.0f64, 100.0);
+        let precise = animation.duration().unwrap();
+        let rough = animation.clone().rest_delta(1.0).rest_speed(10.0);
+        let duration = rough.duration().unwrap();
+        assert!(duration < precise);
+        let near = rough.animate(duration - Duration::from_millis(1));
+        assert!((near - 100.0).abs() < 1.0);
+        assert_eq!(rough.animate(duration), 100.0);
+
+        // undamped, it comes to rest only by the criteria
+        let config = SpringConfig::default().damping(0.0);
+        let mut spring: Spring<f64, TestClock> = Spring::with_config(0.0, config);
+        spring.set_rest_delta(1e3);
+        spring.set_rest_speed(1e5);
+        spring.set_target(1.0);
+        assert_eq!(spring.update(), Status::Completed);
+        assert_eq!(spring.value(), 1.0);
+    }
+
+    #[test]
+    fn test_spring_animation() {
+        let mut spring: Spring<f64, TestClock> = Spring::new(0.0);
+        spring.set_velocity(10.0);
+        let animation = spring.animation();
+        assert_eq!(animation.animate(DURATION_ZERO), 0.0);
+        assert!(animation.animate(Duration::from_millis(50)) > 0.0);
+        let duration = animation.duration().unwrap();
+        assert_eq!(animation.animate(duration), 0.0);
+    }
+}
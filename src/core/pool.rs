@@ -0,0 +1,143 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{animation::Animation, clock::Clock, MaybeSend, Timeline};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// recycles [`Timeline`]s of short-lived transitions, e.g. hover effects, which fire constantly.
+///
+/// a released timeline keeps its id and allocations; once acquired again with an animation of
+/// the same type, no allocation happens.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Options, TimelinePool};
+///
+/// let mut pool: TimelinePool<f32> = TimelinePool::new(16);
+/// // on hover
+/// let mut timeline = pool.acquire(Options::new(0.0, 1.0).build());
+/// timeline.begin();
+/// // once completed
+/// pool.release(timeline);
+/// ```
+pub struct TimelinePool<
+    T,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    free: Vec<Timeline<T, C>>,
+    capacity: usize,
+}
+
+impl<T, C: Clock> TimelinePool<T, C> {
+    /// keep at most `capacity` released timelines
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            free: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// an idle timeline of `animation`, recycled if any
+    #[inline]
+    pub fn acquire<A>(&mut self, animation: A) -> Timeline<T, C>
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+    {
+        match self.free.pop() {
+            Some(mut timeline) => {
+                timeline.recycle(animation);
+                timeline
+            }
+            None => Timeline::new(animation),
+        }
+    }
+
+    /// give the timeline back for reuse; it's dropped if the pool is full
+    #[inline]
+    pub fn release(&mut self, timeline: Timeline<T, C>) {
+        if self.free.len() < self.capacity {
+            self.free.push(timeline);
+        }
+    }
+
+    /// number of released timelines
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    /// has no released timeline?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// drop all released timelines
+    #[inline]
+    pub fn clear(&mut self) {
+        self.free.clear();
+    }
+}
+
+impl<T, C: Clock> fmt::Debug for TimelinePool<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimelinePool")
+            .field("len", &self.free.len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{clock::TestClock, easing, timeline::Status, Options};
+    use core::time::Duration;
+
+    #[test]
+    fn test_pool() {
+        let at = Duration::from_millis;
+        let build = |to: f32| {
+            Options::new(0.0, to)
+                .easing(easing::linear())
+                .duration(at(1000))
+                .build()
+        };
+        let mut pool: TimelinePool<f32, TestClock> = TimelinePool::new(1);
+        let mut timeline = pool.acquire(build(1.0));
+        let id = timeline.id();
+        timeline.begin();
+        timeline.add_marker("half", at(500));
+        timeline.clock_mut().0 = at(1000);
+        assert_eq!(timeline.update(), Status::Completed);
+        pool.release(timeline);
+        // full
+        pool.release(Timeline::new(build(1.0)));
+        assert_eq!(pool.len(), 1);
+
+        let mut timeline = pool.acquire(build(2.0));
+        assert!(pool.is_empty());
+        assert_eq!(timeline.id(), id);
+        assert_eq!(timeline.status(), Status::Idle);
+        timeline.begin();
+        timeline.clock_mut().0 = at(1500);
+        timeline.update();
+        assert_eq!(timeline.value(), 1.0);
+
+        // of another type
+        let mut timeline = pool.acquire(build(2.0).map(|v| v * 2.0));
+        timeline.begin();
+        timeline.clock_mut().0 = at(1000);
+        timeline.update();
+        assert_eq!(timeline.value(), 4.0);
+    }
+}
@@ -0,0 +1,226 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{animation::Animation, clock::Clock, timeline::Status};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, time::Duration};
+
+/// writes the animated value at the elapsed time into the model
+type Apply<M> = Box<dyn Fn(&mut M, Duration)>;
+
+/// an animation bound to a field of the model
+struct Property<M, Time> {
+    apply: Apply<M>,
+    duration: Option<Duration>,
+    start: Time,
+}
+
+/// animates the fields of a model, every animation is registered against an accessor of the field;
+/// then [`PropertyAnimator::apply()`] writes all animated values into the model at once.
+///
+/// ## Example
+/// ```rust
+/// use anim::{Options, PropertyAnimator};
+///
+/// #[derive(Default)]
+/// struct Model {
+///     width: f32,
+///     opacity: f32,
+/// }
+///
+/// let mut animator: PropertyAnimator<Model> = PropertyAnimator::new();
+/// animator.add(|m: &mut Model| &mut m.width, Options::new(0.0, 100.0).build());
+/// animator.add(|m: &mut Model| &mut m.opacity, Options::new(0.0, 1.0).build());
+///
+/// let mut model = Model::default();
+/// // on every frame
+/// let status = animator.update();
+/// animator.apply(&mut model);
+/// ```
+pub struct PropertyAnimator<
+    M,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    properties: Vec<Property<M, C::Time>>,
+    clock: C,
+}
+
+impl<M, C: Clock> PropertyAnimator<M, C> {
+    /// create an empty animator
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            properties: Vec::new(),
+            clock: Default::default(),
+        }
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// animate the field returned by `accessor`, which starts from now on
+    #[inline]
+    pub fn add<T, F, A>(&mut self, accessor: F, animation: A)
+    where
+        F: Fn(&mut M) -> &mut T + 'static,
+        A: Animation<Item = T> + 'static,
+    {
+        let duration = animation.duration();
+        self.properties.push(Property {
+            apply: Box::new(move |model, elapsed| *accessor(model) = animation.animate(elapsed)),
+            duration,
+            start: self.clock.now(),
+        });
+    }
+
+    /// restart all animations
+    #[inline]
+    pub fn begin(&mut self) {
+        let now = self.clock.now();
+        self.properties
+            .iter_mut()
+            .for_each(|item| item.start = now.clone());
+    }
+
+    /// remove all animations
+    #[inline]
+    pub fn clear(&mut self) {
+        self.properties.clear();
+    }
+
+    /// number of animations
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    /// has no animation?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    /// update the clock, returns [`Status::Completed`] once all animations are finished
+    #[inline]
+    pub fn update(&mut self) -> Status {
+        self.clock.tick();
+        if self.properties.is_empty() {
+            return Status::Idle;
+        }
+        let clock = &self.clock;
+        let animating = self.properties.iter().any(|item| match item.duration {
+            Some(duration) => clock.elapsed(&item.start) < duration,
+            None => true,
+        });
+        if animating {
+            Status::Animating
+        } else {
+            Status::Completed
+        }
+    }
+
+    /// write current values of all animations into `model`
+    #[inline]
+    pub fn apply(&self, model: &mut M) {
+        for item in self.properties.iter() {
+            (item.apply)(model, self.clock.elapsed(&item.start));
+        }
+    }
+}
+
+impl<M, C: Clock> Default for PropertyAnimator<M, C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M, C: Clock> fmt::Debug for PropertyAnimator<M, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PropertyAnimator")
+            .field("properties", &self.properties.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{clock::TestClock, easing, Options};
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Model {
+        width: f32,
+        height: f32,
+        visible: bool,
+    }
+
+    #[test]
+    fn test_property_animator() {
+        let mut animator: PropertyAnimator<Model, TestClock> = PropertyAnimator::new();
+        assert_eq!(animator.update(), Status::Idle);
+        animator.add(
+            |m: &mut Model| &mut m.width,
+            Options::new(0.0, 100.0)
+                .duration(Duration::from_millis(1000))
+                .easing(easing::linear())
+                .build(),
+        );
+        animator.add(
+            |m: &mut Model| &mut m.height,
+            Options::new(100.0, 0.0)
+                .duration(Duration::from_millis(500))
+                .easing(easing::linear())
+                .build(),
+        );
+        animator.add(
+            |m: &mut Model| &mut m.visible,
+            Options::new(false, true)
+                .duration(Duration::from_millis(500))
+                .build(),
+        );
+        assert_eq!(animator.len(), 3);
+
+        let mut model = Model::default();
+        animator.clock_mut().0 = Duration::from_millis(250);
+        assert_eq!(animator.update(), Status::Animating);
+        animator.apply(&mut model);
+        assert_eq!(
+            model,
+            Model {
+                width: 25.0,
+                height: 50.0,
+                visible: false
+            }
+        );
+
+        animator.clock_mut().0 = Duration::from_millis(1000);
+        assert_eq!(animator.update(), Status::Completed);
+        animator.apply(&mut model);
+        assert_eq!(
+            model,
+            Model {
+                width: 100.0,
+                height: 0.0,
+                visible: true
+            }
+        );
+
+        animator.begin();
+        animator.apply(&mut model);
+        assert_eq!(model.width, 0.0);
+    }
+}
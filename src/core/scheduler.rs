@@ -0,0 +1,246 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{clock::Clock, DURATION_ZERO};
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// evaluation priority of an animation within a frame, see [`FrameScheduler`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// deferred first once the budget is exceeded
+    Low,
+    /// deferred once the budget is exceeded
+    Normal,
+    /// never deferred
+    High,
+}
+
+impl Default for Priority {
+    #[inline]
+    fn default() -> Self {
+        Priority::Normal
+    }
+}
+
+/// what happened in a frame, see [`FrameScheduler::run()`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameReport {
+    /// number of evaluated tasks
+    pub evaluated: usize,
+    /// number of tasks deferred to later frames
+    pub deferred: usize,
+    /// time spent in the frame
+    pub elapsed: Duration,
+    /// time spent beyond the budget
+    pub overrun: Duration,
+}
+
+impl FrameReport {
+    /// did the frame exceed its budget?
+    #[inline]
+    pub fn is_overrun(&self) -> bool {
+        self.overrun > DURATION_ZERO
+    }
+}
+
+/// evaluates animations within a per-frame time budget, so heavy animation load does not
+/// delay input handling.
+///
+/// tasks are evaluated by [`Priority`], from high to low; once the budget is exceeded,
+/// the rest tasks are deferred except [`Priority::High`] ones.
+/// the time is measured by its [`Clock`].
+///
+/// deferred tasks are aged: the longer a task has been deferred, the earlier it's evaluated in
+/// the next frames, right after [`Priority::High`] ones, so none of them starve.
+/// tasks are identified by their position in `tasks`, keep it stable across frames.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{FrameScheduler, Priority};
+///
+/// let mut scheduler: FrameScheduler = FrameScheduler::new(Duration::from_millis(4));
+/// let mut tasks = vec![(Priority::High, 0), (Priority::Low, 0)];
+/// let report = scheduler.run(&mut tasks, |task| task.0, |task| task.1 += 1);
+/// assert_eq!(report.evaluated + report.deferred, 2);
+/// ```
+#[derive(Debug)]
+pub struct FrameScheduler<
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    budget: Duration,
+    overruns: usize,
+    /// number of frames each task has been deferred in a row, by its position
+    ages: Vec<u32>,
+    clock: C,
+}
+
+impl<C: Clock> FrameScheduler<C> {
+    /// spend at most `budget` per frame
+    #[inline]
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            overruns: 0,
+            ages: Vec::new(),
+            clock: Default::default(),
+        }
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// time budget per frame
+    #[inline]
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// change time budget per frame
+    #[inline]
+    pub fn set_budget(&mut self, budget: Duration) {
+        self.budget = budget;
+    }
+
+    /// number of frames which exceeded the budget so far
+    #[inline]
+    pub fn overruns(&self) -> usize {
+        self.overruns
+    }
+
+    /// evaluate `tasks` of one frame by `eval`, in the order of their `priority` and how long
+    /// they have been deferred
+    pub fn run<T>(
+        &mut self,
+        tasks: &mut [T],
+        priority: impl Fn(&T) -> Priority,
+        mut eval: impl FnMut(&mut T),
+    ) -> FrameReport {
+        self.clock.tick();
+        let start = self.clock.now();
+        self.ages.resize(tasks.len(), 0);
+        let ages = &self.ages;
+        let mut order: Vec<usize> = (0..tasks.len()).collect();
+        // stable, keeps the order of the same priority and age
+        order.sort_by_key(|i| {
+            let priority = priority(&tasks[*i]);
+            core::cmp::Reverse((priority == Priority::High, ages[*i], priority))
+        });
+
+        let mut report = FrameReport::default();
+        for i in order {
+            let task = &mut tasks[i];
+            if priority(task) < Priority::High && self.clock.elapsed(&start) >= self.budget {
+                self.ages[i] = self.ages[i].saturating_add(1);
+                report.deferred += 1;
+                continue;
+            }
+            eval(task);
+            self.ages[i] = 0;
+            report.evaluated += 1;
+        }
+        report.elapsed = self.clock.elapsed(&start);
+        report.overrun = report.elapsed.checked_sub(self.budget).unwrap_or_default();
+        if report.is_overrun() {
+            self.overruns += 1;
+            trace_event!(warn, overrun = ?report.overrun, deferred = report.deferred, "frame overrun");
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::cell::Cell;
+
+    std::thread_local! {
+        static NOW: Cell<Duration> = const { Cell::new(DURATION_ZERO) };
+    }
+
+    /// advanced by the tasks
+    #[derive(Debug, Default)]
+    struct TaskClock;
+
+    impl Clock for TaskClock {
+        type Time = Duration;
+
+        fn now(&self) -> Duration {
+            NOW.with(|now| now.get())
+        }
+    }
+
+    #[test]
+    fn test_frame_scheduler() {
+        let mut scheduler: FrameScheduler<TaskClock> =
+            FrameScheduler::new(Duration::from_millis(4));
+        let mut tasks = vec![
+            (Priority::Low, 0),
+            (Priority::Normal, 0),
+            (Priority::High, 0),
+            (Priority::Normal, 0),
+            (Priority::High, 0),
+        ];
+        let eval = |task: &mut (Priority, usize)| {
+            task.1 += 1;
+            NOW.with(|now| now.set(now.get() + Duration::from_millis(2)));
+        };
+
+        // high: 4ms, then all deferred
+        let report = scheduler.run(&mut tasks, |task| task.0, eval);
+        assert_eq!(report.evaluated, 2);
+        assert_eq!(report.deferred, 3);
+        assert!(!report.is_overrun());
+        assert_eq!(
+            tasks.iter().map(|task| task.1).collect::<Vec<_>>(),
+            vec![0, 0, 1, 0, 1]
+        );
+
+        scheduler.set_budget(Duration::from_millis(5));
+        let report = scheduler.run(&mut tasks, |task| task.0, eval);
+        assert_eq!(report.evaluated, 3);
+        assert_eq!(report.overrun, Duration::from_millis(1));
+        assert_eq!(scheduler.overruns(), 1);
+        assert_eq!(
+            tasks.iter().map(|task| task.1).collect::<Vec<_>>(),
+            vec![0, 1, 2, 0, 2]
+        );
+    }
+
+    #[test]
+    fn test_frame_scheduler_no_starvation() {
+        // only one task fits in a frame
+        let mut scheduler: FrameScheduler<TaskClock> =
+            FrameScheduler::new(Duration::from_millis(2));
+        let mut tasks = vec![
+            (Priority::Normal, 0),
+            (Priority::Low, 0),
+            (Priority::Normal, 0),
+            (Priority::Low, 0),
+            (Priority::Low, 0),
+        ];
+        let eval = |task: &mut (Priority, usize)| {
+            task.1 += 1;
+            NOW.with(|now| now.set(now.get() + Duration::from_millis(2)));
+        };
+        for _ in 0..tasks.len() * 2 {
+            let report = scheduler.run(&mut tasks, |task| task.0, eval);
+            assert_eq!(report.evaluated, 1);
+        }
+        assert!(tasks.iter().all(|task| task.1 > 0));
+    }
+}
@@ -0,0 +1,230 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{animation::Animation, clock::Clock, math, timeline::Status, DURATION_ZERO};
+use core::{fmt, ops::Range, time::Duration};
+
+/// progress below this distance is settled
+const EPSILON: f64 = 1e-6;
+
+/// drives a finite animation by a scroll offset, e.g. parallax and scroll-reveal effects.
+///
+/// offsets in the range map to the progress of the animation linearly, optionally followed
+/// by a low-pass filter to smooth out jumpy scroll events.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{Options, ScrollDriver};
+///
+/// // fade in while scrolling from 200px to 600px
+/// let mut driver: ScrollDriver<_> =
+///     ScrollDriver::new(Options::new(0.0, 1.0).build(), 200.0..600.0)
+///         .smoothing(Duration::from_millis(100));
+/// // on scroll
+/// driver.set_offset(350.0);
+/// // on every frame
+/// let status = driver.update();
+/// let opacity = driver.value();
+/// ```
+pub struct ScrollDriver<
+    A: Animation,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    animation: A,
+    duration: Duration,
+    range: Range<f64>,
+    smoothing: Duration,
+    clamp: bool,
+    offset: f64,
+    /// progress of the offset
+    target: f64,
+    /// smoothed progress
+    progress: f64,
+    /// time of the previous update
+    time: C::Time,
+    clock: C,
+}
+
+impl<A: Animation, C: Clock> ScrollDriver<A, C> {
+    /// drive `animation` by offsets in `range`, it's at the start of the range initially;
+    /// the range can be reversed, e.g. `600.0..200.0`
+    ///
+    /// panics if the animation never finishes or the range is empty
+    #[inline]
+    pub fn new(animation: A, range: Range<f64>) -> Self {
+        let duration = animation
+            .duration()
+            .expect("scroll driver requires a finite animation");
+        assert!(
+            range.start != range.end && (range.end - range.start).is_finite(),
+            "invalid scroll range: {:?}",
+            range
+        );
+        let clock = C::default();
+        Self {
+            animation,
+            duration,
+            offset: range.start,
+            range,
+            smoothing: DURATION_ZERO,
+            clamp: true,
+            target: 0.0,
+            progress: 0.0,
+            time: clock.now(),
+            clock,
+        }
+    }
+
+    /// follow the offset by a low-pass filter, which covers about 63% of a change within
+    /// `time_constant`; default zero, i.e. no smoothing
+    #[inline]
+    pub fn smoothing(mut self, time_constant: Duration) -> Self {
+        self.smoothing = time_constant;
+        self
+    }
+
+    /// clamp the progress into 0..=1, default true; otherwise [`ScrollDriver::progress()`]
+    /// goes beyond it for offsets out of the range, e.g. for parallax, while the animation
+    /// still holds its first or last value
+    #[inline]
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self.set_offset(self.offset);
+        self
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// the latest scroll offset
+    #[inline]
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// scroll to `offset`; it takes effect immediately without smoothing
+    #[inline]
+    pub fn set_offset(&mut self, offset: f64) {
+        self.offset = offset;
+        let progress = (offset - self.range.start) / (self.range.end - self.range.start);
+        self.target = if self.clamp {
+            progress.clamp(0.0, 1.0)
+        } else {
+            progress
+        };
+        if self.smoothing == DURATION_ZERO {
+            self.progress = self.target;
+        }
+    }
+
+    /// current progress, see [`ScrollDriver::clamp()`]
+    #[inline]
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+
+    /// update the clock, returns [`Status::Animating`] while the smoothed progress is catching
+    /// up with the offset, otherwise [`Status::Idle`]
+    #[inline]
+    pub fn update(&mut self) -> Status {
+        self.clock.tick();
+        let elapsed = self.clock.elapsed(&self.time);
+        self.time = self.clock.now();
+        if self.smoothing > DURATION_ZERO {
+            let time = 1.0 - math::exp(-elapsed.as_secs_f64() / self.smoothing.as_secs_f64());
+            self.progress += (self.target - self.progress) * time;
+        }
+        if math::abs(self.target - self.progress) < EPSILON {
+            self.progress = self.target;
+            Status::Idle
+        } else {
+            Status::Animating
+        }
+    }
+
+    /// current value
+    #[inline]
+    pub fn value(&self) -> A::Item {
+        let progress = self.progress.clamp(0.0, 1.0);
+        self.animation.animate(self.duration.mul_f64(progress))
+    }
+}
+
+impl<A: Animation + fmt::Debug, C: Clock> fmt::Debug for ScrollDriver<A, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScrollDriver")
+            .field("animation", &self.animation)
+            .field("range", &self.range)
+            .field("offset", &self.offset)
+            .field("progress", &self.progress)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{clock::TestClock, easing, Options};
+
+    fn driver() -> ScrollDriver<impl Animation<Item = f32>, TestClock> {
+        ScrollDriver::new(
+            Options::new(0.0, 100.0)
+                .duration(Duration::from_millis(1000))
+                .easing(easing::linear())
+                .build(),
+            200.0..600.0,
+        )
+    }
+
+    #[test]
+    fn test_scroll_driver() {
+        let mut driver = driver();
+        assert_eq!(driver.value(), 0.0);
+        driver.set_offset(300.0);
+        assert_eq!(driver.update(), Status::Idle);
+        assert_eq!(driver.value(), 25.0);
+        driver.set_offset(1000.0);
+        assert_eq!(driver.progress(), 1.0);
+        assert_eq!(driver.value(), 100.0);
+
+        let mut driver = driver.clamp(false);
+        assert_eq!(driver.progress(), 2.0);
+        assert_eq!(driver.value(), 100.0);
+        driver.set_offset(0.0);
+        assert_eq!(driver.progress(), -0.5);
+        assert_eq!(driver.value(), 0.0);
+
+        // reversed
+        let mut driver: ScrollDriver<_, TestClock> =
+            ScrollDriver::new(Options::new(0.0, 1.0).build(), 100.0..0.0);
+        driver.set_offset(0.0);
+        assert_eq!(driver.value(), 1.0);
+    }
+
+    #[test]
+    fn test_scroll_driver_smoothing() {
+        let mut driver = driver().smoothing(Duration::from_millis(100));
+        driver.set_offset(600.0);
+        assert_eq!(driver.progress(), 0.0);
+        driver.clock_mut().0 = Duration::from_millis(100);
+        assert_eq!(driver.update(), Status::Animating);
+        assert!((driver.progress() - (1.0 - (-1.0_f64).exp())).abs() < 1e-9);
+        driver.clock_mut().0 = Duration::from_millis(5000);
+        assert_eq!(driver.update(), Status::Idle);
+        assert_eq!(driver.value(), 100.0);
+    }
+}
@@ -0,0 +1,268 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{animation::Animation, clock::Clock, math, timeline::Status, DURATION_ZERO};
+use core::{fmt, time::Duration};
+
+/// where a released [`Scrubber`] plays to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Release {
+    /// play forward to the end of the animation
+    Complete,
+    /// play backward to the beginning of the animation
+    Cancel,
+}
+
+#[derive(Debug)]
+enum State<Time> {
+    /// driven by [`Scrubber::set_progress()`]
+    Scrubbing,
+    /// played by the clock after released
+    Playing {
+        time: Time,
+        from: f64,
+        /// progress per second, negative for [`Release::Cancel`]
+        speed: f64,
+    },
+    /// played to the end or to the beginning
+    Completed,
+}
+
+/// drives a finite animation by an external progress, e.g. the distance of a drag gesture,
+/// then plays it by time once released.
+///
+/// it's the coordination layer of interactive transitions, like swipe back or pull to reveal.
+///
+/// ## Example
+/// ```rust
+/// use anim::{Options, Scrubber};
+///
+/// let mut scrubber: Scrubber<_> = Scrubber::new(Options::new(0.0, 300.0).build());
+/// // while dragging
+/// scrubber.set_progress(0.4);
+/// let value = scrubber.value();
+/// // on release, with the progress velocity of the gesture
+/// let release = scrubber.release(1.5);
+/// // on every frame
+/// let status = scrubber.update();
+/// let value = scrubber.value();
+/// ```
+pub struct Scrubber<
+    A: Animation,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    animation: A,
+    duration: Duration,
+    progress: f64,
+    threshold: f64,
+    state: State<C::Time>,
+    clock: C,
+}
+
+impl<A: Animation, C: Clock> Scrubber<A, C> {
+    /// scrub `animation` from its beginning
+    ///
+    /// panics if the animation never finishes
+    #[inline]
+    pub fn new(animation: A) -> Self {
+        let duration = animation
+            .duration()
+            .expect("scrubber requires a finite animation");
+        Self {
+            animation,
+            duration,
+            progress: 0.0,
+            threshold: 0.5,
+            state: State::Scrubbing,
+            clock: Default::default(),
+        }
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// once released slowly, it completes if the progress is not less than this value,
+    /// otherwise it cancels; default 0.5
+    #[inline]
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// current progress in 0..=1
+    #[inline]
+    pub fn progress(&self) -> f64 {
+        match &self.state {
+            State::Playing { time, from, speed } => {
+                let elapsed = self.clock.elapsed(time).as_secs_f64();
+                (from + speed * elapsed).clamp(0.0, 1.0)
+            }
+            _ => self.progress,
+        }
+    }
+
+    /// scrub to `progress`, it's clamped into 0..=1; it interrupts the playback if released
+    #[inline]
+    pub fn set_progress(&mut self, progress: f64) {
+        self.progress = progress.clamp(0.0, 1.0);
+        self.state = State::Scrubbing;
+    }
+
+    /// hand off to the clock with `velocity` of the progress per second.
+    ///
+    /// it completes if flung forward, cancels if flung backward, otherwise it depends on
+    /// [`Scrubber::threshold()`]. it plays in the normal speed of the animation, or faster
+    /// to keep up with the velocity.
+    pub fn release(&mut self, velocity: f64) -> Release {
+        let from = self.progress();
+        let secs = self.duration.as_secs_f64();
+        // a fling is faster than the normal speed
+        let normal = if secs > 0.0 {
+            1.0 / secs
+        } else {
+            f64::INFINITY
+        };
+        let release = if velocity >= normal {
+            Release::Complete
+        } else if velocity <= -normal {
+            Release::Cancel
+        } else if from >= self.threshold {
+            Release::Complete
+        } else {
+            Release::Cancel
+        };
+        let speed = normal.max(math::abs(velocity));
+        let speed = match release {
+            Release::Complete => speed,
+            Release::Cancel => -speed,
+        };
+        self.progress = from;
+        self.state = State::Playing {
+            time: self.clock.now(),
+            from,
+            speed,
+        };
+        release
+    }
+
+    /// update the clock, returns [`Status::Completed`] once the playback reached the end or the beginning
+    #[inline]
+    pub fn update(&mut self) -> Status {
+        self.clock.tick();
+        if let State::Playing { .. } = self.state {
+            let progress = self.progress();
+            if progress <= 0.0 || progress >= 1.0 {
+                self.progress = progress;
+                self.state = State::Completed;
+            }
+        }
+        self.status()
+    }
+
+    /// - `Idle` while scrubbing
+    /// - `Animating` while playing after released
+    /// - `Completed` once played to the end or to the beginning
+    #[inline]
+    pub fn status(&self) -> Status {
+        match self.state {
+            State::Scrubbing => Status::Idle,
+            State::Playing { .. } => Status::Animating,
+            State::Completed => Status::Completed,
+        }
+    }
+
+    /// current value
+    #[inline]
+    pub fn value(&self) -> A::Item {
+        let elapsed = if self.duration > DURATION_ZERO {
+            self.duration.mul_f64(self.progress())
+        } else {
+            DURATION_ZERO
+        };
+        self.animation.animate(elapsed)
+    }
+}
+
+impl<A: Animation + fmt::Debug, C: Clock> fmt::Debug for Scrubber<A, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scrubber")
+            .field("animation", &self.animation)
+            .field("progress", &self.progress())
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{clock::TestClock, easing, Options};
+
+    fn scrubber() -> Scrubber<impl Animation<Item = f32>, TestClock> {
+        Scrubber::new(
+            Options::new(0.0, 100.0)
+                .duration(Duration::from_millis(1000))
+                .easing(easing::linear())
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_scrubber() {
+        let mut scrubber = scrubber();
+        assert_eq!(scrubber.update(), Status::Idle);
+        scrubber.set_progress(0.25);
+        assert_eq!(scrubber.value(), 25.0);
+        scrubber.set_progress(1.5);
+        assert_eq!(scrubber.value(), 100.0);
+
+        // slow release, cancels
+        scrubber.set_progress(0.25);
+        assert_eq!(scrubber.release(0.0), Release::Cancel);
+        scrubber.clock_mut().0 = Duration::from_millis(100);
+        assert_eq!(scrubber.update(), Status::Animating);
+        assert!((scrubber.value() - 15.0).abs() < 1e-3);
+        scrubber.clock_mut().0 = Duration::from_millis(300);
+        assert_eq!(scrubber.update(), Status::Completed);
+        assert_eq!(scrubber.value(), 0.0);
+
+        // slow release, completes
+        scrubber.set_progress(0.75);
+        assert_eq!(scrubber.release(0.0), Release::Complete);
+        scrubber.clock_mut().0 = Duration::from_millis(600);
+        assert_eq!(scrubber.update(), Status::Completed);
+        assert_eq!(scrubber.value(), 100.0);
+    }
+
+    #[test]
+    fn test_scrubber_fling() {
+        let mut scrubber = scrubber();
+        scrubber.set_progress(0.25);
+        // flung forward, faster than normal
+        assert_eq!(scrubber.release(5.0), Release::Complete);
+        scrubber.clock_mut().0 = Duration::from_millis(100);
+        assert_eq!(scrubber.update(), Status::Animating);
+        assert!((scrubber.value() - 75.0).abs() < 1e-3);
+
+        // interrupted, then flung backward
+        scrubber.set_progress(0.8);
+        assert_eq!(scrubber.status(), Status::Idle);
+        assert_eq!(scrubber.release(-2.0), Release::Cancel);
+        scrubber.clock_mut().0 = Duration::from_millis(500);
+        assert_eq!(scrubber.update(), Status::Completed);
+        assert_eq!(scrubber.value(), 0.0);
+    }
+}
@@ -0,0 +1,181 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+#[cfg(feature = "std")]
+use super::clock::DefaultClock;
+use super::{clock::Clock, Timeline};
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// drives [`Timeline`]s by one master elapsed time, so that they never drift relative to each
+/// other, e.g. lip-synced choreography of multiple properties.
+///
+/// the timelines can be of different types; they follow the master time through
+/// [`SyncClock`]s, which only move on [`SyncGroup::update()`]. so all of them observe the very
+/// same instant, no matter when their own `update()` is called.
+///
+/// ## Example
+/// ```rust
+/// use anim::{Options, SyncClock, SyncGroup, Timeline};
+///
+/// let mut group: SyncGroup = SyncGroup::new();
+/// let mut opacity: Timeline<f32, SyncClock> = Options::new(0.0, 1.0).build().into();
+/// let mut offset: Timeline<(f32, f32), SyncClock> =
+///     Options::new((0.0, 0.0), (10.0, 20.0)).build().into();
+/// group.attach(&mut opacity);
+/// group.attach(&mut offset);
+/// opacity.begin();
+/// offset.begin();
+///
+/// // on every frame
+/// group.update();
+/// opacity.update();
+/// offset.update();
+/// ```
+pub struct SyncGroup<
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
+    clock: C,
+    start: C::Time,
+    /// master elapsed time in nanoseconds
+    time: Arc<AtomicU64>,
+}
+
+impl<C: Clock> SyncGroup<C> {
+    /// the master time starts from now
+    #[inline]
+    pub fn new() -> Self {
+        let clock = C::default();
+        Self {
+            start: clock.now(),
+            clock,
+            time: Default::default(),
+        }
+    }
+
+    /// associated clock
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// associated clock
+    pub fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// a clock following the master time of current group
+    #[inline]
+    pub fn sync_clock(&self) -> SyncClock {
+        SyncClock(self.time.clone())
+    }
+
+    /// let the timeline follow the master time of current group from now on
+    #[inline]
+    pub fn attach<T>(&self, timeline: &mut Timeline<T, SyncClock>) {
+        *timeline.clock_mut() = self.sync_clock();
+    }
+
+    /// master elapsed time observed by the previous update
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.time.load(Ordering::Acquire))
+    }
+
+    /// update the master clock, then publish its elapsed time to all the attached timelines;
+    /// call it before updating them
+    #[inline]
+    pub fn update(&mut self) -> Duration {
+        self.clock.tick();
+        let elapsed = self.clock.elapsed(&self.start);
+        let nanos = elapsed.as_nanos().min(u64::MAX as u128) as u64;
+        // never goes backwards, see [`Clock::elapsed()`]
+        self.time.fetch_max(nanos, Ordering::AcqRel);
+        self.elapsed()
+    }
+}
+
+impl<C: Clock> Default for SyncGroup<C> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clock> fmt::Debug for SyncGroup<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncGroup")
+            .field("elapsed", &self.elapsed())
+            .finish()
+    }
+}
+
+/// [`Clock`] following the master time of a [`SyncGroup`], see [`SyncGroup::attach()`];
+/// it never moves by itself, a default one stays at zero.
+#[derive(Debug, Clone, Default)]
+pub struct SyncClock(Arc<AtomicU64>);
+
+impl Clock for SyncClock {
+    type Time = Duration;
+
+    #[inline]
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.0.load(Ordering::Acquire))
+    }
+
+    #[inline]
+    fn checked_elapsed(now: &Duration, since: &Duration) -> Option<Duration> {
+        now.checked_sub(*since)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::{clock::TestClock, easing, timeline::Status, Options};
+
+    #[test]
+    fn test_sync_group() {
+        let at = Duration::from_millis;
+        let mut group: SyncGroup<TestClock> = SyncGroup::new();
+        let mut first: Timeline<f32, SyncClock> = Options::new(0.0, 1.0)
+            .easing(easing::linear())
+            .duration(at(1000))
+            .build()
+            .into();
+        let mut second: Timeline<f64, SyncClock> = Options::new(0.0, 2.0)
+            .easing(easing::linear())
+            .duration(at(1000))
+            .build()
+            .into();
+        group.attach(&mut first);
+        group.attach(&mut second);
+        first.begin();
+        second.begin();
+
+        group.clock_mut().0 = at(500);
+        assert_eq!(group.update(), at(500));
+        // the master clock moves on, but the timelines still observe the published time
+        group.clock_mut().0 = at(700);
+        assert_eq!(first.update(), Status::Animating);
+        assert_eq!(second.update(), Status::Animating);
+        assert_eq!(first.value(), 0.5);
+        assert_eq!(second.value(), 1.0);
+
+        group.clock_mut().0 = at(1000);
+        group.update();
+        assert_eq!(first.update(), Status::Completed);
+        assert_eq!(second.update(), Status::Completed);
+
+        // never goes backwards
+        group.clock_mut().0 = at(100);
+        assert_eq!(group.update(), at(1000));
+    }
+}
@@ -5,14 +5,23 @@
 // License: MIT
 
 use super::{
-    animation::{Animation, BaseAnimation, Boxed, IsFinished},
+    animation::{AnimNode, Animation, BaseAnimation, Boxed, IsFinished},
     clock::*,
-    Animatable, Options, DURATION_ZERO,
+    easing,
+    event::{AnimEvent, Iterations, Listeners},
+    physics::affine,
+    Animatable, MaybeSend, Options, DURATION_ZERO,
 };
-use std::{
-    fmt::Debug,
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Debug},
     sync::atomic::AtomicUsize,
-    time::{Duration, Instant},
+    time::Duration,
+};
+#[cfg(feature = "std")]
+use std::{
+    sync::mpsc::{channel, Receiver},
+    time::Instant,
 };
 /// unique id
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -54,6 +63,62 @@ impl Status {
     }
 }
 
+/// what happens after a long gap between updates, e.g. a janky frame or the app was suspended;
+/// default [`GapPolicy::CatchUp`]. the gap is measured in the time of the animation
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GapPolicy {
+    /// jump to where it should be, as if nothing was missed
+    CatchUp,
+    /// advance at most the specified time per update, so that it never teleports
+    ClampStep(Duration),
+    /// advance nothing for a gap longer than the specified time, as if paused during the gap
+    PauseOnGap(Duration),
+}
+
+impl Default for GapPolicy {
+    #[inline]
+    fn default() -> Self {
+        GapPolicy::CatchUp
+    }
+}
+
+impl GapPolicy {
+    /// how far to advance for `step` since the previous update
+    #[inline]
+    fn apply(self, step: Duration) -> Duration {
+        match self {
+            GapPolicy::CatchUp => step,
+            GapPolicy::ClampStep(max) => step.min(max),
+            GapPolicy::PauseOnGap(threshold) if step > threshold => DURATION_ZERO,
+            GapPolicy::PauseOnGap(_) => step,
+        }
+    }
+}
+
+/// state of a [`Timeline`] at some point, see [`Timeline::debug_snapshot()`]
+#[derive(Debug, Clone)]
+pub struct TimelineSnapshot {
+    /// the unique id of the timeline
+    pub id: TimelineId,
+    /// status of the timeline
+    pub status: Status,
+    /// elapsed time of the animation, `None` if it's not started
+    pub elapsed: Option<Duration>,
+    /// the animation
+    pub animation: AnimNode,
+}
+
+impl fmt::Display for TimelineSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "timeline {:?}: {:?}, elapsed {:?}",
+            self.id.0, self.status, self.elapsed
+        )?;
+        write!(f, "{}", self.animation)
+    }
+}
+
 /// animation state
 #[derive(Debug)]
 enum State<Time> {
@@ -75,12 +140,59 @@ enum State<Time> {
 static ID_GEN: AtomicUsize = AtomicUsize::new(1);
 
 /// control your animation
+///
+/// the time of its [`Clock`] might go backwards, e.g. adjusted custom clocks, see
+/// [`Clock::checked_elapsed()`]; in that case, no time passes until the clock catches up.
 #[derive(Debug)]
-pub struct Timeline<T, C: Clock = DefaultClock> {
+pub struct Timeline<
+    T,
+    #[cfg(feature = "std")] C: Clock = DefaultClock,
+    #[cfg(not(feature = "std"))] C: Clock,
+> {
     id: usize,
     animation: Boxed<T>, // it's not easy to use if not boxed
     state: State<C::Time>,
     clock: C,
+    /// value observed by [`Timeline::changed_since_last_update()`]
+    last_value: Option<T>,
+    /// sorted by time
+    markers: Vec<(Duration, String)>,
+    /// elapsed time observed by previous update
+    observed: Duration,
+    /// elapsed time observed by the update before, see [`Timeline::value_interpolated()`]
+    previous: Duration,
+    iterations: Option<Iterations>,
+    listeners: Listeners,
+    time_scale: f32,
+    suspended: bool,
+    /// driven by [`test_support::advance()`] instead of `clock`
+    manual: bool,
+    /// see [`Timeline::set_hold_first_frame()`]
+    hold_first_frame: bool,
+    /// the time is frozen on the first frame
+    holding: bool,
+    /// see [`Timeline::with_timeout()`]
+    timeout: Option<Duration>,
+    gap_policy: GapPolicy,
+}
+
+/// time passed on `clock` since `since`, scaled by `time_scale`; none passes while suspended
+#[inline]
+fn scale_elapsed<C: Clock>(
+    clock: &C,
+    since: &C::Time,
+    time_scale: f32,
+    suspended: bool,
+) -> Duration {
+    if suspended {
+        return DURATION_ZERO;
+    }
+    let duration = clock.elapsed(since);
+    if time_scale == 1.0 {
+        duration
+    } else {
+        duration.mul_f32(time_scale)
+    }
 }
 
 impl<T, C: Clock> Timeline<T, C> {
@@ -88,13 +200,26 @@ impl<T, C: Clock> Timeline<T, C> {
     #[inline]
     pub fn new<A>(animation: A) -> Self
     where
-        A: Animation<Item = T> + 'static,
+        A: Animation<Item = T> + MaybeSend + 'static,
     {
         Self {
-            id: ID_GEN.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            id: ID_GEN.fetch_add(1, core::sync::atomic::Ordering::Relaxed),
             animation: Boxed::new(animation),
             state: State::Idle,
             clock: Default::default(),
+            last_value: None,
+            markers: Vec::new(),
+            observed: DURATION_ZERO,
+            previous: DURATION_ZERO,
+            iterations: None,
+            listeners: Default::default(),
+            time_scale: 1.0,
+            suspended: false,
+            manual: false,
+            hold_first_frame: false,
+            holding: false,
+            timeout: None,
+            gap_policy: GapPolicy::CatchUp,
         }
     }
 
@@ -108,33 +233,227 @@ impl<T, C: Clock> Timeline<T, C> {
         &mut self.clock
     }
 
+    /// how fast the time passes for the animation, i.e. the playback rate, default 1.0
+    #[doc(alias = "speed")]
+    #[inline]
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// change how fast the time passes from now on, e.g. 0.1 for slow motion, 0 to freeze;
+    /// the progress so far is kept. unlike [`crate::Animation::scale()`], the animation is not
+    /// rebuilt, so it works on a running timeline. panics if `scale` is negative or not finite
+    #[doc(alias = "set_speed")]
+    #[inline]
+    pub fn set_time_scale(&mut self, scale: f32) {
+        assert!(
+            scale >= 0.0 && scale.is_finite(),
+            "invalid time scale: {}",
+            scale
+        );
+        self.anchor();
+        self.time_scale = scale;
+    }
+
+    /// alias of [`Timeline::time_scale()`]
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// alias of [`Timeline::set_time_scale()`]
+    #[inline]
+    pub fn set_speed(&mut self, speed: f32) {
+        self.set_time_scale(speed)
+    }
+
+    /// is the time frozen? see [`Timeline::set_suspended()`]
+    #[inline]
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// freeze the time while the app is hidden or unfocused, e.g. for decorative animations;
+    /// unlike [`Timeline::pause()`], the status is kept. once unsuspended, it continues where
+    /// it was without a time jump
+    #[inline]
+    pub fn set_suspended(&mut self, suspended: bool) {
+        self.anchor();
+        self.suspended = suspended;
+    }
+
+    /// does the first frame hold? see [`Timeline::set_hold_first_frame()`]
+    #[inline]
+    pub fn holds_first_frame(&self) -> bool {
+        self.hold_first_frame
+    }
+
+    /// once begun, hold the first frame: the time does not pass until the first
+    /// [`Timeline::update()`], which reports the value at the very beginning, and it counts from
+    /// then on; so entrances never start partway in, however late the first frame comes.
+    /// it applies on [`Timeline::begin()`]
+    #[inline]
+    pub fn set_hold_first_frame(&mut self, hold: bool) {
+        self.hold_first_frame = hold;
+    }
+
+    /// stop the animation once it has played for `timeout`, e.g. a [`crate::Animation::forever()`]
+    /// animation that's no longer visible, so it does not keep the redraw loop alive;
+    /// [`Timeline::update()`] reports [`Status::Completed`] and emits [`AnimEvent::TimedOut`]
+    #[inline]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// max time the animation plays for, see [`Timeline::with_timeout()`]
+    #[inline]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// change the max time the animation plays for, `None` for no limit;
+    /// see [`Timeline::with_timeout()`]
+    #[inline]
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// what happens after a long gap between updates, see [`GapPolicy`]
+    #[inline]
+    pub fn gap_policy(&self) -> GapPolicy {
+        self.gap_policy
+    }
+
+    /// change what happens after a long gap between updates; it applies on
+    /// [`Timeline::update()`], see [`GapPolicy`]
+    #[inline]
+    pub fn set_gap_policy(&mut self, policy: GapPolicy) {
+        self.gap_policy = policy;
+    }
+
+    /// accumulate the elapsed time so far, then count from now on
+    #[inline]
+    fn anchor(&mut self) {
+        if let State::Animating { time, elapsed } = &mut self.state {
+            let duration = scale_elapsed(
+                &self.clock,
+                time,
+                self.time_scale,
+                self.suspended || self.manual || self.holding,
+            );
+            *elapsed = Some(elapsed.unwrap_or_default() + duration);
+            *time = self.clock.now();
+        }
+    }
+
     /// the unique id of your animation
     #[inline]
     pub fn id(&self) -> TimelineId {
         TimelineId(self.id)
     }
 
+    /// emit [`AnimEvent::MarkerReached`] once the timeline passes `at`
+    #[inline]
+    pub fn add_marker(&mut self, name: impl Into<String>, at: Duration) {
+        let idx = self.markers.partition_point(|(time, _)| *time <= at);
+        self.markers.insert(idx, (at, name.into()));
+    }
+
+    /// register a callback of [`AnimEvent`]s, which is called as they occur,
+    /// mostly during [`Timeline::update()`]
+    #[inline]
+    pub fn on_event(&mut self, listener: impl FnMut(&AnimEvent) + MaybeSend + 'static) {
+        self.listeners.push(listener);
+    }
+
+    /// call `f` once the timeline begins, see [`AnimEvent::Started`]
+    #[inline]
+    pub fn on_begin(&mut self, mut f: impl FnMut() + MaybeSend + 'static) {
+        self.on_event(move |event| {
+            if let AnimEvent::Started = event {
+                f()
+            }
+        });
+    }
+
+    /// call `f` once the timeline is paused, see [`AnimEvent::Paused`]
+    #[inline]
+    pub fn on_pause(&mut self, mut f: impl FnMut() + MaybeSend + 'static) {
+        self.on_event(move |event| {
+            if let AnimEvent::Paused = event {
+                f()
+            }
+        });
+    }
+
+    /// call `f` with the number of completed iterations once an iteration is completed and
+    /// the next one begins, see [`AnimEvent::Repeated`]; unless the animation is built from
+    /// [`Options`], iterations are counted from the beginning by [`Timeline::period()`]
+    #[inline]
+    pub fn on_loop_iteration(&mut self, mut f: impl FnMut(usize) + MaybeSend + 'static) {
+        self.on_event(move |event| {
+            if let AnimEvent::Repeated(count) = event {
+                f(*count)
+            }
+        });
+    }
+
+    /// call `f` once the animation is finished, see [`AnimEvent::Completed`]
+    #[inline]
+    pub fn on_complete(&mut self, mut f: impl FnMut() + MaybeSend + 'static) {
+        self.on_event(move |event| {
+            if let AnimEvent::Completed = event {
+                f()
+            }
+        });
+    }
+
+    /// receive [`AnimEvent`]s by a channel, see [`Timeline::on_event()`]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn events(&mut self) -> Receiver<AnimEvent> {
+        let (tx, rx) = channel();
+        self.on_event(move |event| {
+            // the receiver might be dropped
+            let _ = tx.send(event.clone());
+        });
+        rx
+    }
+
     /// start your animation; if it's not completed yet, restart it
     #[inline]
     pub fn begin(&mut self) {
+        trace_event!(debug, id = self.id, "timeline begin");
         let now = self.clock.now();
         self.state = State::Animating {
             time: now,
             elapsed: None,
-        }
+        };
+        self.observed = DURATION_ZERO;
+        self.previous = DURATION_ZERO;
+        self.holding = self.hold_first_frame;
+        self.listeners.emit(AnimEvent::Started);
     }
 
     /// stop your animation
     #[inline]
     pub fn stop(&mut self) {
+        trace_event!(debug, id = self.id, "timeline stop");
         match &mut self.state {
             State::Idle | State::Completed { .. } => {}
             State::Animating { time, elapsed } => {
-                let duration = self.clock.now() - time.clone();
+                let duration = scale_elapsed(
+                    &self.clock,
+                    time,
+                    self.time_scale,
+                    self.suspended || self.manual || self.holding,
+                );
                 let elapsed = elapsed.unwrap_or(DURATION_ZERO) + duration;
                 self.state = State::Completed {
                     elapsed: Some(elapsed),
-                }
+                };
+                self.holding = false;
             }
             State::Paused { elapsed } => {
                 self.state = State::Completed {
@@ -147,18 +466,27 @@ impl<T, C: Clock> Timeline<T, C> {
     /// pause your animation only if it's animating
     #[inline]
     pub fn pause(&mut self) {
+        trace_event!(debug, id = self.id, "timeline pause");
         if let State::Animating { time, elapsed } = &mut self.state {
-            let duration = self.clock.now() - time.clone();
+            let duration = scale_elapsed(
+                &self.clock,
+                time,
+                self.time_scale,
+                self.suspended || self.manual || self.holding,
+            );
             let elapsed = elapsed.unwrap_or_default() + duration;
             self.state = State::Paused {
                 elapsed: Some(elapsed),
             };
+            self.holding = false;
+            self.listeners.emit(AnimEvent::Paused);
         }
     }
 
     /// continue your animation if it was paused, otherwise start new animation
     #[inline]
     pub fn resume(&mut self) {
+        trace_event!(debug, id = self.id, "timeline resume");
         match self.state {
             State::Paused { elapsed } => {
                 self.state = State::Animating {
@@ -194,7 +522,12 @@ impl<T, C: Clock> Timeline<T, C> {
         match &self.state {
             State::Idle => None,
             State::Animating { time, elapsed } => {
-                let duration = self.clock.now() - time.clone();
+                let duration = scale_elapsed(
+                    &self.clock,
+                    time,
+                    self.time_scale,
+                    self.suspended || self.manual || self.holding,
+                );
                 if let Some(elapsed) = elapsed {
                     Some(*elapsed + duration)
                 } else {
@@ -213,16 +546,66 @@ impl<T, C: Clock> Timeline<T, C> {
         self.animation.animate(duration)
     }
 
+    /// duration of one loop of your animation, see [`crate::BaseAnimation::period()`]
+    #[inline]
+    pub fn period(&self) -> Option<Duration> {
+        self.animation.period()
+    }
+
+    /// status, elapsed time and the structure of the animation, e.g. for debug overlays or logs
+    #[inline]
+    pub fn debug_snapshot(&self) -> TimelineSnapshot {
+        TimelineSnapshot {
+            id: self.id(),
+            status: self.status(),
+            elapsed: self.elapsed(),
+            animation: self.animation.describe(),
+        }
+    }
+
     /// update the status of the timeline
     #[inline]
     pub fn update(&mut self) -> Status {
+        self.clock.tick();
         match &mut self.state {
             State::Idle => Status::Idle,
             State::Animating { time, elapsed } => {
-                let now = self.clock.now();
+                if self.holding {
+                    // the first frame is observed, counts from now on
+                    *elapsed = Some(elapsed.unwrap_or_default());
+                    *time = self.clock.now();
+                    self.holding = false;
+                }
                 // accumulated time
-                let duration = elapsed.unwrap_or_default() + (now - time.clone());
-                if self.animation.is_finished(duration) {
+                let mut duration = elapsed.unwrap_or_default()
+                    + scale_elapsed(
+                        &self.clock,
+                        time,
+                        self.time_scale,
+                        self.suspended || self.manual || self.holding,
+                    );
+                if let Some(step) = duration.checked_sub(self.observed) {
+                    let advance = self.gap_policy.apply(step);
+                    if advance < step {
+                        trace_event!(debug, id = self.id, gap = ?step, "timeline gap");
+                        // count from now on, without the gap
+                        duration = self.observed + advance;
+                        *elapsed = Some(duration);
+                        *time = self.clock.now();
+                    }
+                }
+                let finished = self.animation.is_finished(duration);
+                self.notify(duration, finished);
+                if !finished && matches!(self.timeout, Some(timeout) if duration >= timeout) {
+                    trace_event!(debug, id = self.id, elapsed = ?duration, "timeline timed out");
+                    self.state = State::Completed {
+                        elapsed: Some(duration),
+                    };
+                    self.listeners.emit(AnimEvent::TimedOut);
+                    return Status::Completed;
+                }
+                if finished {
+                    trace_event!(debug, id = self.id, elapsed = ?duration, "timeline completed");
                     self.state = State::Completed {
                         elapsed: Some(duration),
                     };
@@ -235,7 +618,77 @@ impl<T, C: Clock> Timeline<T, C> {
         }
     }
 
+    /// emit events between previous update and `elapsed`
+    fn notify(&mut self, elapsed: Duration, finished: bool) {
+        let observed = core::mem::replace(&mut self.observed, elapsed);
+        self.previous = observed;
+        let Self {
+            animation,
+            markers,
+            iterations,
+            listeners,
+            ..
+        } = self;
+        if listeners.is_empty() || elapsed < observed {
+            return;
+        }
+        for (at, name) in markers.iter() {
+            if *at >= observed && (*at < elapsed || finished && *at <= elapsed) {
+                listeners.emit(AnimEvent::MarkerReached(name.clone()));
+            }
+        }
+        if finished {
+            listeners.emit(AnimEvent::Completed);
+        } else if let Some(iterations) = iterations.or_else(|| Iterations::of(animation)) {
+            let count = iterations.completed(elapsed);
+            if count > iterations.completed(observed) {
+                listeners.emit(AnimEvent::Repeated(count));
+            }
+        }
+    }
+
+    /// replace your animation, keeping the status and the elapsed time, e.g. a hot-reloaded or
+    /// re-parameterized animation continues from the same progress; see
+    /// [`Timeline::animate_to()`] to restart instead.
+    ///
+    /// a completed timeline stays completed, call [`Timeline::begin()`] to play it again
+    #[inline]
+    pub fn swap_animation<A>(&mut self, animation: A)
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+    {
+        trace_event!(debug, id = self.id, "timeline animation swapped");
+        self.animation.replace(animation);
+        self.iterations = None;
+    }
+
+    /// play `animation` instead from the idle state, keeping the id, the clock and the
+    /// allocations, see [`crate::TimelinePool`]; markers, listeners, the time scale,
+    /// suspension, the first frame hold, the timeout and the gap policy are reset
+    #[inline]
+    pub(crate) fn recycle<A>(&mut self, animation: A)
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+    {
+        self.animation.replace(animation);
+        self.state = State::Idle;
+        self.last_value = None;
+        self.markers.clear();
+        self.observed = DURATION_ZERO;
+        self.previous = DURATION_ZERO;
+        self.iterations = None;
+        self.listeners.clear();
+        self.time_scale = 1.0;
+        self.suspended = false;
+        self.manual = false;
+        self.hold_first_frame = false;
+        self.holding = false;
+        self.timeout = None;
+        self.gap_policy = GapPolicy::CatchUp;
+    }
+
     /// update the timeline
+    #[cfg(feature = "std")]
     #[deprecated = "will be removed"]
     #[inline]
     pub fn update_with_time(&mut self, _now: Instant) -> Status {
@@ -243,16 +696,692 @@ impl<T, C: Clock> Timeline<T, C> {
     }
 }
 
-impl<T: Animation + 'static> From<T> for Timeline<T::Item> {
+impl<T: PartialEq, C: Clock> Timeline<T, C> {
+    /// is the current value different from the one observed by the previous call?
+    /// always `true` for the first call.
+    ///
+    /// call it after [`Timeline::update()`] to skip redrawing if nothing changed
+    #[inline]
+    pub fn changed_since_last_update(&mut self) -> bool {
+        let value = self.value();
+        if self.last_value.as_ref() == Some(&value) {
+            return false;
+        }
+        self.last_value = Some(value);
+        true
+    }
+}
+
+impl<T: Animatable + Default, C: Clock> Timeline<T, C> {
+    /// current velocity per second of real time, taking the time scale into account, relative to
+    /// `T::default()`; it's estimated over one millisecond of the animation.
+    /// zero unless animating, or while no time passes, e.g. suspended.
+    ///
+    /// it's meant for float based values, e.g. `f32`, `f64` and tuples of them;
+    /// integer based values are rounded, so the result is meaningless for them.
+    ///
+    /// interrupt the animation and continue the motion with the same velocity, e.g. by
+    /// [`crate::physics::Spring::set_velocity()`] or a gesture, without a visible discontinuity
+    #[inline]
+    pub fn velocity(&self) -> T {
+        let elapsed = match (&self.state, self.elapsed()) {
+            (State::Animating { .. }, Some(elapsed)) => elapsed,
+            _ => return T::default(),
+        };
+        if self.suspended || self.time_scale == 0.0 {
+            return T::default();
+        }
+        let step = Duration::from_millis(1);
+        let (from, to) = match elapsed.checked_sub(step) {
+            Some(from) => (from, elapsed),
+            None => (elapsed, elapsed + step),
+        };
+        let (from, to) = (self.animation.animate(from), self.animation.animate(to));
+        // the step takes `step / time_scale` of real time
+        let scale = self.time_scale as f64 / step.as_secs_f64();
+        affine((&to, scale), (&from, -scale), (&T::default(), 1.0))
+    }
+}
+
+impl<T: Animatable, C: Clock> Timeline<T, C> {
+    /// blend the values of the last two updates by `alpha` between 0-1, for render frames
+    /// between fixed-timestep updates, e.g. logic at 30 Hz but rendering at 144 Hz;
+    /// `alpha` is how far the render frame is into the current step
+    #[inline]
+    pub fn value_interpolated(&self, alpha: f32) -> T {
+        let alpha = (alpha as f64).clamp(0.0, 1.0);
+        let previous = self.animation.animate(self.previous);
+        let current = self.animation.animate(self.observed);
+        previous.animate(&current, alpha)
+    }
+}
+
+impl<T: Animatable + MaybeSend + 'static, C: Clock> Timeline<T, C> {
+    /// play the animation of `opt` instead and restart; with [`Options::from_current()`], it
+    /// starts from the present value, e.g. to retarget an animation halfway without a jump.
+    ///
+    /// the id, the clock, markers, listeners, the time scale, suspension and the gap policy
+    /// are kept
+    #[inline]
+    pub fn animate_to<F>(&mut self, mut opt: Options<T, F>)
+    where
+        F: easing::Function + Clone + MaybeSend + 'static,
+    {
+        if opt.from_current {
+            opt.from = self.value();
+        }
+        self.iterations = iterations(&opt);
+        self.animation.replace(opt.build());
+        self.begin();
+    }
+}
+
+impl<T: Animation + MaybeSend + 'static, C: Clock> From<T> for Timeline<T::Item, C> {
     #[inline]
     fn from(src: T) -> Self {
         Timeline::new(src)
     }
 }
 
-impl<T: Animatable + 'static> From<Options<T>> for Timeline<T> {
+impl<T, F, C> From<Options<T, F>> for Timeline<T, C>
+where
+    T: Animatable + MaybeSend + 'static,
+    F: easing::Function + Clone + MaybeSend + 'static,
+    C: Clock,
+{
     #[inline]
-    fn from(opt: Options<T>) -> Self {
-        Timeline::new(opt.build())
+    fn from(opt: Options<T, F>) -> Self {
+        let iterations = iterations(&opt);
+        let mut timeline = Timeline::new(opt.build());
+        timeline.iterations = iterations;
+        timeline
+    }
+}
+
+/// iteration boundaries of the animation built from `opt`
+#[inline]
+fn iterations<T: Animatable, F: easing::Function>(opt: &Options<T, F>) -> Option<Iterations> {
+    let period = opt.simple_duration();
+    if period > DURATION_ZERO {
+        Some(Iterations {
+            delay: opt.delay.unwrap_or_default(),
+            skip: opt.skip.unwrap_or_default(),
+            period,
+        })
+    } else {
+        None
+    }
+}
+
+/// drive timelines deterministically in tests and doc examples, even with the default clock;
+/// turn on `testing` feature to use it
+///
+/// ```
+/// use anim::{easing, timeline::{test_support::advance, Status}, Options, Timeline};
+/// use std::time::Duration;
+///
+/// let mut timeline: Timeline<f64> = Options::new(0.0, 1.0)
+///     .duration(Duration::from_secs(1))
+///     .easing(easing::linear())
+///     .into();
+/// timeline.begin();
+/// assert_eq!(advance(&mut timeline, Duration::from_millis(500)), Status::Animating);
+/// assert_eq!(timeline.value(), 0.5);
+/// assert_eq!(advance(&mut timeline, Duration::from_millis(500)), Status::Completed);
+/// assert_eq!(timeline.value(), 1.0);
+/// ```
+#[cfg(any(test, feature = "testing"))]
+pub mod test_support {
+    use super::*;
+
+    /// move `timeline` forward by `dt`, then update it.
+    ///
+    /// from then on, the timeline ignores its clock, so only [`advance()`] moves it;
+    /// its time scale applies, and no time passes while it's suspended.
+    pub fn advance<T, C: Clock>(timeline: &mut Timeline<T, C>, dt: Duration) -> Status {
+        timeline.manual = true;
+        timeline.anchor();
+        if let State::Animating { elapsed, .. } = &mut timeline.state {
+            if !timeline.suspended {
+                let dt = if timeline.time_scale == 1.0 {
+                    dt
+                } else {
+                    dt.mul_f32(timeline.time_scale)
+                };
+                *elapsed = Some(elapsed.unwrap_or_default() + dt);
+            }
+        }
+        timeline.update()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::clock::{FixedClock, TestClock};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_advance() {
+        use super::test_support::advance;
+
+        let mut timeline: Timeline<f32> = Options::new(0.0, 1.0)
+            .duration(Duration::from_millis(1000))
+            .easing(easing::linear())
+            .into();
+        timeline.begin();
+        assert_eq!(
+            advance(&mut timeline, Duration::from_millis(250)),
+            Status::Animating
+        );
+        assert_eq!(timeline.value(), 0.25);
+
+        timeline.set_time_scale(2.0);
+        assert_eq!(
+            advance(&mut timeline, Duration::from_millis(250)),
+            Status::Animating
+        );
+        assert_eq!(timeline.value(), 0.75);
+
+        timeline.set_suspended(true);
+        assert_eq!(
+            advance(&mut timeline, Duration::from_millis(250)),
+            Status::Animating
+        );
+        assert_eq!(timeline.value(), 0.75);
+
+        timeline.set_suspended(false);
+        assert_eq!(
+            advance(&mut timeline, Duration::from_millis(250)),
+            Status::Completed
+        );
+        assert_eq!(timeline.value(), 1.0);
+    }
+
+    #[test]
+    fn test_clock_backwards() {
+        let mut timeline: Timeline<f32, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_millis(1000))
+            .easing(easing::linear())
+            .into();
+        timeline.clock_mut().0 = Duration::from_millis(500);
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(750);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.25);
+
+        // time goes backwards
+        timeline.clock_mut().0 = Duration::from_millis(100);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.0);
+
+        timeline.clock_mut().0 = Duration::from_millis(1500);
+        assert_eq!(timeline.update(), Status::Completed);
+        assert_eq!(timeline.value(), 1.0);
+    }
+
+    #[test]
+    fn test_fixed_clock() {
+        let run = || {
+            let mut timeline: Timeline<f32, FixedClock> = Options::new(0.0, 1.0)
+                .duration(Duration::from_millis(1000))
+                .easing(easing::linear())
+                .into();
+            *timeline.clock_mut() = FixedClock::new(4.0);
+            timeline.begin();
+            let mut values = vec![timeline.value()];
+            while timeline.update() == Status::Animating {
+                values.push(timeline.value());
+            }
+            values.push(timeline.value());
+            values
+        };
+        let values = run();
+        assert_eq!(values, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(values, run());
+    }
+
+    #[test]
+    fn test_changed_since_last_update() {
+        let mut timeline: Timeline<f32, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_millis(1000))
+            .easing(easing::linear())
+            .into();
+        assert!(timeline.changed_since_last_update());
+        assert!(!timeline.changed_since_last_update());
+
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(500);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert!(timeline.changed_since_last_update());
+        assert!(!timeline.changed_since_last_update());
+
+        timeline.pause();
+        timeline.clock_mut().0 = Duration::from_millis(800);
+        assert_eq!(timeline.update(), Status::Paused);
+        assert!(!timeline.changed_since_last_update());
+    }
+
+    #[test]
+    fn test_events() {
+        let mut timeline: Timeline<f32, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_millis(1000))
+            .times(2.0)
+            .into();
+        timeline.add_marker("half", Duration::from_millis(500));
+        timeline.add_marker("end", Duration::from_millis(2000));
+        let events = timeline.events();
+        let received = || events.try_iter().collect::<Vec<_>>();
+
+        timeline.begin();
+        assert_eq!(received(), vec![AnimEvent::Started]);
+        timeline.clock_mut().0 = Duration::from_millis(400);
+        timeline.update();
+        assert_eq!(received(), vec![]);
+        timeline.clock_mut().0 = Duration::from_millis(1200);
+        timeline.update();
+        assert_eq!(
+            received(),
+            vec![
+                AnimEvent::MarkerReached("half".into()),
+                AnimEvent::Repeated(1)
+            ]
+        );
+        timeline.clock_mut().0 = Duration::from_millis(2500);
+        assert_eq!(timeline.update(), Status::Completed);
+        assert_eq!(
+            received(),
+            vec![AnimEvent::MarkerReached("end".into()), AnimEvent::Completed]
+        );
+        timeline.update();
+        assert_eq!(received(), vec![]);
+    }
+
+    #[test]
+    fn test_event_callbacks() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut timeline: Timeline<f32, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_millis(1000))
+            .times(3.0)
+            .into();
+        let counters: Arc<[AtomicUsize; 4]> = Arc::new(Default::default());
+        let get = |i: usize| counters[i].load(Ordering::SeqCst);
+        let c = counters.clone();
+        timeline.on_begin(move || {
+            c[0].fetch_add(1, Ordering::SeqCst);
+        });
+        let c = counters.clone();
+        timeline.on_pause(move || {
+            c[1].fetch_add(1, Ordering::SeqCst);
+        });
+        let c = counters.clone();
+        timeline.on_loop_iteration(move |count| {
+            c[2].store(count, Ordering::SeqCst);
+        });
+        let c = counters.clone();
+        timeline.on_complete(move || {
+            c[3].fetch_add(1, Ordering::SeqCst);
+        });
+
+        timeline.begin();
+        assert_eq!(get(0), 1);
+        timeline.clock_mut().0 = Duration::from_millis(1500);
+        timeline.update();
+        assert_eq!(get(2), 1);
+        timeline.pause();
+        assert_eq!(get(1), 1);
+        timeline.resume();
+        timeline.clock_mut().0 = Duration::from_millis(2500);
+        timeline.update();
+        assert_eq!(get(2), 2);
+        assert_eq!(get(3), 0);
+        timeline.clock_mut().0 = Duration::from_millis(3500);
+        assert_eq!(timeline.update(), Status::Completed);
+        assert_eq!(get(3), 1);
+        // stopped timelines are not paused
+        timeline.pause();
+        assert_eq!((get(0), get(1)), (1, 1));
+    }
+
+    #[test]
+    fn test_repeated_by_period() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let opt = Options::new(0.0, 1.0).duration(Duration::from_millis(1000));
+        let mut timeline: Timeline<f32, TestClock> = Timeline::new(opt.clone().build().times(3.0));
+        let iterations = Arc::new(AtomicUsize::new(0));
+        let c = iterations.clone();
+        timeline.on_loop_iteration(move |count| c.store(count, Ordering::SeqCst));
+        let events = timeline.events();
+        let received = || events.try_iter().collect::<Vec<_>>();
+
+        timeline.begin();
+        received();
+        timeline.clock_mut().0 = Duration::from_millis(1200);
+        timeline.update();
+        assert_eq!(received(), vec![AnimEvent::Repeated(1)]);
+        assert_eq!(iterations.load(Ordering::SeqCst), 1);
+        timeline.clock_mut().0 = Duration::from_millis(2200);
+        timeline.update();
+        assert_eq!(received(), vec![AnimEvent::Repeated(2)]);
+        assert_eq!(iterations.load(Ordering::SeqCst), 2);
+
+        // swapped animations repeat by their own period
+        timeline.swap_animation(opt.build().forever());
+        timeline.clock_mut().0 = Duration::from_millis(3200);
+        timeline.update();
+        assert_eq!(received(), vec![AnimEvent::Repeated(3)]);
+        assert_eq!(iterations.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_time_scale() {
+        let mut timeline: Timeline<f32, TestClock> = Timeline::new(
+            Options::new(0.0, 1.0)
+                .duration(Duration::from_secs(1))
+                .easing(easing::linear())
+                .build(),
+        );
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(250);
+        timeline.set_time_scale(0.5);
+        assert_eq!(timeline.value(), 0.25);
+        timeline.clock_mut().0 = Duration::from_millis(750);
+        assert_eq!(timeline.value(), 0.5);
+
+        timeline.set_time_scale(0.0);
+        timeline.clock_mut().0 = Duration::from_millis(5000);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.5);
+
+        timeline.set_time_scale(2.0);
+        timeline.clock_mut().0 = Duration::from_millis(5250);
+        assert_eq!(timeline.update(), Status::Completed);
+        assert_eq!(timeline.value(), 1.0);
+
+        timeline.set_speed(0.5);
+        assert_eq!(timeline.speed(), 0.5);
+        assert_eq!(timeline.time_scale(), 0.5);
+    }
+
+    #[test]
+    fn test_suspended() {
+        let mut timeline: Timeline<f32, TestClock> = Timeline::new(
+            Options::new(0.0, 1.0)
+                .duration(Duration::from_secs(1))
+                .easing(easing::linear())
+                .build(),
+        );
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(250);
+        timeline.set_suspended(true);
+        timeline.clock_mut().0 = Duration::from_millis(5000);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert!(timeline.is_suspended());
+        assert_eq!(timeline.value(), 0.25);
+
+        // no time jump
+        timeline.set_suspended(false);
+        timeline.clock_mut().0 = Duration::from_millis(5250);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.5);
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "rayon", feature = "bevy-backend")))]
+    fn test_not_send() {
+        use alloc::rc::Rc;
+        use core::cell::Cell;
+
+        let factor = Rc::new(Cell::new(2.0));
+        let captured = factor.clone();
+        let offset = Rc::new(0.0);
+        let animation = Options::new(0.0, 1.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::linear())
+            .interpolator(move |from: &f64, to: &f64, time: f64| {
+                *offset + from + (to - from) * time
+            })
+            .build()
+            .map(move |v| captured.get() * v);
+        let mut timeline: Timeline<f64, TestClock> = Timeline::new(animation);
+        timeline.on_complete(move || factor.set(0.0));
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(500);
+        timeline.update();
+        assert_eq!(timeline.value(), 1.0);
+    }
+
+    #[test]
+    fn test_hold_first_frame() {
+        let mut timeline: Timeline<f32, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::linear())
+            .into();
+        timeline.set_hold_first_frame(true);
+        assert!(timeline.holds_first_frame());
+        timeline.begin();
+        // a slow first frame
+        timeline.clock_mut().0 = Duration::from_millis(800);
+        assert_eq!(timeline.value(), 0.0);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.0);
+
+        timeline.clock_mut().0 = Duration::from_millis(1050);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.25);
+
+        timeline.clock_mut().0 = Duration::from_millis(1800);
+        assert_eq!(timeline.update(), Status::Completed);
+        assert_eq!(timeline.value(), 1.0);
+    }
+
+    #[test]
+    fn test_hold_first_frame_ends_once_observed() {
+        let mut timeline: Timeline<f32, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::linear())
+            .into();
+        timeline.set_hold_first_frame(true);
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(800);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.0);
+
+        // the time passed since the first frame counts, however it's anchored
+        timeline.clock_mut().0 = Duration::from_millis(1000);
+        timeline.set_time_scale(1.0);
+        timeline.clock_mut().0 = Duration::from_millis(1050);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.25);
+
+        // so does a pause in between
+        timeline.pause();
+        timeline.resume();
+        timeline.clock_mut().0 = Duration::from_millis(1300);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.5);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut timeline: Timeline<f32, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::linear())
+            .forever()
+            .into();
+        timeline = timeline.with_timeout(Duration::from_secs(3));
+        assert_eq!(timeline.timeout(), Some(Duration::from_secs(3)));
+        let events = timeline.events();
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(2500);
+        assert_eq!(timeline.update(), Status::Animating);
+        timeline.clock_mut().0 = Duration::from_millis(3500);
+        assert_eq!(timeline.update(), Status::Completed);
+        assert_eq!(timeline.value(), 0.5);
+        assert_eq!(events.try_iter().last(), Some(AnimEvent::TimedOut));
+
+        timeline.set_timeout(None);
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(9000);
+        assert_eq!(timeline.update(), Status::Animating);
+    }
+
+    #[test]
+    fn test_value_interpolated() {
+        let mut timeline: Timeline<f32, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::linear())
+            .into();
+        timeline.begin();
+        assert_eq!(timeline.value_interpolated(0.5), 0.0);
+        timeline.clock_mut().0 = Duration::from_millis(200);
+        timeline.update();
+        timeline.clock_mut().0 = Duration::from_millis(400);
+        timeline.update();
+        // rendering between the updates
+        timeline.clock_mut().0 = Duration::from_millis(450);
+        assert_eq!(timeline.value_interpolated(0.0), 0.2);
+        assert_eq!(timeline.value_interpolated(0.25), 0.25);
+        assert_eq!(timeline.value_interpolated(1.0), 0.4);
+        assert_eq!(timeline.value_interpolated(2.0), 0.4);
+    }
+
+    #[test]
+    fn test_velocity() {
+        let mut timeline: Timeline<f64, TestClock> = Options::new(0.0, 2.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::linear())
+            .into();
+        assert_eq!(timeline.velocity(), 0.0);
+        timeline.begin();
+        assert!((timeline.velocity() - 2.0).abs() < 1e-6);
+        timeline.clock_mut().0 = Duration::from_millis(500);
+        timeline.update();
+        assert!((timeline.velocity() - 2.0).abs() < 1e-6);
+        timeline.set_time_scale(0.5);
+        assert!((timeline.velocity() - 1.0).abs() < 1e-6);
+        timeline.set_suspended(true);
+        assert_eq!(timeline.velocity(), 0.0);
+        timeline.set_suspended(false);
+        timeline.pause();
+        assert_eq!(timeline.velocity(), 0.0);
+    }
+
+    #[test]
+    fn test_gap_policy() {
+        let at = |ms| Duration::from_millis(ms);
+        let mut timeline: Timeline<f64, TestClock> = Options::new(0.0, 1.0)
+            .duration(at(1000))
+            .easing(easing::linear())
+            .into();
+        timeline.set_gap_policy(GapPolicy::ClampStep(at(100)));
+        timeline.begin();
+        timeline.clock_mut().0 = at(50);
+        timeline.update();
+        assert_eq!(timeline.value(), 0.05);
+        // a hitch of 450ms
+        timeline.clock_mut().0 = at(500);
+        timeline.update();
+        assert_eq!(timeline.value(), 0.15);
+        timeline.clock_mut().0 = at(550);
+        timeline.update();
+        assert_eq!(timeline.value(), 0.2);
+
+        timeline.set_gap_policy(GapPolicy::PauseOnGap(at(200)));
+        timeline.clock_mut().0 = at(5550);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 0.2);
+        timeline.clock_mut().0 = at(5750);
+        timeline.update();
+        assert_eq!(timeline.value(), 0.4);
+
+        timeline.set_gap_policy(GapPolicy::CatchUp);
+        timeline.clock_mut().0 = at(7000);
+        assert_eq!(timeline.update(), Status::Completed);
+    }
+
+    #[test]
+    fn test_swap_animation() {
+        let mut timeline: Timeline<f64, TestClock> = Options::new(0.0, 1.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::linear())
+            .into();
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(400);
+        timeline.update();
+        assert_eq!(timeline.value(), 0.4);
+
+        timeline.swap_animation(
+            Options::new(0.0, 10.0)
+                .duration(Duration::from_secs(2))
+                .easing(easing::linear())
+                .build(),
+        );
+        assert_eq!(timeline.value(), 2.0);
+        timeline.clock_mut().0 = Duration::from_millis(1500);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 7.5);
+        timeline.clock_mut().0 = Duration::from_millis(2000);
+        assert_eq!(timeline.update(), Status::Completed);
+    }
+
+    #[test]
+    fn test_animate_to() {
+        let mut timeline: Timeline<f64, TestClock> = Options::new(0.0, 2.0)
+            .duration(Duration::from_secs(1))
+            .easing(easing::linear())
+            .into();
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(500);
+        timeline.update();
+        assert_eq!(timeline.value(), 1.0);
+
+        timeline.animate_to(
+            Options::new(0.0, 3.0)
+                .from_current()
+                .duration(Duration::from_secs(1))
+                .easing(easing::linear()),
+        );
+        assert_eq!(timeline.value(), 1.0);
+        timeline.clock_mut().0 = Duration::from_millis(1000);
+        assert_eq!(timeline.update(), Status::Animating);
+        assert_eq!(timeline.value(), 2.0);
+        timeline.clock_mut().0 = Duration::from_millis(1500);
+        assert_eq!(timeline.update(), Status::Completed);
+        assert_eq!(timeline.value(), 3.0);
+
+        // without the placeholder
+        timeline.animate_to(Options::new(0.0, 1.0).easing(easing::linear()));
+        assert_eq!(timeline.value(), 0.0);
+    }
+
+    #[test]
+    fn test_debug_snapshot() {
+        let animation = Options::new(0.0, 1.0)
+            .duration(Duration::from_millis(1000))
+            .build()
+            .delay(Duration::from_millis(500))
+            .chain(Options::new(1.0, 0.0).build().times(2.0));
+        let mut timeline: Timeline<f32, TestClock> = Timeline::new(animation);
+        timeline.begin();
+        timeline.clock_mut().0 = Duration::from_millis(200);
+        let snapshot = timeline.debug_snapshot();
+        assert_eq!(snapshot.status, Status::Animating);
+        assert_eq!(snapshot.elapsed, Some(Duration::from_millis(200)));
+
+        let node = &snapshot.animation;
+        assert_eq!(node.kind, "chain");
+        assert_eq!(node.duration, Some(Duration::from_millis(3500)));
+        assert_eq!(node.children[0].kind, "delay");
+        assert_eq!(node.children[0].children[0].kind, "primitive");
+        assert_eq!(node.children[1].kind, "repeat");
+        assert_eq!(
+            format!("{}", node),
+            "chain (3.5s)\n  delay (1.5s)\n    primitive (1s)\n  repeat (2s)\n    primitive (1s)\n"
+        );
     }
 }
@@ -4,9 +4,159 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
+use core::time::Duration;
+
 /// normalized time must be in 0..1
 #[inline(always)]
 pub fn check_time(time: f64) -> f64 {
     debug_assert!(time >= 0.0 || time <= 1.0);
     time
 }
+
+/// number of samples kept by [`VelocityTracker`]
+const HISTORY: usize = 20;
+
+/// estimates the velocity of a value from its timestamped samples, e.g. pointer positions
+/// of a drag gesture, to hand off to [`crate::physics::Spring`], [`crate::physics::Decay`]
+/// or [`crate::Scrubber`].
+///
+/// it fits a line to the recent samples by least squares, which smooths out the jitter.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{physics::Decay, utils::VelocityTracker};
+///
+/// let mut tracker = VelocityTracker::new();
+/// // on every pointer move, with the time since the gesture began
+/// tracker.add(Duration::from_millis(0), 10.0);
+/// tracker.add(Duration::from_millis(16), 26.0);
+/// tracker.add(Duration::from_millis(32), 42.0);
+/// // on release
+/// let fling = Decay::new(42.0, tracker.velocity());
+/// ```
+#[derive(Debug, Clone)]
+pub struct VelocityTracker {
+    /// ring buffer of (time, value)
+    samples: [(Duration, f64); HISTORY],
+    len: usize,
+    head: usize,
+    horizon: Duration,
+    stop: Duration,
+}
+
+impl VelocityTracker {
+    /// tracks the samples of recent 100ms
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            samples: [(Duration::from_secs(0), 0.0); HISTORY],
+            len: 0,
+            head: 0,
+            horizon: Duration::from_millis(100),
+            stop: Duration::from_millis(40),
+        }
+    }
+
+    /// only samples within this period before the latest one are used; default 100ms
+    #[inline]
+    pub fn horizon(mut self, horizon: Duration) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// the value is assumed to have stopped if no sample comes within this period,
+    /// so older samples are ignored; default 40ms
+    #[inline]
+    pub fn stop(mut self, stop: Duration) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// add a sample of `value` at `time`; if `time` goes backwards, previous samples are dropped
+    #[inline]
+    pub fn add(&mut self, time: Duration, value: f64) {
+        if self.len > 0 && time < self.samples[self.head].0 {
+            self.reset();
+        }
+        self.head = (self.head + 1) % HISTORY;
+        self.samples[self.head] = (time, value);
+        self.len = (self.len + 1).min(HISTORY);
+    }
+
+    /// drop all samples
+    #[inline]
+    pub fn reset(&mut self) {
+        self.len = 0;
+    }
+
+    /// estimated velocity per second; zero if there are not enough samples
+    pub fn velocity(&self) -> f64 {
+        let (latest, _) = match self.iter().next() {
+            Some(sample) => sample,
+            None => return 0.0,
+        };
+        let mut prev = latest;
+        let samples = self.iter().take_while(|(time, _)| {
+            let recent = latest - *time <= self.horizon && prev - *time <= self.stop;
+            prev = *time;
+            recent
+        });
+        // least squares, relative to the latest sample for precision
+        let (mut n, mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for (time, value) in samples {
+            let x = -(latest - time).as_secs_f64();
+            n += 1.0;
+            sx += x;
+            sy += value;
+            sxx += x * x;
+            sxy += x * value;
+        }
+        let denominator = n * sxx - sx * sx;
+        if n < 2.0 || denominator <= 0.0 {
+            return 0.0;
+        }
+        (n * sxy - sx * sy) / denominator
+    }
+
+    /// samples from the latest to the oldest
+    #[inline]
+    fn iter(&self) -> impl Iterator<Item = (Duration, f64)> + '_ {
+        (0..self.len).map(move |i| self.samples[(self.head + HISTORY - i) % HISTORY])
+    }
+}
+
+impl Default for VelocityTracker {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_velocity_tracker() {
+        let mut tracker = VelocityTracker::new();
+        assert_eq!(tracker.velocity(), 0.0);
+        tracker.add(Duration::from_millis(0), 0.0);
+        assert_eq!(tracker.velocity(), 0.0);
+        for i in 1..50 {
+            // 1000 per second, with jitter
+            let jitter = if i % 2 == 0 { 0.5 } else { -0.5 };
+            tracker.add(Duration::from_millis(i * 10), (i * 10) as f64 + jitter);
+        }
+        assert!((tracker.velocity() - 1000.0).abs() < 50.0);
+
+        // stopped for a while, then moves backwards
+        tracker.add(Duration::from_millis(1000), 490.0);
+        tracker.add(Duration::from_millis(1010), 480.0);
+        assert!((tracker.velocity() + 1000.0).abs() < 1e-6);
+
+        // time goes backwards
+        tracker.add(Duration::from_millis(0), 0.0);
+        assert_eq!(tracker.velocity(), 0.0);
+    }
+}
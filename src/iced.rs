@@ -4,8 +4,17 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use crate::core::Animatable;
-use iced_native::{Color, Point, Rectangle, Size, Vector};
+use crate::core::{
+    animatable::AxisEasing,
+    easing::{self, BoxFunction, Function},
+    math,
+    timeline::Status,
+    AnimContext, Animatable, Clock, DefaultClock, Interpolator, MaybeSend, MaybeSync, Options,
+    PathPoint, Timeline,
+};
+use core::{fmt, time::Duration};
+use iced_native::{Background, Color, Column, Element, Point, Rectangle, Row, Size, Vector};
+use std::{boxed::Box, vec::Vec};
 
 impl Animatable for Point {
     #[inline]
@@ -16,6 +25,14 @@ impl Animatable for Point {
     }
 }
 
+impl PathPoint for Point {
+    #[inline]
+    fn distance(&self, other: &Self) -> f64 {
+        let (dx, dy) = ((other.x - self.x) as f64, (other.y - self.y) as f64);
+        math::sqrt(dx * dx + dy * dy)
+    }
+}
+
 impl<T: Animatable> Animatable for Rectangle<T> {
     #[inline]
     fn animate(&self, to: &Self, time: f64) -> Self {
@@ -32,6 +49,30 @@ impl<T: Animatable> Animatable for Rectangle<T> {
     }
 }
 
+impl<X: Function, Y: Function> Interpolator<Point> for AxisEasing<X, Y> {
+    #[inline]
+    fn interpolate(&self, from: &Point, to: &Point, time: f64) -> Point {
+        let (x, y) = self.axes((&from.x, &from.y), (&to.x, &to.y), time);
+        Point::new(x, y)
+    }
+}
+
+impl<T: Animatable, X: Function, Y: Function> Interpolator<Vector<T>> for AxisEasing<X, Y> {
+    #[inline]
+    fn interpolate(&self, from: &Vector<T>, to: &Vector<T>, time: f64) -> Vector<T> {
+        let (x, y) = self.axes((&from.x, &from.y), (&to.x, &to.y), time);
+        Vector { x, y }
+    }
+}
+
+impl<T: Animatable, X: Function, Y: Function> Interpolator<Size<T>> for AxisEasing<X, Y> {
+    #[inline]
+    fn interpolate(&self, from: &Size<T>, to: &Size<T>, time: f64) -> Size<T> {
+        let (width, height) = self.axes((&from.width, &from.height), (&to.width, &to.height), time);
+        Size { width, height }
+    }
+}
+
 impl Animatable for Color {
     #[inline]
     fn animate(&self, to: &Self, time: f64) -> Self {
@@ -43,6 +84,525 @@ impl Animatable for Color {
     }
 }
 
+impl Animatable for Background {
+    #[inline]
+    fn animate(&self, to: &Self, time: f64) -> Self {
+        match (self, to) {
+            (Background::Color(a), Background::Color(b)) => Background::Color(a.animate(b, time)),
+        }
+    }
+}
+
+/// common style payload of boxed widgets, e.g. buttons and containers; map it into the style
+/// of your widget, see [`AnimatedStyle`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxStyle {
+    /// offset of the shadow
+    pub shadow_offset: Vector,
+    /// background; it changes abruptly from or to `None`
+    pub background: Option<Background>,
+    /// radius of the border
+    pub border_radius: f32,
+    /// width of the border
+    pub border_width: f32,
+    /// color of the border
+    pub border_color: Color,
+    /// color of the text
+    pub text_color: Color,
+}
+
+impl Default for BoxStyle {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            shadow_offset: Vector::default(),
+            background: None,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+            text_color: Color::BLACK,
+        }
+    }
+}
+
+impl Animatable for BoxStyle {
+    #[inline]
+    fn animate(&self, to: &Self, time: f64) -> Self {
+        Self {
+            shadow_offset: self.shadow_offset.animate(&to.shadow_offset, time),
+            background: self.background.animate(&to.background, time),
+            border_radius: self.border_radius.animate(&to.border_radius, time),
+            border_width: self.border_width.animate(&to.border_width, time),
+            border_color: self.border_color.animate(&to.border_color, time),
+            text_color: self.text_color.animate(&to.text_color, time),
+        }
+    }
+}
+
+/// transitions of widget styles, e.g. hover color fades; it starts from the current value
+/// whenever the target changes, even in the middle of a transition.
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{AnimatedStyle, BoxStyle};
+/// use iced_native::{Background, Color};
+///
+/// let normal = BoxStyle::default();
+/// let hovered = BoxStyle {
+///     background: Some(Background::Color(Color::from_rgb(0.9, 0.9, 1.0))),
+///     ..normal
+/// };
+/// let mut style: AnimatedStyle<BoxStyle> =
+///     AnimatedStyle::new(normal).duration(Duration::from_millis(150));
+/// // on mouse enter
+/// style.transition_to(hovered);
+/// // on every frame
+/// let status = style.update();
+/// let current = style.value();
+/// ```
+pub struct AnimatedStyle<T, C: Clock = DefaultClock> {
+    target: T,
+    duration: Duration,
+    easing: BoxFunction,
+    reduce_motion: bool,
+    /// see [`AnimatedStyle::new_in()`]
+    time_scale: f32,
+    timeline: Option<Timeline<T, C>>,
+}
+
+impl<T: Animatable + MaybeSend + 'static, C: Clock> AnimatedStyle<T, C> {
+    /// start with `style`
+    #[inline]
+    pub fn new(style: T) -> Self {
+        Self {
+            target: style,
+            duration: Duration::from_millis(200),
+            easing: Box::new(easing::linear()),
+            reduce_motion: false,
+            time_scale: 1.0,
+            timeline: None,
+        }
+    }
+
+    /// start with `style`, transitions follow the defaults of `ctx`, and durations set later are
+    /// scaled by its time scale; if `ctx` reduces motion, it changes styles immediately
+    #[inline]
+    pub fn new_in(ctx: &AnimContext, style: T) -> Self {
+        Self {
+            target: style,
+            duration: ctx.default_duration(),
+            easing: ctx.default_easing().clone(),
+            reduce_motion: ctx.reduces_motion(),
+            time_scale: ctx.current_time_scale(),
+            timeline: None,
+        }
+    }
+
+    /// duration of transitions, default 200ms
+    #[inline]
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = if self.time_scale == 1.0 {
+            duration
+        } else {
+            duration.div_f64(self.time_scale as f64)
+        };
+        self
+    }
+
+    /// ease function of transitions, default linear
+    #[inline]
+    pub fn easing(mut self, func: impl Function + Clone + MaybeSend + MaybeSync + 'static) -> Self {
+        self.easing = Box::new(func);
+        self
+    }
+
+    /// the style to transition to
+    #[inline]
+    pub fn target(&self) -> &T {
+        &self.target
+    }
+
+    /// transition from the current value to `style`
+    #[inline]
+    pub fn transition_to(&mut self, style: T) {
+        if self.reduce_motion {
+            self.set(style);
+            return;
+        }
+        let mut timeline: Timeline<T, C> = Options::new(self.value(), style.clone())
+            .easing(self.easing.clone())
+            .duration(self.duration)
+            .into();
+        timeline.begin();
+        self.timeline = Some(timeline);
+        self.target = style;
+    }
+
+    /// change to `style` immediately
+    #[inline]
+    pub fn set(&mut self, style: T) {
+        self.timeline = None;
+        self.target = style;
+    }
+
+    /// update the transition, returns [`Status::Idle`] if there is none
+    #[inline]
+    pub fn update(&mut self) -> Status {
+        match &mut self.timeline {
+            Some(timeline) => timeline.update(),
+            None => Status::Idle,
+        }
+    }
+
+    /// current value
+    #[inline]
+    pub fn value(&self) -> T {
+        match &self.timeline {
+            Some(timeline) => timeline.value(),
+            None => self.target.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, C: Clock> fmt::Debug for AnimatedStyle<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnimatedStyle")
+            .field("target", &self.target)
+            .field("duration", &self.duration)
+            .field("animating", &self.timeline.is_some())
+            .finish()
+    }
+}
+
+/// phase of a child of [`AnimatedColumn`] or [`AnimatedRow`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChildPhase {
+    /// it was added
+    Entering,
+    /// it's settled
+    Present,
+    /// it was reordered, it slides to its position
+    Moving,
+    /// it was removed, it's gone once the transition completes
+    Exiting,
+}
+
+/// transition of a child of [`AnimatedColumn`] or [`AnimatedRow`]; map it into the view of
+/// the child, e.g. its opacity, size or padding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChildTransition {
+    /// current phase
+    pub phase: ChildPhase,
+    /// 0 if it's absent, 1 if it's present; it grows while entering and shrinks while exiting
+    pub presence: f32,
+    /// how many slots it's displaced from its position, positive towards the end;
+    /// it shrinks to 0 while moving
+    pub offset: f32,
+}
+
+struct Child<K, C: Clock> {
+    key: K,
+    phase: ChildPhase,
+    /// presence while entering or exiting, offset while moving
+    timeline: Option<Timeline<f32, C>>,
+}
+
+impl<K, C: Clock> Child<K, C> {
+    #[inline]
+    fn transition(&self) -> ChildTransition {
+        let value = self.timeline.as_ref().map(|timeline| timeline.value());
+        let (presence, offset) = match self.phase {
+            ChildPhase::Entering | ChildPhase::Exiting => (value.unwrap_or(1.0), 0.0),
+            ChildPhase::Moving => (1.0, value.unwrap_or(0.0)),
+            ChildPhase::Present => (1.0, 0.0),
+        };
+        ChildTransition {
+            phase: self.phase,
+            presence,
+            offset,
+        }
+    }
+}
+
+/// a child placed by [`Children::sync()`]
+struct Slot<K, C: Clock> {
+    /// index before the sync
+    origin: Option<usize>,
+    /// kept its place in the order, so it may move
+    settled: bool,
+    child: Child<K, C>,
+}
+
+/// keyed children shared by [`AnimatedColumn`] and [`AnimatedRow`]
+struct Children<K, C: Clock> {
+    children: Vec<Child<K, C>>,
+    enter: Duration,
+    exit: Duration,
+    moving: Duration,
+    easing: BoxFunction,
+}
+
+impl<K: PartialEq, C: Clock> Children<K, C> {
+    #[inline]
+    fn new(keys: impl IntoIterator<Item = K>) -> Self {
+        let children = keys
+            .into_iter()
+            .map(|key| Child {
+                key,
+                phase: ChildPhase::Present,
+                timeline: None,
+            })
+            .collect();
+        Self {
+            children,
+            enter: Duration::from_millis(200),
+            exit: Duration::from_millis(200),
+            moving: Duration::from_millis(200),
+            easing: Box::new(easing::linear()),
+        }
+    }
+
+    #[inline]
+    fn transition(&self, from: f32, to: f32, duration: Duration) -> Option<Timeline<f32, C>> {
+        let mut timeline: Timeline<f32, C> = Options::new(from, to)
+            .easing(self.easing.clone())
+            .duration(duration)
+            .into();
+        timeline.begin();
+        Some(timeline)
+    }
+
+    fn sync(&mut self, keys: impl IntoIterator<Item = K>) {
+        let mut old: Vec<_> = core::mem::take(&mut self.children)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut next: Vec<Slot<K, C>> = Vec::new();
+        for key in keys {
+            let found = old
+                .iter()
+                .position(|child| matches!(child, Some(child) if child.key == key));
+            let slot = match found {
+                Some(origin) => {
+                    let mut child = old[origin].take().unwrap();
+                    let settled = match child.phase {
+                        ChildPhase::Exiting => {
+                            // it's back, enter from where it is
+                            let presence = child.transition().presence;
+                            child.phase = ChildPhase::Entering;
+                            child.timeline = self.transition(presence, 1.0, self.enter);
+                            false
+                        }
+                        ChildPhase::Entering => false,
+                        ChildPhase::Present | ChildPhase::Moving => true,
+                    };
+                    Slot {
+                        origin: Some(origin),
+                        settled,
+                        child,
+                    }
+                }
+                None => Slot {
+                    origin: None,
+                    settled: false,
+                    child: Child {
+                        key,
+                        phase: ChildPhase::Entering,
+                        timeline: self.transition(0.0, 1.0, self.enter),
+                    },
+                },
+            };
+            next.push(slot);
+        }
+        // removed children stay right after their previous neighbors until they're gone
+        for (origin, child) in old.into_iter().enumerate() {
+            if let Some(mut child) = child {
+                if child.phase != ChildPhase::Exiting {
+                    let presence = child.transition().presence;
+                    child.phase = ChildPhase::Exiting;
+                    child.timeline = self.transition(presence, 0.0, self.exit);
+                }
+                let idx = origin
+                    .checked_sub(1)
+                    .and_then(|prev| next.iter().position(|slot| slot.origin == Some(prev)))
+                    .map_or(0, |idx| idx + 1);
+                let slot = Slot {
+                    origin: Some(origin),
+                    settled: false,
+                    child,
+                };
+                next.insert(idx, slot);
+            }
+        }
+        // only the order of settled children matters, insertions and removals are not moves
+        let mut ranks: Vec<_> = next
+            .iter()
+            .filter(|slot| slot.settled)
+            .filter_map(|slot| slot.origin)
+            .collect();
+        ranks.sort_unstable();
+        let mut rank = 0;
+        for slot in next.iter_mut() {
+            if !slot.settled {
+                continue;
+            }
+            let old_rank = slot
+                .origin
+                .and_then(|origin| ranks.binary_search(&origin).ok())
+                .unwrap_or(rank);
+            if old_rank != rank {
+                let offset = slot.child.transition().offset + (old_rank as f32 - rank as f32);
+                slot.child.phase = ChildPhase::Moving;
+                slot.child.timeline = self.transition(offset, 0.0, self.moving);
+            }
+            rank += 1;
+        }
+        self.children = next.into_iter().map(|slot| slot.child).collect();
+    }
+
+    fn update(&mut self) -> Status {
+        let mut status = Status::Idle;
+        for child in self.children.iter_mut() {
+            if let Some(timeline) = &mut child.timeline {
+                if timeline.update().is_completed() {
+                    child.timeline = None;
+                    if child.phase != ChildPhase::Exiting {
+                        child.phase = ChildPhase::Present;
+                    }
+                } else {
+                    status = Status::Animating;
+                }
+            }
+        }
+        self.children
+            .retain(|child| child.phase != ChildPhase::Exiting || child.timeline.is_some());
+        status
+    }
+}
+
+impl<K: fmt::Debug, C: Clock> fmt::Debug for Children<K, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.children.iter().map(|child| (&child.key, child.phase)))
+            .finish()
+    }
+}
+
+macro_rules! animated_children {
+    ($(#[$meta:meta])* $name:ident, $widget:ident) => {
+        $(#[$meta])*
+        pub struct $name<K, C: Clock = DefaultClock>(Children<K, C>);
+
+        impl<K: PartialEq, C: Clock> $name<K, C> {
+            /// start with children of `keys`, which are present
+            #[inline]
+            pub fn new(keys: impl IntoIterator<Item = K>) -> Self {
+                Self(Children::new(keys))
+            }
+
+            /// duration of enter transitions, default 200ms
+            #[inline]
+            pub fn enter(mut self, duration: Duration) -> Self {
+                self.0.enter = duration;
+                self
+            }
+
+            /// duration of exit transitions, default 200ms
+            #[inline]
+            pub fn exit(mut self, duration: Duration) -> Self {
+                self.0.exit = duration;
+                self
+            }
+
+            /// duration of move transitions, default 200ms
+            #[inline]
+            pub fn moving(mut self, duration: Duration) -> Self {
+                self.0.moving = duration;
+                self
+            }
+
+            /// ease function of transitions, default linear
+            #[inline]
+            pub fn easing(mut self, func: impl Function + Clone + MaybeSend + MaybeSync + 'static) -> Self {
+                self.0.easing = Box::new(func);
+                self
+            }
+
+            /// the keys of the current view, in order; added children enter, removed ones
+            /// exit and reordered ones move. keys are compared one by one, it suits short lists
+            #[inline]
+            pub fn sync(&mut self, keys: impl IntoIterator<Item = K>) {
+                self.0.sync(keys)
+            }
+
+            /// update the transitions, returns [`Status::Idle`] if there is none
+            #[inline]
+            pub fn update(&mut self) -> Status {
+                self.0.update()
+            }
+
+            /// all the children, including exiting ones, in order
+            #[inline]
+            pub fn children(&self) -> impl Iterator<Item = (&K, ChildTransition)> {
+                self.0
+                    .children
+                    .iter()
+                    .map(|child| (&child.key, child.transition()))
+            }
+
+            /// build the widget by the view of every child
+            #[inline]
+            pub fn view<'a, Message, Renderer>(
+                &self,
+                mut view: impl FnMut(&K, ChildTransition) -> Element<'a, Message, Renderer>,
+            ) -> $widget<'a, Message, Renderer> {
+                let children = self.children().map(|(key, transition)| view(key, transition));
+                $widget::with_children(children.collect())
+            }
+        }
+
+        impl<K: fmt::Debug, C: Clock> fmt::Debug for $name<K, C> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.0).finish()
+            }
+        }
+    };
+}
+
+animated_children!(
+    /// a [`Column`] of keyed children, which animates children entering, exiting and moving
+    /// between views; call [`AnimatedColumn::sync()`] with the keys of every view.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use anim::AnimatedColumn;
+    /// use iced_native::{renderer::Null, Column, Text};
+    ///
+    /// let mut list: AnimatedColumn<u32> =
+    ///     AnimatedColumn::new(vec![1, 2, 3]).exit(Duration::from_millis(300));
+    /// // 2 is removed, 4 is added
+    /// list.sync(vec![1, 3, 4]);
+    /// // on every frame
+    /// let status = list.update();
+    /// let column: Column<(), Null> = list.view(|key, transition| {
+    ///     Text::new(key.to_string())
+    ///         .size((20.0 * transition.presence) as u16)
+    ///         .into()
+    /// });
+    /// ```
+    AnimatedColumn,
+    Column
+);
+
+animated_children!(
+    /// a [`Row`] of keyed children, which animates children entering, exiting and moving
+    /// between views; see [`AnimatedColumn`]
+    AnimatedRow,
+    Row
+);
+
 impl<T: Animatable> Animatable for Size<T> {
     #[inline]
     fn animate(&self, to: &Self, time: f64) -> Self {
@@ -60,3 +620,83 @@ impl<T: Animatable> Animatable for Vector<T> {
         Vector { x, y }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::MockClock as TestClock;
+
+    #[test]
+    fn test_animated_style() {
+        let at = Duration::from_millis;
+        let normal = BoxStyle::default();
+        let hovered = BoxStyle {
+            background: Some(Background::Color(Color::WHITE)),
+            border_width: 2.0,
+            ..normal
+        };
+        let mut style: AnimatedStyle<BoxStyle, TestClock> = AnimatedStyle::new(BoxStyle {
+            background: Some(Background::Color(Color::BLACK)),
+            ..normal
+        })
+        .duration(at(100));
+        assert_eq!(style.update(), Status::Idle);
+
+        style.transition_to(hovered);
+        assert_eq!(style.update(), Status::Animating);
+        let value = style.value();
+        assert_eq!(value.border_width, 0.0);
+        assert_eq!(value.background, Some(Background::Color(Color::BLACK)));
+
+        style.set(normal);
+        assert_eq!(style.value(), normal);
+        assert_eq!(style.target(), &normal);
+
+        let ctx = AnimContext::default().reduce_motion(true);
+        let mut style: AnimatedStyle<BoxStyle, TestClock> = AnimatedStyle::new_in(&ctx, normal);
+        style.transition_to(hovered);
+        assert_eq!(style.update(), Status::Idle);
+        assert_eq!(style.value(), hovered);
+    }
+
+    #[test]
+    fn test_animated_column() {
+        let snapshot = |list: &AnimatedColumn<u32, TestClock>| {
+            list.children()
+                .map(|(key, t)| (*key, t.phase, t.presence, t.offset))
+                .collect::<Vec<_>>()
+        };
+        let mut list: AnimatedColumn<u32, TestClock> = AnimatedColumn::new(vec![1, 2, 3]);
+        assert_eq!(list.update(), Status::Idle);
+
+        list.sync(vec![3, 1, 4]);
+        assert_eq!(list.update(), Status::Animating);
+        assert_eq!(
+            snapshot(&list),
+            vec![
+                (3, ChildPhase::Moving, 1.0, 1.0),
+                (1, ChildPhase::Moving, 1.0, -1.0),
+                (2, ChildPhase::Exiting, 1.0, 0.0),
+                (4, ChildPhase::Entering, 0.0, 0.0),
+            ]
+        );
+
+        // it's back before it's gone
+        list.sync(vec![3, 1, 2, 4]);
+        assert_eq!(snapshot(&list)[2], (2, ChildPhase::Entering, 1.0, 0.0));
+
+        let mut list: AnimatedColumn<u32, TestClock> = AnimatedColumn::new(vec![1, 2, 3])
+            .enter(Duration::from_millis(0))
+            .exit(Duration::from_millis(0))
+            .moving(Duration::from_millis(0));
+        list.sync(vec![4, 3]);
+        assert_eq!(list.update(), Status::Idle);
+        assert_eq!(
+            snapshot(&list),
+            vec![
+                (4, ChildPhase::Present, 1.0, 0.0),
+                (3, ChildPhase::Present, 1.0, 0.0),
+            ]
+        );
+    }
+}
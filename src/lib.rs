@@ -34,7 +34,7 @@ Note: `anim` turns on `iced-backend` feature by default. You need to disable def
 
 ```toml
 [dependencies]
-anim = { version="0.1", default-features = false }
+anim = { version="0.1", default-features = false, features = ["std"] }
 ```
 
 ## How to use?
@@ -78,11 +78,50 @@ loop {
 
 For complex scenarios, please look at [examples](https://github.com/Joylei/anim-rs/tree/master/examples/) to gain some ideas.
 
+## `no_std` support
+
+`anim` works with `no_std + alloc` if you turn off the default features. You need to supply your own [`Clock`] to construct a [`Timeline`] in this case.
+
+```toml
+[dependencies]
+anim = { version="0.1", default-features = false }
+```
+
+## Bevy
+
+Turn on `bevy-backend` feature to animate `bevy` types, e.g. `Vec3`, `Quat` and `Transform`; attach timelines to entities by `TimelineComponent` and add a `TimelinePlugin` to tick them every frame.
+
+## Diagnostics
+
+Turn on `tracing` feature to get structured [tracing](https://docs.rs/tracing) events about timelines, key-frames and the thread local scheduler.
+
+## Asset files
+
+Turn on `serde` feature to load [`Options`] and [`KeyFrame`]s from asset files, e.g. RON or JSON, instead of hardcoding them; ease functions are named by [`easing::Builtin`].
+
+## Testing
+
+Turn on `testing` feature to get `anim::test` helpers, e.g. `assert_samples!` for golden sampling and a deterministic mock clock timeline harness, and `anim::timeline::test_support::advance()` to step timelines without sleeping.
+
+Turn on `test-vectors` feature to get `anim::easing::vectors`, reference samples of all the built-in ease functions, to check them by `anim::easing::sample()`.
+
 
 */
 
 #![warn(missing_docs)]
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+#[macro_use]
+extern crate alloc;
+
+#[macro_use]
+mod trace;
 
+/// bevy animation backend
+#[cfg(feature = "bevy-backend")]
+mod bevy;
 mod core;
 /// iced animation backend
 #[cfg(feature = "iced-backend")]
@@ -90,8 +129,12 @@ mod iced;
 /// thread local based timeline
 #[cfg(feature = "local")]
 pub mod local;
+#[cfg(any(test, feature = "testing"))]
+pub mod test;
 
 // reexports
+#[cfg(feature = "bevy-backend")]
+pub use crate::bevy::*;
 pub use crate::core::*;
 #[cfg(feature = "iced-backend")]
 pub use crate::iced::*;
@@ -8,10 +8,19 @@ use super::timeline::{Timeline, TimelineEx};
 use crate::{
     core::timeline::Timeline as CoreTimeline,
     timeline::{Status, TimelineId},
-    Animation,
+    Animation, FrameReport, FrameScheduler, MaybeSend, Priority,
 };
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    boxed::Box,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    string::String,
+    thread_local,
+    time::Duration,
+    vec::Vec,
+};
 
 thread_local! {
     /// thread local manager
@@ -23,7 +32,7 @@ thread_local! {
 /// build a thread-local based [`Timeline`], which attaches to current thread once created
 pub fn timeline<F>(animation: F) -> Timeline<F::Item>
 where
-    F: Animation + 'static,
+    F: Animation + MaybeSend + 'static,
 {
     let timeline: CoreTimeline<_> = CoreTimeline::new(animation);
     let shared = MANAGER.with(|m| m.shared.clone());
@@ -39,6 +48,62 @@ pub fn update() {
     MANAGER.with(|m| m.update());
 }
 
+/// update only the given current thread associated [`Timeline`]s, so parts of the ui can be
+/// advanced independently, e.g. live HUD animations while the game world is paused;
+/// the others keep their time, see [`Timeline::id()`]
+#[inline]
+pub fn update_only(ids: &[TimelineId]) {
+    MANAGER.with(|m| m.shared.update_where(|id, _| ids.contains(id)));
+}
+
+/// update only current thread associated [`Timeline`]s tagged with `tag`,
+/// see [`Timeline::set_tag()`], [`update_only()`]
+#[inline]
+pub fn update_tag(tag: &str) {
+    MANAGER.with(|m| m.shared.update_where(|_, item| item.has_tag(tag)));
+}
+
+/// change how fast the time passes for all current thread associated [`Timeline`]s,
+/// e.g. 0.1 for slow motion debugging, 2.0 for a turbo mode, 0 to freeze;
+/// panics if `scale` is negative or not finite.
+///
+/// see [`crate::Timeline::set_time_scale()`]
+#[inline]
+pub fn set_time_scale(scale: f32) {
+    MANAGER.with(|m| m.shared.set_time_scale(scale));
+}
+
+/// the time scale of current thread associated [`Timeline`]s, default 1.0
+#[inline]
+pub fn time_scale() -> f32 {
+    MANAGER.with(|m| m.shared.1.get())
+}
+
+/// freeze the time of all current thread associated [`Timeline`]s while the app is hidden or
+/// unfocused, then continue without a time jump.
+///
+/// see [`crate::Timeline::set_suspended()`]
+#[inline]
+pub fn set_suspended(suspended: bool) {
+    MANAGER.with(|m| m.shared.set_suspended(suspended));
+}
+
+/// is the time frozen for current thread associated [`Timeline`]s?
+#[inline]
+pub fn is_suspended() -> bool {
+    MANAGER.with(|m| m.shared.2.get())
+}
+
+/// update current thread associated [`Timeline`]s within the time `budget`;
+/// once it's exceeded, timelines of lower [`Priority`] are deferred to the next update.
+/// deferred timelines are updated earlier in the next updates, so none of them starve.
+///
+/// see [`Timeline::set_priority()`], [`FrameScheduler`]
+#[inline]
+pub fn update_within(budget: Duration) -> FrameReport {
+    MANAGER.with(|m| m.update_within(budget))
+}
+
 pub(crate) struct TimelineWrapper<T> {
     id: TimelineId,
     pub(crate) inner: Rc<Mutex<Inner<T>>>,
@@ -48,6 +113,8 @@ pub(crate) struct TimelineWrapper<T> {
 pub(crate) struct Inner<T> {
     pub(crate) timeline: CoreTimeline<T>,
     scheduled: bool,
+    priority: Priority,
+    tag: Option<String>,
 }
 
 impl<T> TimelineWrapper<T> {
@@ -58,6 +125,8 @@ impl<T> TimelineWrapper<T> {
             inner: Rc::new(Mutex::new(Inner {
                 timeline,
                 scheduled: false,
+                priority: Priority::default(),
+                tag: None,
             })),
             shared,
         }
@@ -70,6 +139,11 @@ impl<T> TimelineWrapper<T> {
 }
 
 impl<T: 'static> TimelineEx<T> for TimelineWrapper<T> {
+    #[inline]
+    fn id(&self) -> TimelineId {
+        self.id
+    }
+
     #[inline]
     fn status(&self) -> Status {
         let state = &*self.inner.lock();
@@ -126,15 +200,26 @@ impl<T: 'static> TimelineEx<T> for TimelineWrapper<T> {
         let state = &mut *self.inner.lock();
         state.timeline.reset();
     }
+
+    #[inline]
+    fn set_priority(&self, priority: Priority) {
+        let state = &mut *self.inner.lock();
+        state.priority = priority;
+    }
+
+    #[inline]
+    fn set_tag(&self, tag: Option<String>) {
+        let state = &mut *self.inner.lock();
+        state.tag = tag;
+    }
 }
 
 impl<T> Drop for TimelineWrapper<T> {
     fn drop(&mut self) {
         let id = self.id;
         let scheduled = self.scheduled();
-        //dbg!(Rc::strong_count(&self.inner));
         if scheduled && Rc::strong_count(&self.inner) == 2 {
-            //eprintln!("drop TimelineWrapper");
+            trace_event!(trace, ?id, "drop scheduled timeline");
             self.shared.cancel(id);
         }
     }
@@ -153,6 +238,18 @@ trait TimelineControl {
     /// update timeline
     fn update(&self) -> Status;
 
+    /// evaluation priority
+    fn priority(&self) -> Priority;
+
+    /// is it tagged with `tag`?
+    fn has_tag(&self, tag: &str) -> bool;
+
+    /// change time scale
+    fn set_time_scale(&self, scale: f32);
+
+    /// freeze or unfreeze the time
+    fn set_suspended(&self, suspended: bool);
+
     /// on schedule into [`TimelineScheduler`]
     fn on_schedule(&self);
 
@@ -173,6 +270,30 @@ impl<T> TimelineControl for Rc<Mutex<Inner<T>>> {
         state.timeline.update()
     }
 
+    #[inline]
+    fn priority(&self) -> Priority {
+        let state = &*self.lock();
+        state.priority
+    }
+
+    #[inline]
+    fn has_tag(&self, tag: &str) -> bool {
+        let state = &*self.lock();
+        state.tag.as_deref() == Some(tag)
+    }
+
+    #[inline]
+    fn set_time_scale(&self, scale: f32) {
+        let state = &mut *self.lock();
+        state.timeline.set_time_scale(scale);
+    }
+
+    #[inline]
+    fn set_suspended(&self, suspended: bool) {
+        let state = &mut *self.lock();
+        state.timeline.set_suspended(suspended);
+    }
+
     #[inline]
     fn on_schedule(&self) {
         let state = &mut *self.lock();
@@ -186,21 +307,73 @@ impl<T> TimelineControl for Rc<Mutex<Inner<T>>> {
     }
 }
 
+/// scheduled timelines, the time scale, suspension and the scheduler of [`update_within()`]
 #[derive(Clone)]
-struct Shared(Rc<RwLock<HashMap<TimelineId, Box<dyn TimelineControl + 'static>>>>);
+struct Shared(
+    Rc<RwLock<HashMap<TimelineId, Box<dyn TimelineControl + 'static>>>>,
+    Rc<Cell<f32>>,
+    Rc<Cell<bool>>,
+    Rc<RefCell<FrameScheduler>>,
+);
 
 impl Shared {
     #[inline]
     fn update(&self) {
+        self.update_where(|_, _| true)
+    }
+
+    /// update scheduled timelines which match `filter`
+    #[inline]
+    fn update_where(&self, filter: impl Fn(&TimelineId, &dyn TimelineControl) -> bool) {
         let mut holder = Vec::new();
         let state = self.0.upgradable_read();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("update", timelines = state.len()).entered();
         for (id, item) in state.iter() {
+            if !filter(id, item.as_ref()) {
+                continue;
+            }
             let status = item.update();
             if status == Status::Completed || status == Status::Paused {
                 holder.push(*id);
             }
         }
+        Self::unschedule(state, holder);
+    }
+
+    #[inline]
+    fn update_within(&self, budget: Duration) -> FrameReport {
+        let mut holder = Vec::new();
+        let state = self.0.upgradable_read();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("update", timelines = state.len()).entered();
+        let mut scheduler = self.3.borrow_mut();
+        scheduler.set_budget(budget);
+        // the scheduler ages deferred timelines by their positions
+        let mut items: Vec<_> = state.iter().collect();
+        items.sort_by_key(|(id, _)| **id);
+        let report = scheduler.run(
+            &mut items,
+            |(_, item)| item.priority(),
+            |(id, item)| {
+                let status = item.update();
+                if status == Status::Completed || status == Status::Paused {
+                    holder.push(**id);
+                }
+            },
+        );
+        Self::unschedule(state, holder);
+        report
+    }
+
+    /// remove finished timelines
+    #[inline]
+    fn unschedule(
+        state: RwLockUpgradableReadGuard<'_, HashMap<TimelineId, Box<dyn TimelineControl>>>,
+        holder: Vec<TimelineId>,
+    ) {
         if !holder.is_empty() {
+            trace_event!(trace, finished = holder.len(), "unschedule timelines");
             let mut state = RwLockUpgradableReadGuard::upgrade(state);
             for id in holder {
                 state.remove(&id);
@@ -213,12 +386,40 @@ impl Shared {
         let id = timeline.id();
         let state = self.0.upgradable_read();
         if !state.contains_key(&id) {
+            trace_event!(trace, ?id, "schedule timeline");
+            timeline.set_time_scale(self.1.get());
+            timeline.set_suspended(self.2.get());
             timeline.on_schedule();
             let mut state = RwLockUpgradableReadGuard::upgrade(state);
             state.insert(id, Box::new(timeline));
         }
     }
 
+    #[inline]
+    fn set_time_scale(&self, scale: f32) {
+        assert!(
+            scale >= 0.0 && scale.is_finite(),
+            "invalid time scale: {}",
+            scale
+        );
+        trace_event!(debug, scale, "set time scale");
+        self.1.set(scale);
+        let state = self.0.read();
+        for item in state.values() {
+            item.set_time_scale(scale);
+        }
+    }
+
+    #[inline]
+    fn set_suspended(&self, suspended: bool) {
+        trace_event!(debug, suspended, "set suspended");
+        self.2.set(suspended);
+        let state = self.0.read();
+        for item in state.values() {
+            item.set_suspended(suspended);
+        }
+    }
+
     #[inline]
     fn cancel(&self, id: TimelineId) -> bool {
         let res = {
@@ -231,6 +432,7 @@ impl Shared {
             }
         };
         if let Some(ref item) = res {
+            trace_event!(trace, ?id, "cancel timeline");
             item.on_remove();
         }
         res.is_some()
@@ -244,7 +446,12 @@ struct Manager {
 impl Manager {
     fn new() -> Self {
         Self {
-            shared: Shared(Rc::new(RwLock::new(Default::default()))),
+            shared: Shared(
+                Rc::new(RwLock::new(Default::default())),
+                Rc::new(Cell::new(1.0)),
+                Rc::new(Cell::new(false)),
+                Rc::new(RefCell::new(FrameScheduler::new(Duration::from_secs(0)))),
+            ),
         }
     }
 
@@ -252,4 +459,9 @@ impl Manager {
     fn update(&self) {
         self.shared.update();
     }
+
+    #[inline]
+    fn update_within(&self, budget: Duration) -> FrameReport {
+        self.shared.update_within(budget)
+    }
 }
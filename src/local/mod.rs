@@ -7,15 +7,22 @@
 mod animator;
 mod timeline;
 
-use crate::core::{Animatable, Options};
+use crate::core::{easing, Animatable, MaybeSend, Options};
 #[doc(inline)]
-pub use animator::{timeline, update};
+pub use animator::{
+    is_suspended, set_suspended, set_time_scale, time_scale, timeline, update, update_only,
+    update_tag, update_within,
+};
 #[doc(inline)]
 pub use timeline::Timeline;
 
-impl<T: Animatable + 'static> From<Options<T>> for Timeline<T> {
+impl<T, F> From<Options<T, F>> for Timeline<T>
+where
+    T: Animatable + MaybeSend + 'static,
+    F: easing::Function + Clone + MaybeSend + 'static,
+{
     #[inline]
-    fn from(opt: Options<T>) -> Self {
+    fn from(opt: Options<T, F>) -> Self {
         timeline(opt.build())
     }
 }
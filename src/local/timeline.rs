@@ -4,9 +4,14 @@
 // Copyright: 2021, Joylei <leingliu@gmail.com>
 // License: MIT
 
-use crate::core::timeline::Status;
+use crate::core::{
+    timeline::{Status, TimelineId},
+    Priority,
+};
+use std::{boxed::Box, string::String};
 
 pub(crate) trait TimelineEx<T> {
+    fn id(&self) -> TimelineId;
     fn status(&self) -> Status;
     fn value(&self) -> T;
     fn begin(&self);
@@ -14,6 +19,8 @@ pub(crate) trait TimelineEx<T> {
     fn pause(&self);
     fn resume(&self);
     fn reset(&self);
+    fn set_priority(&self, priority: Priority);
+    fn set_tag(&self, tag: Option<String>);
 }
 
 /// thread local specialized timeline
@@ -25,6 +32,12 @@ impl<T> Timeline<T> {
         Self(Box::new(e))
     }
 
+    /// the unique id of the timeline, see [`crate::local::update_only()`]
+    #[inline]
+    pub fn id(&self) -> TimelineId {
+        self.0.id()
+    }
+
     /// timeline status
     ///
     /// see [`crate::timeline::Status`]
@@ -67,9 +80,26 @@ impl<T> Timeline<T> {
     pub fn reset(&mut self) {
         self.0.resume()
     }
+
+    /// evaluation priority under [`crate::local::update_within()`], default [`Priority::Normal`]
+    #[inline]
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.0.set_priority(priority)
+    }
+
+    /// tag the timeline, e.g. `"sidebar"`, to update it by [`crate::local::update_tag()`]
+    #[inline]
+    pub fn set_tag(&mut self, tag: impl Into<String>) {
+        self.0.set_tag(Some(tag.into()))
+    }
 }
 
 impl<T> TimelineEx<T> for Timeline<T> {
+    #[inline]
+    fn id(&self) -> TimelineId {
+        self.0.id()
+    }
+
     #[inline]
     fn status(&self) -> Status {
         self.0.status()
@@ -104,4 +134,14 @@ impl<T> TimelineEx<T> for Timeline<T> {
     fn reset(&self) {
         self.0.resume()
     }
+
+    #[inline]
+    fn set_priority(&self, priority: Priority) {
+        self.0.set_priority(priority)
+    }
+
+    #[inline]
+    fn set_tag(&self, tag: Option<String>) {
+        self.0.set_tag(tag)
+    }
 }
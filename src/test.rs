@@ -0,0 +1,257 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+//! helpers to test your animations, turn on `testing` feature to use them.
+//!
+//! ## Example
+//! ```rust
+//! use std::time::Duration;
+//! use anim::{assert_samples, easing, Options};
+//!
+//! let animation = Options::new(0.0, 1.0)
+//!     .duration(Duration::from_millis(1000))
+//!     .easing(easing::linear())
+//!     .build();
+//! assert_samples!(animation, Duration::from_millis(250), [0.0, 0.25, 0.5, 0.75, 1.0], 1e-6);
+//! ```
+
+use crate::{timeline::Status, Animation, Clock, MaybeSend, Timeline};
+use alloc::vec::Vec;
+use core::{fmt::Debug, time::Duration};
+
+/// a manual [`Clock`], its time only changes when you set it
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MockClock(pub Duration);
+
+impl MockClock {
+    /// move the time forward
+    #[inline]
+    pub fn advance(&mut self, duration: Duration) {
+        self.0 += duration;
+    }
+}
+
+impl Clock for MockClock {
+    type Time = Duration;
+
+    #[inline]
+    fn now(&self) -> Duration {
+        self.0
+    }
+
+    #[inline]
+    fn checked_elapsed(now: &Duration, since: &Duration) -> Option<Duration> {
+        now.checked_sub(*since)
+    }
+}
+
+/// approximate equality, see [`assert_samples!`]
+pub trait Approx {
+    /// are they equal within `epsilon`?
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool;
+}
+
+macro_rules! impl_approx {
+    ($($ty:ident),*) => {
+        $(
+            impl Approx for $ty {
+                #[inline]
+                fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+                    crate::core::math::abs(*self as f64 - *other as f64) <= epsilon
+                }
+            }
+        )*
+    };
+}
+
+impl_approx!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl Approx for bool {
+    #[inline]
+    fn approx_eq(&self, other: &Self, _epsilon: f64) -> bool {
+        self == other
+    }
+}
+
+impl Approx for char {
+    #[inline]
+    fn approx_eq(&self, other: &Self, _epsilon: f64) -> bool {
+        self == other
+    }
+}
+
+impl<T: Approx, const N: usize> Approx for [T; N] {
+    #[inline]
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.iter()
+            .zip(other.iter())
+            .all(|(a, b)| a.approx_eq(b, epsilon))
+    }
+}
+
+macro_rules! impl_approx_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: Approx),+> Approx for ($($name,)+) {
+            #[inline]
+            fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+                $(self.$index.approx_eq(&other.$index, epsilon))&&+
+            }
+        }
+    };
+}
+
+impl_approx_tuple!(A 0);
+impl_approx_tuple!(A 0, B 1);
+impl_approx_tuple!(A 0, B 1, C 2);
+impl_approx_tuple!(A 0, B 1, C 2, D 3);
+
+/// sample `animation` every `step`, starting from zero, `count` samples in total
+#[inline]
+pub fn samples<A: Animation>(animation: &A, step: Duration, count: usize) -> Vec<A::Item> {
+    (0..count)
+        .map(|i| animation.animate(step * i as u32))
+        .collect()
+}
+
+/// panics if the samples of `animation` every `step` are not equal to `expected` within `epsilon`;
+/// see [`assert_samples!`]
+#[track_caller]
+pub fn assert_samples<A>(animation: &A, step: Duration, expected: &[A::Item], epsilon: f64)
+where
+    A: Animation,
+    A::Item: Approx + Debug,
+{
+    let actual = samples(animation, step, expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(expected.iter()).enumerate() {
+        assert!(
+            actual.approx_eq(expected, epsilon),
+            "sample {} at {:?}: expected {:?}, got {:?}",
+            i,
+            step * i as u32,
+            expected,
+            actual
+        );
+    }
+}
+
+/// asserts the samples of an animation every `step`, starting from zero
+///
+/// usage: `assert_samples!(animation, step, [expected...], epsilon)`; `epsilon` defaults to `1e-6`
+#[macro_export]
+macro_rules! assert_samples {
+    ($animation:expr, $step:expr, [$($expected:expr),* $(,)?], $epsilon:expr) => {
+        $crate::test::assert_samples(&$animation, $step, &[$($expected),*], $epsilon)
+    };
+    ($animation:expr, $step:expr, [$($expected:expr),* $(,)?]) => {
+        $crate::assert_samples!($animation, $step, [$($expected),*], 1e-6)
+    };
+}
+
+/// drives a [`Timeline`] by a [`MockClock`] deterministically
+///
+/// ## Example
+/// ```rust
+/// use std::time::Duration;
+/// use anim::{test::Harness, Options};
+///
+/// let mut harness = Harness::new(Options::new(0.0, 1.0).duration(Duration::from_secs(1)).build());
+/// let values = harness.run(Duration::from_millis(250));
+/// assert_eq!(values.len(), 5);
+/// assert_eq!(values[4], 1.0);
+/// ```
+#[derive(Debug)]
+pub struct Harness<T> {
+    timeline: Timeline<T, MockClock>,
+}
+
+impl<T> Harness<T> {
+    /// create a started timeline of `animation`
+    #[inline]
+    pub fn new<A>(animation: A) -> Self
+    where
+        A: Animation<Item = T> + MaybeSend + 'static,
+    {
+        let mut timeline = Timeline::new(animation);
+        timeline.begin();
+        Self { timeline }
+    }
+
+    /// the underlying timeline
+    #[inline]
+    pub fn timeline(&mut self) -> &mut Timeline<T, MockClock> {
+        &mut self.timeline
+    }
+
+    /// move the time forward by `duration`, then update the timeline
+    #[inline]
+    pub fn step(&mut self, duration: Duration) -> (Status, T) {
+        self.timeline.clock_mut().advance(duration);
+        let status = self.timeline.update();
+        (status, self.timeline.value())
+    }
+
+    /// values from now on every `step`, until the timeline is no longer animating
+    ///
+    /// panics if the animation never finishes
+    pub fn run(&mut self, step: Duration) -> Vec<T> {
+        assert!(step > Duration::from_secs(0));
+        let mut values = vec![self.timeline.value()];
+        loop {
+            let (status, value) = self.step(step);
+            values.push(value);
+            if status != Status::Animating {
+                return values;
+            }
+            assert!(
+                values.len() <= 1_000_000,
+                "the animation does not seem to finish"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{easing, Options};
+
+    #[test]
+    fn test_assert_samples() {
+        let animation = Options::new((0.0, 10), (1.0, 14))
+            .duration(Duration::from_millis(1000))
+            .easing(easing::linear())
+            .build();
+        assert_samples!(
+            animation,
+            Duration::from_millis(250),
+            [(0.0, 10), (0.25, 11), (0.5, 12), (0.75, 13), (1.0, 14)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "sample 1")]
+    fn test_assert_samples_mismatch() {
+        let animation = Options::new(0.0, 1.0)
+            .duration(Duration::from_millis(1000))
+            .easing(easing::linear())
+            .build();
+        assert_samples!(animation, Duration::from_millis(500), [0.0, 0.4, 1.0], 0.01);
+    }
+
+    #[test]
+    fn test_harness() {
+        let animation = Options::new(0.0, 1.0)
+            .duration(Duration::from_millis(1000))
+            .easing(easing::linear())
+            .build();
+        let mut harness = Harness::new(animation);
+        let (status, value) = harness.step(Duration::from_millis(250));
+        assert_eq!(status, Status::Animating);
+        assert_eq!(value, 0.25);
+        let values = harness.run(Duration::from_millis(250));
+        assert_eq!(values, vec![0.25, 0.5, 0.75, 1.0]);
+    }
+}
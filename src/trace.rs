@@ -0,0 +1,15 @@
+// anim
+//
+// A framework independent animation library for rust, works nicely with Iced and the others
+// Copyright: 2021, Joylei <leingliu@gmail.com>
+// License: MIT
+
+/// emits a [`tracing`](https://docs.rs/tracing) event if `tracing` feature is on, otherwise it's a no-op
+///
+/// usage: `trace_event!(debug, id = self.id, "timeline begin");`
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}